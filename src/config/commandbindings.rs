@@ -17,10 +17,18 @@ pub struct CommandBindings {
     pub playlist: HashMap<KeyCode, HashMap<u8, String>>,
     pub channel_main: HashMap<KeyCode, HashMap<u8, String>>,
     pub channel_videos: HashMap<KeyCode, HashMap<u8, String>>,
+    pub channel_shorts: HashMap<KeyCode, HashMap<u8, String>>,
+    pub channel_streams: HashMap<KeyCode, HashMap<u8, String>>,
     pub channel_playlists: HashMap<KeyCode, HashMap<u8, String>>,
+    pub channel_podcasts: HashMap<KeyCode, HashMap<u8, String>>,
     pub watchhistory: HashMap<KeyCode, HashMap<u8, String>>,
     pub feed: HashMap<KeyCode, HashMap<u8, String>>,
     pub libray: HashMap<KeyCode, HashMap<u8, String>>,
+    pub queue: HashMap<KeyCode, HashMap<u8, String>>,
+    pub comments: HashMap<KeyCode, HashMap<u8, String>>,
+    pub related: HashMap<KeyCode, HashMap<u8, String>>,
+    pub localsearch: HashMap<KeyCode, HashMap<u8, String>>,
+    pub instances: HashMap<KeyCode, HashMap<u8, String>>,
 }
 
 impl Key for CommandBindings {
@@ -41,21 +49,41 @@ impl CommandBindings {
             Page::MainMenu(MainMenuPage::Popular) => get_command(key, &self.popular),
             Page::MainMenu(MainMenuPage::History) => get_command(key, &self.watchhistory),
             Page::MainMenu(MainMenuPage::Library) => get_command(key, &self.libray),
+            Page::MainMenu(MainMenuPage::Queue) => get_command(key, &self.queue),
             Page::Feed => get_command(key, &self.feed),
             Page::SingleItem(SingleItemPage::Video(_)) => get_command(key, &self.video),
-            Page::SingleItem(SingleItemPage::Playlist(_)) => get_command(key, &self.playlist),
+            Page::SingleItem(SingleItemPage::Playlist(_) | SingleItemPage::LocalPlaylist(_)) => {
+                get_command(key, &self.playlist)
+            }
             Page::ChannelDisplay(ChannelDisplayPage {
                 r#type: ChannelDisplayPageType::Main,
                 ..
             }) => get_command(key, &self.channel_main),
+            // search results are listed like the videos page
             Page::ChannelDisplay(ChannelDisplayPage {
-                r#type: ChannelDisplayPageType::Videos,
+                r#type: ChannelDisplayPageType::Videos | ChannelDisplayPageType::Search(_),
                 ..
             }) => get_command(key, &self.channel_videos),
+            Page::ChannelDisplay(ChannelDisplayPage {
+                r#type: ChannelDisplayPageType::Shorts,
+                ..
+            }) => get_command(key, &self.channel_shorts),
+            Page::ChannelDisplay(ChannelDisplayPage {
+                r#type: ChannelDisplayPageType::Streams,
+                ..
+            }) => get_command(key, &self.channel_streams),
             Page::ChannelDisplay(ChannelDisplayPage {
                 r#type: ChannelDisplayPageType::Playlists,
                 ..
             }) => get_command(key, &self.channel_playlists),
+            Page::ChannelDisplay(ChannelDisplayPage {
+                r#type: ChannelDisplayPageType::Podcasts,
+                ..
+            }) => get_command(key, &self.channel_podcasts),
+            Page::Comments(_) => get_command(key, &self.comments),
+            Page::Related(_) => get_command(key, &self.related),
+            Page::LocalSearch(_) => get_command(key, &self.localsearch),
+            Page::Instances => get_command(key, &self.instances),
         };
 
         if let Some(command) = command {
@@ -87,14 +115,30 @@ pub struct CommandBindingsSerde {
     pub channel_main: HashMap<KeyCodeSerde, HashMap<u8, String>>,
     #[serde(default = "channel_videos_default")]
     pub channel_videos: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "channel_shorts_default")]
+    pub channel_shorts: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "channel_streams_default")]
+    pub channel_streams: HashMap<KeyCodeSerde, HashMap<u8, String>>,
     #[serde(default = "channel_playlists_default")]
     pub channel_playlists: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "channel_podcasts_default")]
+    pub channel_podcasts: HashMap<KeyCodeSerde, HashMap<u8, String>>,
     #[serde(default = "watchhistory_default")]
     pub watchhistory: HashMap<KeyCodeSerde, HashMap<u8, String>>,
     #[serde(default = "feed_default")]
     pub feed: HashMap<KeyCodeSerde, HashMap<u8, String>>,
     #[serde(default = "library_default")]
     pub library: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "queue_default")]
+    pub queue: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "comments_default")]
+    pub comments: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "related_default")]
+    pub related: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "localsearch_default")]
+    pub localsearch: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "instances_default")]
+    pub instances: HashMap<KeyCodeSerde, HashMap<u8, String>>,
 }
 
 impl ConfigTrait for CommandBindingsSerde {
@@ -109,13 +153,21 @@ impl CommandBindingsSerde {
             popular: de_serde(self.popular)?,
             channel_main: de_serde(self.channel_main)?,
             channel_videos: de_serde(self.channel_videos)?,
+            channel_shorts: de_serde(self.channel_shorts)?,
+            channel_streams: de_serde(self.channel_streams)?,
             channel_playlists: de_serde(self.channel_playlists)?,
+            channel_podcasts: de_serde(self.channel_podcasts)?,
             playlist: de_serde(self.playlist)?,
             video: de_serde(self.video)?,
             trending: de_serde(self.trending)?,
             watchhistory: de_serde(self.watchhistory)?,
             feed: de_serde(self.feed)?,
             libray: de_serde(self.library)?,
+            queue: de_serde(self.queue)?,
+            comments: de_serde(self.comments)?,
+            related: de_serde(self.related)?,
+            localsearch: de_serde(self.localsearch)?,
+            instances: de_serde(self.instances)?,
         })
     }
 }
@@ -128,13 +180,21 @@ impl Default for CommandBindingsSerde {
             popular: popular_default(),
             channel_main: channel_main_default(),
             channel_videos: channel_videos_default(),
+            channel_shorts: channel_shorts_default(),
+            channel_streams: channel_streams_default(),
             channel_playlists: channel_playlists_default(),
+            channel_podcasts: channel_podcasts_default(),
             playlist: playlist_default(),
             video: video_default(),
             trending: trending_default(),
             watchhistory: watchhistory_default(),
             feed: feed_default(),
             library: library_default(),
+            queue: queue_default(),
+            comments: comments_default(),
+            related: related_default(),
+            localsearch: localsearch_default(),
+            instances: instances_default(),
         }
     }
 }
@@ -213,6 +273,8 @@ fn search_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
         (KeyCodeSerde::Char('a'), HashMap::from([(2, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video"))])),
         (KeyCodeSerde::Char('A'), HashMap::from([(1, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video --loop-playlist=inf --shuffle"))])),
         (KeyCodeSerde::Char('p'), HashMap::from([(2, format!("parrun mpv {Q}${{hover-url}}{Q}"))])),
+        (KeyCodeSerde::Char('w'), HashMap::from([(0, String::from("togglewatched ${hover-id}"))])),
+        (KeyCodeSerde::Char('W'), HashMap::from([(1, String::from("togglehidewatched"))])),
     ])
 }
 
@@ -233,9 +295,23 @@ fn channel_videos_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
         (KeyCodeSerde::Char('a'), HashMap::from([(2, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video"))])),
         (KeyCodeSerde::Char('A'), HashMap::from([(1, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video --loop-playlist=inf --shuffle"))])),
         (KeyCodeSerde::Char('p'), HashMap::from([(2, format!("parrun mpv {Q}${{hover-url}}{Q}"))])),
+        (KeyCodeSerde::Char('w'), HashMap::from([(0, String::from("togglewatched ${hover-id}"))])),
+        (KeyCodeSerde::Char('W'), HashMap::from([(1, String::from("togglehidewatched"))])),
     ])
 }
 
+fn channel_shorts_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    channel_videos_default()
+}
+
+fn channel_streams_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    channel_videos_default()
+}
+
+fn channel_podcasts_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    channel_playlists_default()
+}
+
 fn playlist_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
     HashMap::from([
         (KeyCodeSerde::Char('a'), HashMap::from([(2, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video"))])),
@@ -249,6 +325,8 @@ fn popular_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
         (KeyCodeSerde::Char('a'), HashMap::from([(2, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video"))])),
         (KeyCodeSerde::Char('A'), HashMap::from([(1, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video --loop-playlist=inf --shuffle"))])),
         (KeyCodeSerde::Char('p'), HashMap::from([(2, format!("parrun mpv {Q}${{hover-url}}{Q}"))])),
+        (KeyCodeSerde::Char('w'), HashMap::from([(0, String::from("togglewatched ${hover-id}"))])),
+        (KeyCodeSerde::Char('W'), HashMap::from([(1, String::from("togglehidewatched"))])),
     ])
 }
 
@@ -257,6 +335,8 @@ fn trending_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
         (KeyCodeSerde::Char('a'), HashMap::from([(2, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video"))])),
         (KeyCodeSerde::Char('A'), HashMap::from([(1, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video --loop-playlist=inf --shuffle"))])),
         (KeyCodeSerde::Char('p'), HashMap::from([(2, format!("parrun mpv {Q}${{hover-url}}{Q}"))])),
+        (KeyCodeSerde::Char('w'), HashMap::from([(0, String::from("togglewatched ${hover-id}"))])),
+        (KeyCodeSerde::Char('W'), HashMap::from([(1, String::from("togglehidewatched"))])),
     ])
 }
 
@@ -269,6 +349,7 @@ fn watchhistory_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
         (KeyCodeSerde::Char('a'), HashMap::from([(2, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video"))])),
         (KeyCodeSerde::Char('A'), HashMap::from([(1, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video --loop-playlist=inf --shuffle"))])),
         (KeyCodeSerde::Char('p'), HashMap::from([(2, format!("parrun mpv {Q}${{hover-url}}{Q}"))])),
+        (KeyCodeSerde::Char('w'), HashMap::from([(0, String::from("togglewatched ${hover-id}"))])),
     ])
 }
 
@@ -287,6 +368,14 @@ fn feed_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
             KeyCodeSerde::Char('p'),
             HashMap::from([(2, format!("parrun mpv {Q}${{hover-video-url}}{Q}"))]),
         ),
+        (
+            KeyCodeSerde::Char('w'),
+            HashMap::from([(0, String::from("togglewatched ${hover-video-id}"))]),
+        ),
+        (
+            KeyCodeSerde::Char('W'),
+            HashMap::from([(1, String::from("togglehidewatched"))]),
+        ),
     ])
 }
 
@@ -295,5 +384,59 @@ fn library_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
         (KeyCodeSerde::Char('a'), HashMap::from([(2, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video"))])),
         (KeyCodeSerde::Char('A'), HashMap::from([(1, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video --loop-playlist=inf --shuffle"))])),
         (KeyCodeSerde::Char('p'), HashMap::from([(2, format!("parrun mpv {Q}${{hover-url}}{Q}"))])),
+        (KeyCodeSerde::Char('w'), HashMap::from([(0, String::from("togglewatched ${hover-id}"))])),
+    ])
+}
+
+fn queue_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (
+            KeyCodeSerde::Char('d'),
+            HashMap::from([(0, String::from("queue rm ${hover-id}"))]),
+        ),
+        (
+            KeyCodeSerde::Char('n'),
+            HashMap::from([(0, String::from("queue next"))]),
+        ),
+        (
+            KeyCodeSerde::Char('s'),
+            HashMap::from([(0, String::from("queue shuffle"))]),
+        ),
+    ])
+}
+
+fn comments_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::new()
+}
+
+fn related_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (KeyCodeSerde::Char('a'), HashMap::from([(2, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video"))])),
+        (KeyCodeSerde::Char('A'), HashMap::from([(1, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video --loop-playlist=inf --shuffle"))])),
+        (KeyCodeSerde::Char('p'), HashMap::from([(2, format!("parrun mpv {Q}${{hover-url}}{Q}"))])),
+        (KeyCodeSerde::Char('w'), HashMap::from([(0, String::from("togglewatched ${hover-id}"))])),
+        (KeyCodeSerde::Char('W'), HashMap::from([(1, String::from("togglehidewatched"))])),
+    ])
+}
+
+fn localsearch_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (KeyCodeSerde::Char('a'), HashMap::from([(2, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video"))])),
+        (KeyCodeSerde::Char('A'), HashMap::from([(1, format!("parrun ${{terminal-emulator}} mpv {Q}${{hover-url}}{Q} --no-video --loop-playlist=inf --shuffle"))])),
+        (KeyCodeSerde::Char('p'), HashMap::from([(2, format!("parrun mpv {Q}${{hover-url}}{Q}"))])),
+        (KeyCodeSerde::Char('w'), HashMap::from([(0, String::from("togglewatched ${hover-id}"))])),
+    ])
+}
+
+fn instances_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (
+            KeyCodeSerde::Char('p'),
+            HashMap::from([(0, String::from("instances probe"))]),
+        ),
+        (
+            KeyCodeSerde::Char('u'),
+            HashMap::from([(0, String::from("instances unpin"))]),
+        ),
     ])
 }
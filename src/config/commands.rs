@@ -13,6 +13,7 @@ pub struct CommandsConfig {
     pub saved_video: Vec<(String, String)>,
     pub playlist: Vec<(String, String)>,
     pub saved_playlist: Vec<(String, String)>,
+    pub local_playlist: Vec<(String, String)>,
     pub channel: Vec<(String, String)>,
 }
 
@@ -44,6 +45,11 @@ impl From<CommandsConfigSerde> for CommandsConfig {
                 .into_iter()
                 .map(|hashmap| hashmap.into_iter().last().unwrap())
                 .collect(),
+            local_playlist: original
+                .local_playlist
+                .into_iter()
+                .map(|hashmap| hashmap.into_iter().last().unwrap())
+                .collect(),
             channel: original
                 .channel
                 .into_iter()
@@ -67,6 +73,8 @@ pub struct CommandsConfigSerde {
     pub playlist: Vec<HashMap<String, String>>,
     #[serde(default = "saved_playlist_default")]
     pub saved_playlist: Vec<HashMap<String, String>>,
+    #[serde(default = "local_playlist_default")]
+    pub local_playlist: Vec<HashMap<String, String>>,
     #[serde(default = "channel_default")]
     pub channel: Vec<HashMap<String, String>>,
 }
@@ -83,6 +91,7 @@ impl Default for CommandsConfigSerde {
             saved_video: saved_video_default(),
             playlist: playlist_default(),
             saved_playlist: saved_playlist_default(),
+            local_playlist: local_playlist_default(),
             channel: channel_default(),
         }
     }
@@ -115,24 +124,56 @@ fn video_default() -> Vec<HashMap<String, String>> {
         )]),
         HashMap::from([(
             String::from("Play video"),
-            format!("parrun ${{video-player}} {q}${{embed-url}}{q}"),
+            format!("parrun ${{video-player}} {q}${{play-url}}{q} ${{resume-args}}"),
+        )]),
+        HashMap::from([(
+            String::from("Play video (with captions)"),
+            format!("captions ${{id}} ;; parrun ${{video-player}} {q}${{play-url}}{q} --sub-file={q}${{caption-path}}{q} ${{resume-args}}"),
         )]),
         HashMap::from([(
             String::from("Play audio"),
-            format!("mpv stop ;; resume ;; mpv sprop loop-file no ;; mpv loadfile {q}${{embed-url}}{q} ;; echo mpv Player started"),
+            format!("mpv stop ;; resume ;; mpv sprop loop-file no ;; mpv loadfile {q}${{play-url}}{q} ;; echo mpv Player started"),
         )]),
         HashMap::from([(
             String::from("Play audio (loop)"),
-            format!("mpv stop ;; resume ;; mpv sprop loop-file inf ;; mpv loadfile {q}${{embed-url}}{q} ;; echo mpv Player started"),
+            format!("mpv stop ;; resume ;; mpv sprop loop-file inf ;; mpv loadfile {q}${{play-url}}{q} ;; echo mpv Player started"),
+        )]),
+        HashMap::from([(
+            String::from("Add to queue"),
+            String::from("queue add ${id}"),
         )]),
         HashMap::from([(
             String::from("View channel"),
             String::from("channel ${channel-id}"),
         )]),
+        HashMap::from([(
+            String::from("View comments"),
+            String::from("comments ${id}"),
+        )]),
+        HashMap::from([(
+            String::from("View related videos"),
+            String::from("related ${id}"),
+        )]),
+        HashMap::from([(
+            String::from("View mix"),
+            String::from("playlist RD${id}"),
+        )]),
+        HashMap::from([(
+            String::from("Play mix in queue"),
+            String::from("queue addnext RD${id} ;; queue next"),
+        )]),
+        HashMap::from([(
+            String::from("View transcript"),
+            String::from("%switch-view%"),
+        )]),
         HashMap::from([(
             String::from("Subscribe to channel"),
             String::from("sync ${channel-id}"),
         )]),
+        HashMap::from([(
+            String::from("Block channel"),
+            String::from("block channel ${channel-id}"),
+        )]),
         HashMap::from([(
             String::from("Open in browser"),
             format!("parrun ${{browser}} {q}${{url}}{q}"),
@@ -141,6 +182,10 @@ fn video_default() -> Vec<HashMap<String, String>> {
             String::from("Toggle bookmark"),
             String::from("togglemark ${id}")
         )]),
+        HashMap::from([(
+            String::from("Toggle watched"),
+            String::from("togglewatched ${id}")
+        )]),
         HashMap::from([(
             String::from("Save video to library"),
             format!("bookmark ${{id}} ;; {} ;; parrun ${{terminal-emulator}} ${{youtube-downloader}} {q}${{embed-url}}{q} -o {q}${{save-path}}%(title)s[${{id}}].%(ext)s{q}", rm_cmd("${save-path}${id}.*"))
@@ -184,14 +229,42 @@ fn saved_video_default() -> Vec<HashMap<String, String>> {
             String::from("[Offline] Play saved file (audio loop)"),
             format!("mpv stop ;; resume ;; mpv sprop loop-file inf ;; mpv loadfile {q}${{offline-path}}{q} ;; echo mpv Player started"),
         )]),
+        HashMap::from([(
+            String::from("Add to queue"),
+            String::from("queue add ${id}"),
+        )]),
         HashMap::from([(
             String::from("View channel"),
             String::from("channel ${channel-id}"),
         )]),
+        HashMap::from([(
+            String::from("View comments"),
+            String::from("comments ${id}"),
+        )]),
+        HashMap::from([(
+            String::from("View related videos"),
+            String::from("related ${id}"),
+        )]),
+        HashMap::from([(
+            String::from("View mix"),
+            String::from("playlist RD${id}"),
+        )]),
+        HashMap::from([(
+            String::from("Play mix in queue"),
+            String::from("queue addnext RD${id} ;; queue next"),
+        )]),
+        HashMap::from([(
+            String::from("View transcript"),
+            String::from("%switch-view%"),
+        )]),
         HashMap::from([(
             String::from("Subscribe to channel"),
             String::from("sync ${channel-id}"),
         )]),
+        HashMap::from([(
+            String::from("Block channel"),
+            String::from("block channel ${channel-id}"),
+        )]),
         HashMap::from([(
             String::from("Open in browser"),
             format!("parrun ${{browser}} {q}${{url}}{q}"),
@@ -200,6 +273,10 @@ fn saved_video_default() -> Vec<HashMap<String, String>> {
             String::from("Toggle bookmark"),
             String::from("togglemark ${id}")
         )]),
+        HashMap::from([(
+            String::from("Toggle watched"),
+            String::from("togglewatched ${id}")
+        )]),
         HashMap::from([(
             String::from("Redownload video to library"),
             format!("bookmark ${{id}} ;; {} ;; parrun ${{terminal-emulator}} ${{youtube-downloader}} ${{embed-url}} -o {q}${{save-path}}%(title)s[${{id}}].%(ext)s{q}", rm_cmd("${save-path}*${id}*.*")),
@@ -239,6 +316,10 @@ fn playlist_default() -> Vec<HashMap<String, String>> {
             String::from("Shuffle play all (audio loop)"),
             String::from("mpv stop ;; resume ;; ${mpv-queuelist} ;; mpv sprop loop-playlist yes ;; mpv playlist-shuffle ;; mpv playlist-play-index 0 ;; echo mpv Player started"),
         )]),
+        HashMap::from([(
+            String::from("Add all to queue"),
+            String::from("queue add ${id}"),
+        )]),
         HashMap::from([(
             String::from("View channel"),
             String::from("channel ${channel-id}"),
@@ -247,6 +328,10 @@ fn playlist_default() -> Vec<HashMap<String, String>> {
             String::from("Subscribe to channel"),
             String::from("sync ${channel-id}"),
         )]),
+        HashMap::from([(
+            String::from("Block channel"),
+            String::from("block channel ${channel-id}"),
+        )]),
         HashMap::from([(
             String::from("Open in browser"),
             format!("parrun ${{browser}} {q}${{url}}{q}"),
@@ -312,6 +397,10 @@ fn saved_playlist_default() -> Vec<HashMap<String, String>> {
             String::from("Subscribe to channel"),
             String::from("sync ${channel-id}"),
         )]),
+        HashMap::from([(
+            String::from("Block channel"),
+            String::from("block channel ${channel-id}"),
+        )]),
         HashMap::from([(
             String::from("Open in browser"),
             format!("parrun ${{browser}} {q}${{url}}{q}"),
@@ -350,6 +439,32 @@ fn saved_playlist_default() -> Vec<HashMap<String, String>> {
     ]
 }
 
+fn local_playlist_default() -> Vec<HashMap<String, String>> {
+    vec![
+        HashMap::from([(String::from("Switch view"), String::from("%switch-view%"))]),
+        HashMap::from([(
+            String::from("Play all"),
+            String::from("plist play ${title}"),
+        )]),
+        HashMap::from([(
+            String::from("Play all (audio)"),
+            String::from("mpv stop ;; resume ;; ${mpv-queuelist} ;; mpv sprop loop-playlist no ;; mpv playlist-play-index 0 ;; echo mpv Player started"),
+        )]),
+        HashMap::from([(
+            String::from("Shuffle play all (audio loop)"),
+            String::from("mpv stop ;; resume ;; ${mpv-queuelist} ;; mpv sprop loop-playlist yes ;; mpv playlist-shuffle ;; mpv playlist-play-index 0 ;; echo mpv Player started"),
+        )]),
+        HashMap::from([(
+            String::from("Add all to queue"),
+            String::from("plist queue ${title}"),
+        )]),
+        HashMap::from([(
+            String::from("Delete playlist"),
+            String::from("plist rm ${title} ;; back"),
+        )]),
+    ]
+}
+
 fn channel_default() -> Vec<HashMap<String, String>> {
     vec![
         HashMap::from([(
@@ -360,6 +475,10 @@ fn channel_default() -> Vec<HashMap<String, String>> {
             String::from("Subscribe to channel"),
             String::from("sync ${id}"),
         )]),
+        HashMap::from([(
+            String::from("Block channel"),
+            String::from("block channel ${id}"),
+        )]),
         HashMap::from([(
             String::from("Play all (videos)"),
             String::from("parrun ${video-player} ${url}"),
@@ -31,10 +31,26 @@ impl Default for CommandsRemapConfig {
             ("feed".to_string(), "loadpage feed".to_string()),
             ("bookmarks".to_string(), "loadpage bookmarks".to_string()),
             ("library".to_string(), "loadpage library".to_string()),
+            ("queue".to_string(), "loadpage queue".to_string()),
             ("search".to_string(), "loadpage search".to_string()),
             ("channel".to_string(), "loadpage channel".to_string()),
+            (
+                "channelsearch".to_string(),
+                "loadpage channelsearch".to_string(),
+            ),
             ("video".to_string(), "loadpage video".to_string()),
             ("playlist".to_string(), "loadpage playlist".to_string()),
+            (
+                "localplaylist".to_string(),
+                "loadpage localplaylist".to_string(),
+            ),
+            ("comments".to_string(), "loadpage comments".to_string()),
+            ("related".to_string(), "loadpage related".to_string()),
+            (
+                "localsearch".to_string(),
+                "loadpage localsearch".to_string(),
+            ),
+            ("instances".to_string(), "loadpage instances".to_string()),
             ("back".to_string(), "history back".to_string()),
             ("r".to_string(), "reload".to_string()),
             ("rc".to_string(), "reload configs".to_string()),
@@ -0,0 +1,74 @@
+use crate::global::{functions::paths, traits::ConfigTrait};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs::OpenOptions, io::Write};
+use typemap::Key;
+
+/// `filters.yml`, rules deciding which items are shown in lists
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct FiltersConfig {
+    #[serde(default)]
+    pub rules: Vec<FilterRule>,
+}
+
+impl Key for FiltersConfig {
+    type Value = Self;
+}
+
+impl ConfigTrait for FiltersConfig {
+    const LABEL: &'static str = "filters";
+}
+
+impl FiltersConfig {
+    /// writes the rules back to `filters.yml`, used when rules are added with `block`
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(paths::config_dir().join(format!("{}.yml", Self::LABEL)))?;
+        file.write_all(serde_yaml_ng::to_string(self)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// a rule matches items that meet all of its conditions, a rule without conditions matches
+/// everything
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct FilterRule {
+    pub action: FilterAction,
+    /// when several rules match an item, the one with the highest priority is used
+    pub priority: i32,
+    /// regex matched against the title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// regex matched against the description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// matches if any of the words is in the title or description, ignoring case
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// video length in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub live: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upcoming: Option<bool>,
+    /// videos of 60 seconds or less
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shorts: Option<bool>,
+    /// channel id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+}
+
+/// what happens to items matching a rule
+#[derive(Clone, Copy, Default, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FilterAction {
+    Allow,
+    #[default]
+    Deny,
+}
@@ -145,6 +145,23 @@ impl Default for KeyBindingsConfigSerde {
                 KeyCodeSerde::Char('e'),
                 HashMap::from([(2, KeyAction::End)]),
             ),
+            // embedded player
+            (
+                KeyCodeSerde::Char(','),
+                HashMap::from([(0, KeyAction::SeekBackward)]),
+            ),
+            (
+                KeyCodeSerde::Char('.'),
+                HashMap::from([(0, KeyAction::SeekForward)]),
+            ),
+            (
+                KeyCodeSerde::Char('-'),
+                HashMap::from([(0, KeyAction::VolumeDown)]),
+            ),
+            (
+                KeyCodeSerde::Char('='),
+                HashMap::from([(0, KeyAction::VolumeUp)]),
+            ),
         ]))
     }
 }
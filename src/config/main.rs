@@ -10,6 +10,15 @@ pub struct MainConfig {
     pub mouse_support: bool,
     #[serde(default = "invidious_instance_default")]
     pub invidious_instance: String,
+    /// more instances to use when `invidious_instance` is down
+    #[serde(default)]
+    pub invidious_instances: Vec<String>,
+    /// the Piped API used by the `Piped` search provider
+    #[serde(default = "piped_instance_default")]
+    pub piped_instance: String,
+    /// seconds to wait for an Invidious or Piped instance
+    #[serde(default = "instance_timeout_default")]
+    pub instance_timeout: u64,
     #[serde(default = "write_to_config_default")]
     pub write_config: WriteConfig,
     #[serde(default = "allow_unicode_default")]
@@ -24,8 +33,14 @@ pub struct MainConfig {
     pub syncing: SyncConfig,
     #[serde(default)]
     pub limits: Limits,
+    /// responses from the search provider kept in the cache directory
+    #[serde(default)]
+    pub cache: CacheConfig,
     #[serde(default = "textbar_scroll_behaviour_default")]
     pub textbar_scroll_behaviour: TextbarScrollBehaviour,
+    /// number of suggestions shown under the search bar, 0 to turn them off
+    #[serde(default = "search_suggestions_default")]
+    pub search_suggestions: usize,
     #[serde(default = "image_index_default")]
     // The image to download from the array of images provided by the invidious api
     // 0 is usually `maxres` and 3 (default) is good enough for normal uses without having huge files sizes
@@ -35,12 +50,35 @@ pub struct MainConfig {
     pub provider: Provider,
     #[serde(default = "search_provider_default")]
     pub search_provider: SearchProvider,
+    /// providers to try in order when `search_provider` fails or does not support an operation
+    #[serde(default)]
+    pub fallback_providers: Vec<ProviderEntry>,
+    /// directory of captured responses used by the `Fixture` search provider
+    #[serde(default = "fixture_dir_default")]
+    pub fixture_dir: String,
     #[serde(default = "api_key_default")]
     pub api_key: String,
     #[serde(default = "shell_default")]
     pub shell: String,
     #[serde(default = "legacy_input_handling_default")]
     pub legacy_input_handling: bool,
+    #[serde(default = "captions_language_default")]
+    pub captions_language: String,
+    #[serde(default = "seek_seconds_default")]
+    pub seek_seconds: u32,
+    #[serde(default = "volume_step_default")]
+    pub volume_step: u32,
+    #[serde(default = "watched_threshold_default")]
+    pub watched_threshold: u8,
+    /// arguments for external players to start from the saved position and save it when quit,
+    /// by the name of the player, the ones for `video-player` are used as `${resume-args}`
+    #[serde(default = "resume_args_default")]
+    pub resume_args: HashMap<String, String>,
+    #[serde(default = "hide_watched_default")]
+    pub hide_watched: bool,
+    /// read from local data instead of the search provider
+    #[serde(default = "offline_default")]
+    pub offline: bool,
     #[serde(default = "default_env")]
     pub env: HashMap<String, String>,
     #[serde(default = "blacklist")]
@@ -54,7 +92,7 @@ pub struct BlockList {
 }
 
 const fn channels() -> Vec<String> {
-    return vec![]
+    vec![]
 }
 const fn blacklist() -> BlockList {
     BlockList {
@@ -72,6 +110,9 @@ impl Default for MainConfig {
             write_config: write_to_config_default(),
             mouse_support: mouse_support_default(),
             invidious_instance: invidious_instance_default(),
+            invidious_instances: Vec::new(),
+            piped_instance: piped_instance_default(),
+            instance_timeout: instance_timeout_default(),
             allow_unicode: allow_unicode_default(),
             message_bar_default: message_bar_default_default(),
             images: images_default(),
@@ -79,13 +120,24 @@ impl Default for MainConfig {
             refresh_after_modifying_search_filters: refresh_after_modifying_search_filters_default(
             ),
             textbar_scroll_behaviour: textbar_scroll_behaviour_default(),
+            search_suggestions: search_suggestions_default(),
             limits: Limits::default(),
+            cache: CacheConfig::default(),
             syncing: sync_config_default(),
             provider: provider_default(),
             search_provider: search_provider_default(),
+            fallback_providers: Vec::new(),
+            fixture_dir: fixture_dir_default(),
             api_key: api_key_default(),
             shell: shell_default(),
             legacy_input_handling: legacy_input_handling_default(),
+            captions_language: captions_language_default(),
+            seek_seconds: seek_seconds_default(),
+            volume_step: volume_step_default(),
+            watched_threshold: watched_threshold_default(),
+            resume_args: resume_args_default(),
+            hide_watched: hide_watched_default(),
+            offline: offline_default(),
 
             env: default_env(),
             block_list: blacklist(),
@@ -138,6 +190,38 @@ impl Default for Limits {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// seconds a response is used for before it is fetched again, operations that are not listed
+    /// are never cached
+    #[serde(default = "cache_ttl_default")]
+    pub ttl: HashMap<String, u64>,
+    /// seconds after expiring that a response is still shown, while it is fetched again in the
+    /// background
+    #[serde(default = "cache_stale_default")]
+    pub stale: u64,
+    /// megabytes, the oldest responses are removed when the cache gets larger
+    #[serde(default = "cache_max_size_default")]
+    pub max_size: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: cache_ttl_default(),
+            stale: cache_stale_default(),
+            max_size: cache_max_size_default(),
+        }
+    }
+}
+
+impl CacheConfig {
+    /// seconds a response of the operation is used for, 0 if it is not cached
+    pub fn ttl(&self, operation: &str) -> u64 {
+        self.ttl.get(operation).copied().unwrap_or_default()
+    }
+}
+
 /// how images are handled/displayed
 #[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Images {
@@ -169,20 +253,72 @@ pub enum SearchProvider {
     Invidious,
     #[cfg(feature = "rustypipe")]
     RustyPipe,
+    #[cfg(feature = "piped")]
+    Piped,
+    /// serves responses from `fixture_dir`, for offline development and tests
+    Fixture,
 }
 
 impl SearchProvider {
     pub fn create(&self) -> Box<dyn SearchProviderTrait> {
-        match self {
+        ProviderEntry::from(*self).create()
+    }
+}
+
+/// a search provider in the provider chain
+///
+/// `instance` is the Invidious or Piped instance, or fixture directory, the instance pool,
+/// `piped_instance` or `fixture_dir` is used if not specified
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ProviderEntry {
+    pub provider: SearchProvider,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+}
+
+impl From<SearchProvider> for ProviderEntry {
+    fn from(provider: SearchProvider) -> Self {
+        Self {
+            provider,
+            instance: None,
+        }
+    }
+}
+
+impl ProviderEntry {
+    pub fn create(&self) -> Box<dyn SearchProviderTrait + Send + Sync> {
+        let config = unsafe { crate::MAIN_CONFIG.get() }.unwrap();
+        match self.provider {
             #[cfg(feature = "invidious")]
-            Self::Invidious => Box::new(crate::global::structs::InvidiousClient::new(
-                unsafe { crate::MAIN_CONFIG.get() }
-                    .unwrap()
-                    .invidious_instance
-                    .clone(),
-            )),
+            SearchProvider::Invidious => Box::new(match &self.instance {
+                Some(instance) => crate::global::structs::InvidiousClient::new(instance.clone()),
+                None => crate::global::structs::InvidiousClient::pool(),
+            }),
             #[cfg(feature = "rustypipe")]
-            Self::RustyPipe => Box::new(crate::global::structs::RustyPipeWrapper::default()),
+            SearchProvider::RustyPipe => {
+                Box::new(crate::global::structs::RustyPipeWrapper::default())
+            }
+            #[cfg(feature = "piped")]
+            SearchProvider::Piped => Box::new(crate::global::structs::PipedClient::new(
+                self.instance
+                    .clone()
+                    .unwrap_or(config.piped_instance.clone()),
+                std::time::Duration::from_secs(config.instance_timeout),
+            )),
+            SearchProvider::Fixture => Box::new(crate::global::structs::FixtureProvider::new(
+                crate::global::functions::paths::expand_home(
+                    self.instance.as_ref().unwrap_or(&config.fixture_dir),
+                ),
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ProviderEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.instance {
+            Some(instance) => write!(f, "{} ({instance})", self.provider.as_str()),
+            None => f.write_str(self.provider.as_str()),
         }
     }
 }
@@ -194,6 +330,9 @@ impl SearchProvider {
             Self::Invidious => "Invidious",
             #[cfg(feature = "rustypipe")]
             Self::RustyPipe => "RustyPipe",
+            #[cfg(feature = "piped")]
+            Self::Piped => "Piped",
+            Self::Fixture => "Fixture",
         }
     }
 }
@@ -227,6 +366,21 @@ fn invidious_instance_default() -> String {
     String::from("https://invidious.f5.si")
 }
 
+fn piped_instance_default() -> String {
+    String::from("https://pipedapi.kavin.rocks")
+}
+
+const fn instance_timeout_default() -> u64 {
+    10
+}
+
+fn fixture_dir_default() -> String {
+    crate::global::functions::paths::data_dir()
+        .join("fixtures")
+        .to_string_lossy()
+        .to_string()
+}
+
 fn message_bar_default_default() -> String {
     String::from("All good :)")
 }
@@ -251,9 +405,9 @@ const fn provider_default() -> Provider {
     Provider::YouTube
 }
 
-#[cfg(not(any(feature = "rustypipe", feature = "invidious")))]
+#[cfg(not(any(feature = "rustypipe", feature = "invidious", feature = "piped")))]
 compile_error!(
-    "You must include a search provider in your feature list, they are `rustypipe` and `invidious`"
+    "You must include a search provider in your feature list, they are `rustypipe`, `invidious` and `piped`"
 );
 
 #[cfg(feature = "rustypipe")]
@@ -266,6 +420,14 @@ const fn search_provider_default() -> SearchProvider {
     SearchProvider::Invidious
 }
 
+#[cfg(all(
+    feature = "piped",
+    not(any(feature = "rustypipe", feature = "invidious"))
+))]
+const fn search_provider_default() -> SearchProvider {
+    SearchProvider::Piped
+}
+
 const fn search_history_default() -> usize {
     75
 }
@@ -274,6 +436,36 @@ const fn commands_history_default() -> usize {
     75
 }
 
+fn cache_ttl_default() -> HashMap<String, u64> {
+    [
+        ("trending", 3600),
+        ("popular", 3600),
+        ("search", 1800),
+        ("video", 21600),
+        ("playlist", 3600),
+        ("mix", 3600),
+        ("comments", 1800),
+        ("channel", 21600),
+        ("channel_videos", 3600),
+        ("channel_playlists", 3600),
+        ("channel_shorts", 3600),
+        ("channel_streams", 3600),
+        ("channel_podcasts", 3600),
+        ("channel_search", 1800),
+    ]
+    .into_iter()
+    .map(|(operation, ttl)| (operation.to_string(), ttl))
+    .collect()
+}
+
+const fn cache_stale_default() -> u64 {
+    86400
+}
+
+const fn cache_max_size_default() -> u64 {
+    100
+}
+
 const fn watch_history_default() -> usize {
     50
 }
@@ -287,10 +479,10 @@ const fn write_to_config_default() -> WriteConfig {
 }
 
 fn default_env() -> HashMap<String, String> {
-    use crate::global::functions::paths;
-
     #[cfg(target_os = "windows")]
     {
+        use crate::global::functions::paths;
+
         HashMap::from([
             (String::from("video-player"), String::from("mpv")),
             (String::from("browser"), String::from("explorer")),
@@ -342,6 +534,42 @@ fn shell_default() -> String {
     }
 }
 
+fn captions_language_default() -> String {
+    String::from("en")
+}
+
+const fn seek_seconds_default() -> u32 {
+    10
+}
+
+const fn volume_step_default() -> u32 {
+    5
+}
+
+const fn watched_threshold_default() -> u8 {
+    90
+}
+
+fn resume_args_default() -> HashMap<String, String> {
+    #[cfg(target_os = "windows")]
+    let q = "\"";
+    #[cfg(not(target_os = "windows"))]
+    let q = "'";
+
+    HashMap::from([(
+        String::from("mpv"),
+        format!("--start=${{resume-position}} --save-position-on-quit --write-filename-in-watch-later-config --watch-later-directory={q}${{watch-later-path}}{q}"),
+    )])
+}
+
+const fn hide_watched_default() -> bool {
+    false
+}
+
+const fn offline_default() -> bool {
+    false
+}
+
 fn api_key_default() -> String {
     String::from("YOUR API KEY HERE")
 }
@@ -375,3 +603,7 @@ pub enum TextbarScrollBehaviour {
 const fn textbar_scroll_behaviour_default() -> TextbarScrollBehaviour {
     TextbarScrollBehaviour::History
 }
+
+const fn search_suggestions_default() -> usize {
+    8
+}
@@ -17,5 +17,7 @@ mod commandbindings;
 pub use commandbindings::*;
 mod commands_remap;
 pub use commands_remap::*;
+mod filters;
+pub use filters::*;
 
 pub mod serde;
@@ -1,4 +1,11 @@
-use crate::{global::traits::ConfigTrait, items::*};
+use crate::{
+    config::MainConfig,
+    global::{
+        structs::{Fetch, Page},
+        traits::ConfigTrait,
+    },
+    items::*,
+};
 use ratatui::layout::Constraint;
 use serde::{Deserialize, Serialize};
 use std::slice;
@@ -36,6 +43,14 @@ pub struct PagesConfig {
     pub singleitem: PageConfig,
     #[serde(default = "channeldisplay_default")]
     pub channeldisplay: PageConfig,
+    #[serde(default = "comments_default")]
+    pub comments: PageConfig,
+    #[serde(default = "related_default")]
+    pub related: PageConfig,
+    #[serde(default = "localsearch_default")]
+    pub localsearch: PageConfig,
+    #[serde(default = "instances_default")]
+    pub instances: PageConfig,
 }
 
 impl Key for PagesConfig {
@@ -54,6 +69,10 @@ impl Default for PagesConfig {
             search: search_default(),
             singleitem: singleitem_default(),
             channeldisplay: channeldisplay_default(),
+            comments: comments_default(),
+            related: related_default(),
+            localsearch: localsearch_default(),
+            instances: instances_default(),
         }
     }
 }
@@ -96,6 +115,27 @@ impl PageConfig {
             .sum::<u16>()
     }
 
+    /// The provider calls made by items of the page when loading `page`, `None` if they only show
+    /// local data
+    pub fn prefetch(
+        &self,
+        page: &Page,
+        mainconfig: &MainConfig,
+    ) -> Option<impl FnOnce() + Send + 'static> {
+        let requests = self
+            .layout
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter_map(|item| item.prefetch(page, mainconfig))
+            .collect::<Vec<_>>();
+
+        (!requests.is_empty()).then_some(move || {
+            for request in requests {
+                request();
+            }
+        })
+    }
+
     /// Converts itself into `State` to be used in `Framework`
     pub fn to_state(&self, framework: &mut Framework) -> State {
         State(
@@ -172,6 +212,8 @@ pub enum PageItems {
     Feed,
     /// the library button which loads the saved page
     Library,
+    /// the queue button which loads the play queue page
+    Queue,
     /// Feed list (channels)
     ChannelList,
     /// Feed list (videos)
@@ -186,8 +228,22 @@ pub enum PageItems {
     ChannelMain,
     /// button which loads the channel videos page
     ChannelVideos,
+    /// button which loads the channel shorts page
+    ChannelShorts,
+    /// button which loads the channel live streams page
+    ChannelStreams,
     /// button which loads the channel playlists page
     ChannelPlaylists,
+    /// button which loads the channel podcasts page
+    ChannelPodcasts,
+    /// text field which searches the videos of the channel
+    ChannelSearch,
+    /// threaded comments of a video
+    CommentsView,
+    /// progress, play state and volume of the embedded mpv player
+    NowPlaying,
+    /// Invidious instances and their health, select an instance to pin it
+    InstanceList,
 }
 
 impl PageItems {
@@ -200,6 +256,7 @@ impl PageItems {
             Self::History => Box::new(PageButton::History),
             Self::Feed => Box::new(PageButton::Feed),
             Self::Library => Box::new(PageButton::Library),
+            Self::Queue => Box::new(PageButton::Queue),
             Self::ChannelList => Box::<ChannelList>::default(),
             Self::VideoList => Box::<VideoList>::default(),
             Self::MessageBar => Box::<MessageBar>::default(),
@@ -209,7 +266,36 @@ impl PageItems {
             Self::ChannelDisplay => Box::<ChannelDisplay>::default(),
             Self::ChannelMain => Box::new(PageButton::ChannelMain),
             Self::ChannelVideos => Box::new(PageButton::ChannelVideos),
+            Self::ChannelShorts => Box::new(PageButton::ChannelShorts),
+            Self::ChannelStreams => Box::new(PageButton::ChannelStreams),
             Self::ChannelPlaylists => Box::new(PageButton::ChannelPlaylists),
+            Self::ChannelPodcasts => Box::new(PageButton::ChannelPodcasts),
+            Self::ChannelSearch => Box::<ChannelSearchBar>::default(),
+            Self::CommentsView => Box::<CommentsView>::default(),
+            Self::NowPlaying => Box::<NowPlaying>::default(),
+            Self::InstanceList => Box::<InstanceList>::default(),
+        }
+    }
+
+    /// The provider calls the item makes when loading `page`, from the same function its
+    /// `load_item` gets its data from
+    pub fn prefetch(
+        &self,
+        page: &Page,
+        mainconfig: &MainConfig,
+    ) -> Option<Box<dyn FnOnce() + Send>> {
+        fn discard<T: 'static>(fetch: Fetch<T>) -> Box<dyn FnOnce() + Send> {
+            Box::new(move || {
+                let _ = fetch();
+            })
+        }
+
+        match self {
+            Self::ItemList => ItemList::fetch(page, mainconfig).map(discard),
+            Self::SingleItemInfo => SingleItem::fetch(page, mainconfig).map(discard),
+            Self::ChannelDisplay => ChannelDisplay::fetch(page, mainconfig).map(discard),
+            Self::CommentsView => CommentsView::fetch(page).map(discard),
+            _ => None,
         }
     }
 
@@ -220,16 +306,23 @@ impl PageItems {
             | Self::History
             | Self::Feed
             | Self::Library
-            | Self::ChannelMain
+            | Self::Queue => Constraint::Length(15),
+            Self::ChannelMain
             | Self::ChannelVideos
-            | Self::ChannelPlaylists => Constraint::Length(15),
+            | Self::ChannelShorts
+            | Self::ChannelStreams
+            | Self::ChannelPlaylists
+            | Self::ChannelPodcasts => Constraint::Length(12),
+            Self::ChannelSearch => Constraint::Length(30),
             Self::SearchBar => Constraint::Min(16),
-            Self::MessageBar => Constraint::Min(3),
+            Self::MessageBar | Self::NowPlaying => Constraint::Min(3),
             Self::ItemList
             | Self::SingleItemInfo
             | Self::ChannelDisplay
             | Self::ChannelList
-            | Self::VideoList => Constraint::Min(9),
+            | Self::VideoList
+            | Self::CommentsView
+            | Self::InstanceList => Constraint::Min(9),
             Self::SearchFilters => Constraint::Length(5),
         }
     }
@@ -240,16 +333,25 @@ impl PageItems {
             | Self::History
             | Self::Feed
             | Self::Library
+            | Self::Queue
             | Self::ChannelMain
             | Self::ChannelVideos
+            | Self::ChannelShorts
+            | Self::ChannelStreams
             | Self::ChannelPlaylists
+            | Self::ChannelPodcasts
+            | Self::ChannelSearch
             | Self::Trending
             | Self::MessageBar
+            | Self::NowPlaying
             | Self::SearchBar
             | Self::SearchFilters => Constraint::Length(3),
-            Self::ItemList | Self::SingleItemInfo | Self::ChannelDisplay | Self::VideoList => {
-                Constraint::Min(6)
-            }
+            Self::ItemList
+            | Self::SingleItemInfo
+            | Self::ChannelDisplay
+            | Self::VideoList
+            | Self::CommentsView
+            | Self::InstanceList => Constraint::Min(6),
             Self::ChannelList => Constraint::Length(18),
         }
     }
@@ -284,7 +386,12 @@ fn main_menu_default() -> PageConfig {
         layout: vec![
             PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
             PageRow::from_vec(
-                vec![PageItems::Library, PageItems::Feed, PageItems::History],
+                vec![
+                    PageItems::Library,
+                    PageItems::Feed,
+                    PageItems::History,
+                    PageItems::Queue,
+                ],
                 true,
             ),
             PageRow::from_vec(vec![PageItems::ItemList], false),
@@ -327,7 +434,11 @@ fn channeldisplay_default() -> PageConfig {
                 vec![
                     PageItems::ChannelMain,
                     PageItems::ChannelVideos,
+                    PageItems::ChannelShorts,
+                    PageItems::ChannelStreams,
                     PageItems::ChannelPlaylists,
+                    PageItems::ChannelPodcasts,
+                    PageItems::ChannelSearch,
                 ],
                 true,
             ),
@@ -344,7 +455,12 @@ fn feed_default() -> PageConfig {
         layout: vec![
             PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
             PageRow::from_vec(
-                vec![PageItems::Library, PageItems::Feed, PageItems::History],
+                vec![
+                    PageItems::Library,
+                    PageItems::Feed,
+                    PageItems::History,
+                    PageItems::Queue,
+                ],
                 true,
             ),
             PageRow::from_vec(vec![PageItems::ChannelList], false),
@@ -355,3 +471,51 @@ fn feed_default() -> PageConfig {
         command: String::from("key Esc 0 ;; key Down 0 ;; key Down 0 ;; key Enter 0"),
     }
 }
+
+fn comments_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(vec![PageItems::CommentsView], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Loading comments..."),
+        command: String::from("key Esc 0 ;; key Down 0 ;; key Enter 0"),
+    }
+}
+
+fn related_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(vec![PageItems::ItemList], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Loading related videos..."),
+        command: String::from("key Esc 0 ;; key Down 0 ;; key Enter 0"),
+    }
+}
+
+fn localsearch_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(vec![PageItems::ItemList], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Searching saved items..."),
+        command: String::from("key Esc 0 ;; key Down 0 ;; key Enter 0"),
+    }
+}
+
+fn instances_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(vec![PageItems::InstanceList], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Loading instances..."),
+        command: String::from("key Esc 0 ;; key Down 0 ;; key Enter 0"),
+    }
+}
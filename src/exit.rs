@@ -31,6 +31,14 @@ pub fn exit(framework: &mut Framework) -> Result<(), Box<dyn Error>> {
     let mut commandhistory = framework.data.global.remove::<CommandHistory>().unwrap();
     commandhistory.trim(limits.commands_history);
     let _ = commandhistory.save();
+    let mut playqueue = framework.data.global.remove::<PlayQueue>().unwrap();
+    // the item that was interrupted will be played first next time
+    if let Some(item) = playqueue.playing.take() {
+        playqueue.push_front(item);
+    }
+    let _ = playqueue.save();
+    let localplaylists = framework.data.global.remove::<LocalPlaylists>().unwrap();
+    let _ = localplaylists.save();
     let library = framework.data.global.get_mut::<Library>().unwrap();
     let _ = library.save();
 
@@ -46,9 +54,23 @@ pub fn exit(framework: &mut Framework) -> Result<(), Box<dyn Error>> {
                     .iter()
                     .filter_map(|item| item.id())
                     .map(str::to_string),
-            ),
+            )
+            .chain(
+                playqueue
+                    .items()
+                    .iter()
+                    .filter_map(|item| item.id())
+                    .map(str::to_string),
+            )
+            .chain(localplaylists.ids()),
     );
 
+    let watchpositions = framework.data.global.get_mut::<WatchPositions>().unwrap();
+    watchpositions.read_watch_later();
+    watchpositions.retain(&cached_after);
+    let _ = watchpositions.save();
+    let _ = framework.data.global.get::<WatchedVideos>().unwrap().save();
+
     let cached_before = CACHED_BEFORE.get().unwrap();
 
     let data = paths::data_dir();
@@ -1,7 +1,7 @@
 use crate::global::common::CommonPlaylist;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ChannelPlaylists {
     pub playlists: Vec<CommonPlaylist>,
     pub continuation: Option<String>,
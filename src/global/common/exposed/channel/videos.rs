@@ -1,7 +1,7 @@
 use crate::global::common::CommonVideo;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ChannelVideos {
     pub videos: Vec<CommonVideo>,
     pub continuation: Option<String>,
@@ -1,10 +1,33 @@
 use crate::global::common::hidden::*;
 use serde::{Deserialize, Serialize};
 
+use super::Playlist;
+
+/// an auto generated radio playlist, its id is `RD` followed by the id of the video it is based on
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Mix {
     pub title: String,
-    #[serde(rename = "midId")]
+    #[serde(rename = "mixId")]
     pub id: String,
     pub videos: Vec<MixVideo>,
 }
+
+impl From<Mix> for Playlist {
+    fn from(value: Mix) -> Self {
+        Self {
+            title: value.title,
+            id: value.id,
+            thumbnail: value
+                .videos
+                .first()
+                .and_then(|video| video.thumbnails.first())
+                .map(|thumbnail| thumbnail.url.clone())
+                .unwrap_or_default(),
+            author: String::from("YouTube"),
+            video_count: value.videos.len() as u32,
+            listed: false,
+            videos: value.videos.into_iter().map(PlaylistItem::from).collect(),
+            ..Default::default()
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::global::common::{hidden::*, CommonImage};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Playlist {
     pub title: String,
     #[serde(rename = "playlistId")]
@@ -27,4 +27,7 @@ pub struct Playlist {
     pub listed: bool,
 
     pub videos: Vec<PlaylistItem>,
+    /// token for the next page of videos, `None` if all videos are loaded
+    #[serde(default)]
+    pub continuation: Option<String>,
 }
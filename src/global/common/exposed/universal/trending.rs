@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::global::common::CommonVideo;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Trending {
     pub videos: Vec<CommonVideo>,
+    #[serde(default)]
+    pub continuation: Option<String>,
 }
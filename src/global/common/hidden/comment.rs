@@ -46,6 +46,6 @@ pub struct CreatorHeart {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Replies {
     #[serde(rename = "replyCount")]
-    replies: u32,
-    continuation: String,
+    pub replies: u32,
+    pub continuation: String,
 }
@@ -2,18 +2,36 @@ use serde::{Deserialize, Serialize};
 
 use crate::global::common::CommonThumbnail;
 
+use super::PlaylistItem;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MixVideo {
-    title: String,
-    #[serde(rename = "mixId")]
-    id: String,
-    #[serde(rename = "audioId")]
-    audio_id: String,
-    author: String,
+    pub title: String,
+    #[serde(rename = "videoId")]
+    pub id: String,
+    pub author: String,
+    #[serde(rename = "authorId", default)]
+    pub author_id: String,
     #[serde(rename = "authorUrl")]
-    author_url: String,
-    videos: Vec<CommonThumbnail>,
-    index: u32,
+    pub author_url: String,
+    #[serde(rename = "videoThumbnails")]
+    pub thumbnails: Vec<CommonThumbnail>,
+    pub index: u32,
     #[serde(rename = "lengthSeconds")]
-    length: u32,
+    pub length: u32,
+}
+
+impl From<MixVideo> for PlaylistItem {
+    fn from(value: MixVideo) -> Self {
+        Self {
+            title: value.title,
+            id: value.id,
+            author: value.author,
+            author_id: value.author_id,
+            author_url: value.author_url,
+            thumbnails: value.thumbnails,
+            index: value.index,
+            length: value.length,
+        }
+    }
 }
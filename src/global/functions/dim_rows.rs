@@ -0,0 +1,44 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+};
+use tui_additions::widgets::TextList;
+
+use crate::config::AppearanceConfig;
+
+/// style of watched videos in video lists
+pub fn watched_style(appearance: &AppearanceConfig) -> Style {
+    Style::default()
+        .fg(appearance.colors.text_secondary)
+        .add_modifier(Modifier::DIM)
+}
+
+/// restyle rows of an already rendered text list, `dim` is called with the index of each visible
+/// item, the selected item is left as is
+pub fn dim_rows(
+    buf: &mut Buffer,
+    textlist: &TextList,
+    area: Rect,
+    style: Style,
+    dim: impl Fn(usize) -> bool,
+) {
+    // same layout as the text list widget: the selected item takes up 3 rows, everything else 1
+    let mut y = area.y;
+    for index in
+        (textlist.scroll..textlist.items.len()).take(area.height.saturating_sub(2) as usize)
+    {
+        if index == textlist.selected {
+            y += 3;
+            continue;
+        }
+
+        if dim(index) {
+            buf.set_style(
+                Rect::new(area.x + 1, y, area.width.saturating_sub(2), 1),
+                style,
+            );
+        }
+        y += 1;
+    }
+}
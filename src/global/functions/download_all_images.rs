@@ -1,5 +1,6 @@
 use crate::global::structs::{Item, LocalStore};
-use std::{error::Error, fs, io::Cursor, path::PathBuf, thread};
+use http_req::{request::Request, uri::Uri};
+use std::{error::Error, fs, io::Cursor, path::PathBuf, thread, time::Duration};
 
 pub struct DownloadRequest {
     pub url: String,
@@ -61,12 +62,24 @@ pub fn download_all_images(downloads: Vec<Option<DownloadRequest>>) {
     })
 }
 
-fn httpreq_get(url: &str) -> Result<Vec<u8>, http_req::error::Error> {
+pub fn httpreq_get(url: &str) -> Result<Vec<u8>, http_req::error::Error> {
     let mut buffer = Vec::new();
     http_req::request::get(url, &mut buffer)?;
     Ok(buffer)
 }
 
+/// get request which gives up after `timeout`, returns the status code with the body
+pub fn httpreq_get_timeout(url: &str, timeout: Duration) -> Result<(u16, Vec<u8>), Box<dyn Error>> {
+    let uri = Uri::try_from(url)?;
+    let mut buffer = Vec::new();
+    let res = Request::new(&uri)
+        .connect_timeout(Some(timeout))
+        .read_timeout(Some(timeout))
+        .timeout(timeout)
+        .send(&mut buffer)?;
+    Ok((res.status_code().into(), buffer))
+}
+
 fn download_single(url: &str, path: PathBuf) -> Result<(), Box<dyn Error>> {
     let res = httpreq_get(url)?;
     let mut file = fs::File::create(path)?;
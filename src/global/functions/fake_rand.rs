@@ -9,3 +9,16 @@ where T: From<i64> + Sub + Rem<<T as std::ops::Sub>::Output> + std::convert::Fro
 
     (<i64 as Into<T>>::into(timestamp) % (to - from) + from).into()
 }
+
+/// shuffle a slice in place, seeded with the current time
+pub fn fake_shuffle<T>(items: &mut [T]) {
+    let mut state = u64::from(Utc::now().timestamp_subsec_nanos()) | 1;
+
+    for i in (1..items.len()).rev() {
+        // xorshift
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        items.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
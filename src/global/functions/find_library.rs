@@ -1,30 +1,54 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
-use crate::config::MainConfig;
+use crate::{config::MainConfig, global::traits::SearchProviderWrapper};
 
 use super::paths;
 
-pub fn find_library_item(id: &str, mainconfig: &MainConfig) -> Option<PathBuf> {
+/// the directory videos are saved to, from the `save-path` env
+pub fn library_dir(mainconfig: &MainConfig) -> Option<PathBuf> {
     let save_path_str = mainconfig.env.get("save-path")?;
 
     // Resolve save-path: handle tilde expansion for backward compatibility
-    let save_path = if save_path_str.starts_with("~/") || save_path_str.starts_with("~\\") {
-        home::home_dir()
-            .unwrap()
-            .join(&save_path_str[2..])
-    } else {
-        // If it's an absolute path (e.g., from paths::default_save_path()), use directly
-        let p = PathBuf::from(save_path_str);
-        if p.is_absolute() {
-            p
+    Some(
+        if save_path_str.starts_with("~/") || save_path_str.starts_with("~\\") {
+            home::home_dir().unwrap().join(&save_path_str[2..])
         } else {
-            // Relative path: treat as relative to data_dir for safety
-            paths::data_dir().join(save_path_str)
-        }
-    };
+            // If it's an absolute path (e.g., from paths::default_save_path()), use directly
+            let p = PathBuf::from(save_path_str);
+            if p.is_absolute() {
+                p
+            } else {
+                // Relative path: treat as relative to data_dir for safety
+                paths::data_dir().join(save_path_str)
+            }
+        },
+    )
+}
 
-    fs::read_dir(save_path)
+/// the saved file or folder with `id` in its name
+pub fn find_in_library(library: &Path, id: &str) -> Option<PathBuf> {
+    fs::read_dir(library)
         .ok()?
         .filter_map(|entry| Some(entry.ok()?.path()))
         .find(|stem| stem.as_os_str().to_str().unwrap_or_default().contains(id))
 }
+
+pub fn find_library_item(id: &str, mainconfig: &MainConfig) -> Option<PathBuf> {
+    find_in_library(&library_dir(mainconfig)?, id)
+}
+
+/// the saved file of a video to play instead of its url, only in offline mode
+pub fn offline_file(id: &str, mainconfig: &MainConfig) -> Option<String> {
+    if !SearchProviderWrapper::offline() {
+        return None;
+    }
+
+    Some(
+        find_library_item(id, mainconfig)?
+            .to_string_lossy()
+            .to_string(),
+    )
+}
@@ -9,8 +9,11 @@ pub fn from_channel_url(identifier: &str) -> Result<String, String> {
         return Ok(identifier.to_string());
     }
 
+    // feed urls (such as in OPML files) have the id as "channel_id=" instead
     let index = if let Some(index) = identifier.find("/channel/") {
         index + 9
+    } else if let Some(index) = identifier.find("channel_id=") {
+        index + 11
     } else {
         return Err(format!("Cannot find channel id from string `{identifier}`"));
     };
@@ -31,6 +34,8 @@ pub fn from_video_url(identifier: &str) -> Result<String, String> {
         // and also "youtu.be/"
     } else if let Some(index) = identifier.find("youtu.be/") {
         index + 9
+    } else if let Some(index) = identifier.find("/embed/") {
+        index + 7
     } else {
         return Err(format!("Cannot find video id from string `{identifier}`"));
     };
@@ -48,11 +53,6 @@ pub fn from_playlist_url(identifier: &str) -> Result<String, String> {
 
     // the id can come after "list=" in urls
     let index = if let Some(index) = identifier.find("list=") {
-        if identifier.len() < index + 39 {
-            return Err(format!(
-                "Cannot find plalyist id from string `{identifier}`"
-            ));
-        }
         index + 5
     } else {
         return Err(format!(
@@ -60,8 +60,27 @@ pub fn from_playlist_url(identifier: &str) -> Result<String, String> {
         ));
     };
 
-    Ok(identifier[index..]
+    // mix ids (`RD` followed by a video id) are shorter than other playlist ids
+    let id = identifier[index..]
         .chars()
         .take_while(|c| is_id_char(*c))
-        .collect())
+        .collect::<String>();
+    if id.is_empty() {
+        return Err(format!(
+            "Cannot find playlist id from string `{identifier}`"
+        ));
+    }
+
+    Ok(id)
+}
+
+/// video id from a url, or from a file saved as `title[id].ext`
+pub fn path_video_id(path: &str) -> Option<String> {
+    if path.contains("://") {
+        return from_video_url(path).ok().filter(|id| !id.is_empty());
+    }
+
+    let (name, _ext) = path.rsplit_once('.')?;
+    let id = name.strip_suffix(']')?.rsplit_once('[')?.1;
+    Some(id.to_string()).filter(|id| id.len() == 11)
 }
@@ -1,18 +1,21 @@
-use std::{any::TypeId, io::Stdout};
+use std::any::TypeId;
 
 use crossterm::event::KeyEvent;
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::Backend, Terminal};
 use tui_additions::framework::{Framework, FrameworkDirection};
 
 use crate::{config::*, global::structs::*, items::SearchBar};
 
 use super::*;
 
-pub fn key_input(
+pub fn key_input<B: Backend>(
     mut key: KeyEvent,
     framework: &mut Framework,
-    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-) {
+    terminal: &mut Terminal<B>,
+)
+where
+    B::Error: 'static,
+{
     framework
         .data
         .global
@@ -32,6 +35,23 @@ pub fn key_input(
         .unwrap()
         .get(key);
 
+    // deselecting cancels the page being loaded, so does going back when not typing
+    let typing = framework.is_selected()
+        || framework
+            .data
+            .global
+            .get::<Status>()
+            .unwrap()
+            .command_capture
+            .is_some();
+    match action {
+        Some(KeyAction::Deselect) => {
+            cancel_load(framework);
+        }
+        Some(KeyAction::Back) if !typing && cancel_load(framework) => return,
+        _ => {}
+    }
+
     if action == Some(KeyAction::Deselect) {
         let _ = framework.deselect();
         framework
@@ -203,6 +223,12 @@ pub fn key_input(
             KeyAction::Select if !framework.is_selected() => {
                 let _ = framework.select();
             }
+            #[cfg(feature = "mpv")]
+            KeyAction::SeekForward
+            | KeyAction::SeekBackward
+            | KeyAction::VolumeUp
+            | KeyAction::VolumeDown
+            | KeyAction::TogglePause => mpv_key_action(action, framework),
             _ => render = false,
         }
         if render {
@@ -216,3 +242,37 @@ pub fn key_input(
         }
     }
 }
+
+/// runs the mpv command corresponding to a player key action
+#[cfg(feature = "mpv")]
+fn mpv_key_action(action: KeyAction, framework: &mut Framework) {
+    let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+    let (name, args) = match action {
+        KeyAction::SeekForward => ("seek", vec![mainconfig.seek_seconds.to_string()]),
+        KeyAction::SeekBackward => ("seek", vec![format!("-{}", mainconfig.seek_seconds)]),
+        KeyAction::VolumeUp => (
+            "add",
+            vec![String::from("volume"), mainconfig.volume_step.to_string()],
+        ),
+        KeyAction::VolumeDown => (
+            "add",
+            vec![
+                String::from("volume"),
+                format!("-{}", mainconfig.volume_step),
+            ],
+        ),
+        KeyAction::TogglePause => ("cycle", vec![String::from("pause")]),
+        _ => unreachable!(),
+    };
+
+    if let MpvResponse::Error(e) = framework
+        .data
+        .global
+        .get::<MpvWrapper>()
+        .unwrap()
+        .command(name.to_string(), args)
+    {
+        *framework.data.global.get_mut::<Message>().unwrap() =
+            Message::Error(format!("MPV error: {e}"));
+    }
+}
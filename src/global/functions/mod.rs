@@ -35,4 +35,8 @@ mod key_input;
 pub use key_input::*;
 mod find_library;
 pub use find_library::*;
+mod dim_rows;
+pub use dim_rows::*;
+mod opml;
+pub use opml::*;
 pub mod paths;
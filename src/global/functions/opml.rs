@@ -0,0 +1,100 @@
+use super::from_channel_url;
+
+/// escapes text to be used in an xml attribute
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\'', "&apos;")
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// gets the value of an attribute from the inside of a tag, e.g. `outline text="a" xmlUrl="b"`
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag;
+    while let Some(index) = rest.find(name) {
+        let preceded_by_space = rest[..index]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace);
+        let after = rest[index + name.len()..].trim_start();
+        rest = &rest[index + name.len()..];
+
+        if !preceded_by_space {
+            continue;
+        }
+        let Some(after) = after.strip_prefix('=') else {
+            continue;
+        };
+        let after = after.trim_start();
+        let quote = after.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            continue;
+        }
+        let value = &after[1..];
+        return value.find(quote).map(|end| unescape(&value[..end]));
+    }
+
+    None
+}
+
+/// writes channels to an OPML file, in the same format as exported by YouTube
+pub fn to_opml<'a>(channels: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    let mut opml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"1.1\">\n<body>\n<outline text=\"YouTube Subscriptions\" title=\"YouTube Subscriptions\">\n",
+    );
+
+    for (id, name) in channels {
+        let name = escape(name);
+        opml.push_str(&format!(
+            "<outline text=\"{name}\" title=\"{name}\" type=\"rss\" xmlUrl=\"https://www.youtube.com/feeds/videos.xml?channel_id={}\"/>\n",
+            escape(id)
+        ));
+    }
+
+    opml.push_str("</outline>\n</body>\n</opml>\n");
+    opml
+}
+
+/// reads (id, name) of channels from an OPML file, outlines that are not YouTube channels are
+/// skipped
+pub fn from_opml(opml: &str) -> Vec<(String, String)> {
+    let mut channels: Vec<(String, String)> = Vec::new();
+
+    for tag in opml.split('<').skip(1) {
+        let Some(tag) = tag.strip_prefix("outline") else {
+            continue;
+        };
+        let tag = tag.split('>').next().unwrap_or_default();
+
+        let Some(id) = ["xmlUrl", "htmlUrl"]
+            .iter()
+            .filter_map(|name| attribute(tag, name))
+            .filter(|url| url.contains("youtube.com"))
+            .find_map(|url| from_channel_url(&url).ok())
+            .filter(|id| id.len() == 24)
+        else {
+            continue;
+        };
+
+        if channels.iter().any(|(existing, _)| existing == &id) {
+            continue;
+        }
+
+        let name = attribute(tag, "title")
+            .or_else(|| attribute(tag, "text"))
+            .unwrap_or_default();
+        channels.push((id, name));
+    }
+
+    channels
+}
@@ -22,6 +22,14 @@ pub fn data_dir() -> PathBuf {
         .join("youtube-tui")
 }
 
+/// Expands a leading `~` in a path given by the user to the home directory.
+pub fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")) {
+        Some(rest) => home::home_dir().unwrap().join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
 /// Returns the cache directory for youtube-tui.
 ///
 /// - Linux: `~/.cache/youtube-tui/`
@@ -4,14 +4,14 @@ use crate::{
     load_configs,
 };
 use crossterm::event::{KeyEvent, KeyModifiers};
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::Backend, Terminal};
 use std::{
     env,
     error::Error,
     fs,
-    io::Stdout,
     process::{Command, Stdio},
     sync::Arc,
+    time::Duration,
 };
 use tui_additions::framework::Framework;
 
@@ -28,24 +28,91 @@ pub fn text_command(command: &str) -> Option<String> {
             env!("CARGO_PKG_NAME"),
             env!("CARGO_PKG_VERSION")
         )),
+        ["subscriptions", "import", path @ ..] if !path.is_empty() => {
+            let mut subscriptions = Subscriptions::load();
+            Some(
+                match subscriptions.import_opml(&paths::expand_home(&path.join(" "))) {
+                    Ok((added, existing)) => format!(
+                        "Imported {added} channels ({existing} already subscribed), run `youtube-tui syncall` to fetch their videos"
+                    ),
+                    Err(e) => format!("Import failed: {e}"),
+                },
+            )
+        }
+        ["subscriptions", "export", path @ ..] if !path.is_empty() => Some(
+            match Subscriptions::load().export_opml(&paths::expand_home(&path.join(" "))) {
+                Ok(count) => format!("Exported {count} channels"),
+                Err(e) => format!("Export failed: {e}"),
+            },
+        ),
+        ["subscriptions", "import" | "export"] => Some(String::from(
+            "Usage: `subscriptions import {file}` or `subscriptions export {file}`",
+        )),
+        // writing needs the search provider to fetch video info, so it is ran in the TUI
+        ["import", "write", _, ..] => None,
+        ["import"] | ["import", "write"] => Some(String::from(
+            "Usage: `import {file or directory}` or `import write {file or directory}`",
+        )),
+        ["import", path @ ..] => {
+            let path = path.join(" ");
+            Some(match Import::from_path(&paths::expand_home(&path)) {
+                Ok(import) => format!(
+                    "{}\nRun `youtube-tui import write {path}` to import",
+                    import.report(
+                        &Subscriptions::load(),
+                        &WatchHistory::load(),
+                        &Library::load(),
+                        MainConfig::load(WriteConfig::Dont)
+                            .unwrap_or_default()
+                            .limits
+                            .watch_history,
+                    )
+                ),
+                Err(e) => format!("Import failed: {e}"),
+            })
+        }
+        ["instances", "probe"] => {
+            let mainconfig = MainConfig::load(WriteConfig::Dont).unwrap_or_default();
+            InstancePool::global().set_instances(
+                std::iter::once(mainconfig.invidious_instance.clone())
+                    .chain(mainconfig.invidious_instances.iter().cloned())
+                    .collect(),
+                Duration::from_secs(mainconfig.instance_timeout),
+            );
+            let (up, total) = InstancePool::probe_all();
+            Some(format!(
+                "{}\n{up} of {total} instances are up",
+                InstancePool::global().report()
+            ))
+        }
+        ["instances", "pin"] => Some(String::from("Usage: `instances pin {url or number}`")),
         ["loadpage", page, ..] => {
             // Validate loadpage commands from command line to prevent hanging in raw screen
             match *page {
-                "popular" | "trending" | "watchhistory" | "feed" | "library" => {
+                "popular" | "trending" | "watchhistory" | "feed" | "library" | "queue"
+                | "instances" => {
                     if command_parts.len() != 2 {
                         return Some(format!("Usage: `loadpage {}`", page));
                     }
                     None // Let it proceed to TUI
                 }
-                "channel" | "video" | "playlist" => {
+                "channel" | "video" | "playlist" | "localplaylist" | "comments" | "related" => {
                     if command_parts.len() != 3 {
                         return Some(format!("Usage: `loadpage {} {{id/url}}`", page));
                     }
                     None // Let it proceed to TUI
                 }
-                "search" => {
+                "search" | "localsearch" => {
                     if command_parts.len() < 3 {
-                        return Some("Usage: `loadpage search {query}`".to_string());
+                        return Some(format!("Usage: `loadpage {} {{query}}`", page));
+                    }
+                    None // Let it proceed to TUI
+                }
+                "channelsearch" => {
+                    if command_parts.len() < 4 {
+                        return Some(
+                            "Usage: `loadpage channelsearch {id/url} {query}`".to_string(),
+                        );
                     }
                     None // Let it proceed to TUI
                 }
@@ -64,26 +131,26 @@ pub fn text_command(command: &str) -> Option<String> {
                     if remapped_parts.len() >= 2 {
                         let page = remapped_parts[1];
                         match page {
-                            "popular" | "trending" | "watchhistory" | "feed" | "library" => {
-                                if command_parts.len() != 1 {
-                                    return Some(format!("Usage: `{}`", command_parts[0]));
-                                }
+                            "popular" | "trending" | "watchhistory" | "feed" | "library"
+                            | "queue" | "instances"
+                                if command_parts.len() != 1 =>
+                            {
+                                return Some(format!("Usage: `{}`", command_parts[0]));
                             }
-                            "channel" | "video" | "playlist" => {
-                                if command_parts.len() != 2 {
-                                    return Some(format!(
-                                        "Usage: `{} {{id/url}}`",
-                                        command_parts[0]
-                                    ));
-                                }
+                            "channel" | "video" | "playlist" | "localplaylist" | "comments"
+                            | "related"
+                                if command_parts.len() != 2 =>
+                            {
+                                return Some(format!("Usage: `{} {{id/url}}`", command_parts[0]));
                             }
-                            "search" => {
-                                if command_parts.len() < 2 {
-                                    return Some(format!(
-                                        "Usage: `{} {{query}}`",
-                                        command_parts[0]
-                                    ));
-                                }
+                            "search" | "localsearch" if command_parts.len() < 2 => {
+                                return Some(format!("Usage: `{} {{query}}`", command_parts[0]));
+                            }
+                            "channelsearch" if command_parts.len() < 3 => {
+                                return Some(format!(
+                                    "Usage: `{} {{id/url}} {{query}}`",
+                                    command_parts[0]
+                                ));
                             }
                             _ => {}
                         }
@@ -95,11 +162,10 @@ pub fn text_command(command: &str) -> Option<String> {
     }
 }
 
-pub fn run_command(
-    command: &str,
-    framework: &mut Framework,
-    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-) {
+pub fn run_command<B: Backend>(command: &str, framework: &mut Framework, terminal: &mut Terminal<B>)
+where
+    B::Error: 'static,
+{
     command.split(";;").for_each(|single_command| {
         run_single_command(
             &single_command
@@ -113,18 +179,21 @@ pub fn run_command(
 }
 
 /// runs a command in the TUI, returns true if its a loadpage command, false if not
-pub fn run_single_command(
+pub fn run_single_command<B: Backend>(
     command: &[&str],
     framework: &mut Framework,
-    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-) {
+    terminal: &mut Terminal<B>,
+) where
+    B::Error: 'static,
+{
     // match a command splitted by space to a bunch of avaliable commands
     match command {
         [] => {}
         ["bookmark", id] => match LocalStore::get_info(id) {
             Some(item) => {
-                let library = framework.data.global.get_mut::<Library>().unwrap();
-                let _ = library.push(item);
+                let global = &mut framework.data.global;
+                global.get_mut::<LocalIndex>().unwrap().push(&item);
+                let _ = global.get_mut::<Library>().unwrap().push(item);
                 *framework.data.global.get_mut::<Message>().unwrap() =
                     Message::Success(String::from("Bookmark added"))
             }
@@ -137,6 +206,12 @@ pub fn run_single_command(
             let library = framework.data.global.get_mut::<Library>().unwrap();
 
             if library.remove(id) {
+                framework
+                    .data
+                    .global
+                    .get_mut::<LocalIndex>()
+                    .unwrap()
+                    .invalidate();
                 *framework.data.global.get_mut::<Message>().unwrap() =
                     Message::Success(String::from("Bookmark removed"))
             } else {
@@ -147,12 +222,115 @@ pub fn run_single_command(
         ["togglemark", id] => {
             let library = framework.data.global.get_mut::<Library>().unwrap();
             if library.remove(id) {
+                framework
+                    .data
+                    .global
+                    .get_mut::<LocalIndex>()
+                    .unwrap()
+                    .invalidate();
                 *framework.data.global.get_mut::<Message>().unwrap() =
                     Message::Success(String::from("Bookmark removed"))
             } else {
                 run_single_command(&["bookmark", id], framework, terminal);
             }
         }
+        ["watched", id] => {
+            framework
+                .data
+                .global
+                .get_mut::<WatchedVideos>()
+                .unwrap()
+                .mark(id);
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from("Marked as watched"))
+        }
+        ["unwatched", id] => {
+            if framework
+                .data
+                .global
+                .get_mut::<WatchedVideos>()
+                .unwrap()
+                .unmark(id)
+            {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Success(String::from("Marked as unwatched"))
+            } else {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Error(String::from("Video is not marked as watched"))
+            }
+        }
+        ["togglewatched", id] => {
+            if framework
+                .data
+                .global
+                .get::<WatchedVideos>()
+                .unwrap()
+                .contains(id)
+            {
+                run_single_command(&["unwatched", id], framework, terminal);
+            } else {
+                run_single_command(&["watched", id], framework, terminal);
+            }
+        }
+        ["togglehidewatched"] => {
+            let watched = framework.data.global.get_mut::<WatchedVideos>().unwrap();
+            watched.hide = !watched.hide;
+            let hide = watched.hide;
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from(if hide {
+                    "Hiding watched videos"
+                } else {
+                    "Showing watched videos"
+                }));
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::Reload);
+        }
+        ["offline"] => {
+            let offline = !SearchProviderWrapper::offline();
+            run_single_command(
+                &["offline", if offline { "on" } else { "off" }],
+                framework,
+                terminal,
+            );
+        }
+        ["offline", mode @ ("on" | "off")] => {
+            let offline = *mode == "on";
+            SearchProviderWrapper::set_offline(offline);
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from(if offline {
+                    "Offline mode: showing saved videos, playlists and channels"
+                } else {
+                    "Offline mode off"
+                }));
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::Reload);
+        }
+        ["block", args @ ..] => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                match block_rule(args).and_then(|rule| add_filter(rule, framework)) {
+                    Ok(()) => {
+                        framework
+                            .data
+                            .state
+                            .get_mut::<Tasks>()
+                            .unwrap()
+                            .priority
+                            .push(Task::Reload);
+                        Message::Success(String::from("Added a rule to filters.yml"))
+                    }
+                    Err(e) => Message::Error(e.to_string()),
+                };
+        }
         ["rmcache", id] => {
             let res = LocalStore::rm_cache(id);
             let data = paths::data_dir();
@@ -195,6 +373,8 @@ pub fn run_single_command(
                 "watchhistory" => Some(Page::MainMenu(MainMenuPage::History)),
                 "feed" => Some(Page::Feed),
                 "library" => Some(Page::MainMenu(MainMenuPage::Library)),
+                "queue" => Some(Page::MainMenu(MainMenuPage::Queue)),
+                "instances" => Some(Page::Instances),
                 "channel" => {
                     if command.len() != 3 {
                         *framework.data.global.get_mut::<Message>().unwrap() =
@@ -214,6 +394,26 @@ pub fn run_single_command(
                         }
                     }
                 }
+                "channelsearch" => {
+                    if command.len() < 4 {
+                        *framework.data.global.get_mut::<Message>().unwrap() = Message::Message(
+                            String::from("Usage: `loadpage channelsearch {id/url} {query}`"),
+                        );
+                        return;
+                    }
+
+                    match from_channel_url(command[2]) {
+                        Ok(id) => Some(Page::ChannelDisplay(ChannelDisplayPage {
+                            id,
+                            r#type: ChannelDisplayPageType::Search(command[3..].join(" ")),
+                        })),
+                        Err(e) => {
+                            *framework.data.global.get_mut::<Message>().unwrap() =
+                                Message::Error(e);
+                            return;
+                        }
+                    }
+                }
                 "video" => {
                     if command.len() != 3 {
                         *framework.data.global.get_mut::<Message>().unwrap() =
@@ -246,6 +446,50 @@ pub fn run_single_command(
                         }
                     }
                 }
+                "localplaylist" => {
+                    if command.len() != 3 {
+                        *framework.data.global.get_mut::<Message>().unwrap() = Message::Message(
+                            String::from("Usage: `loadpage localplaylist {name}`"),
+                        );
+                        return;
+                    }
+
+                    Some(Page::SingleItem(SingleItemPage::LocalPlaylist(
+                        command[2].to_string(),
+                    )))
+                }
+                "comments" => {
+                    if command.len() != 3 {
+                        *framework.data.global.get_mut::<Message>().unwrap() =
+                            Message::Message(String::from("Usage: `loadpage comments {id/url}`"));
+                        return;
+                    }
+
+                    match from_video_url(command[2]) {
+                        Ok(id) => Some(Page::Comments(id)),
+                        Err(e) => {
+                            *framework.data.global.get_mut::<Message>().unwrap() =
+                                Message::Error(e);
+                            return;
+                        }
+                    }
+                }
+                "related" => {
+                    if command.len() != 3 {
+                        *framework.data.global.get_mut::<Message>().unwrap() =
+                            Message::Message(String::from("Usage: `loadpage related {id/url}`"));
+                        return;
+                    }
+
+                    match from_video_url(command[2]) {
+                        Ok(id) => Some(Page::Related(id)),
+                        Err(e) => {
+                            *framework.data.global.get_mut::<Message>().unwrap() =
+                                Message::Error(e);
+                            return;
+                        }
+                    }
+                }
                 "search" => {
                     if command.len() == 2 {
                         *framework.data.global.get_mut::<Message>().unwrap() =
@@ -260,6 +504,15 @@ pub fn run_single_command(
                     let cloned = search.clone();
                     Some(Page::Search(cloned))
                 }
+                "localsearch" => {
+                    if command.len() == 2 {
+                        *framework.data.global.get_mut::<Message>().unwrap() =
+                            Message::Message(String::from("Usage: `localsearch {query}`"));
+                        return;
+                    }
+
+                    Some(Page::LocalSearch(command[2..].join(" ")))
+                }
                 _ => {
                     *framework.data.global.get_mut::<Message>().unwrap() =
                         Message::Error(format!("Unknown page: `{page}`"));
@@ -278,6 +531,10 @@ pub fn run_single_command(
             }
         }
         ["history", "back"] | ["back"] => {
+            // going back from a page that is still loading only cancels it
+            if cancel_load(framework) {
+                return;
+            }
             let _ = framework.revert_last_history();
             framework
                 .data
@@ -322,11 +579,50 @@ pub fn run_single_command(
                 .push(Task::Reload);
         }
         ["reload", "configs"] => {
+            let res = load_configs(&mut framework.split_clean().0);
+            // transcripts may be in another language now
+            framework
+                .data
+                .global
+                .get_mut::<LocalIndex>()
+                .unwrap()
+                .invalidate();
+            let filters = framework.data.global.get_mut::<Filters>().unwrap();
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(()) => match filters.take_skipped() {
+                    Some(skipped) => Message::Error(skipped),
+                    None => Message::Success(String::from("Config files have been reloaded")),
+                },
+                Err(e) => Message::Error(e.to_string()),
+            };
+        }
+        ["cache", "clear"] => {
+            let cache = ResponseCache::open(
+                framework
+                    .data
+                    .global
+                    .get::<MainConfig>()
+                    .unwrap()
+                    .cache
+                    .clone(),
+            );
+            *framework.data.global.get_mut::<Message>().unwrap() = match cache.clear() {
+                Ok(count) => Message::Success(format!("Removed {count} cached responses")),
+                Err(e) => Message::Error(e.to_string()),
+            };
+        }
+        ["cache", "stats"] => {
+            let cache = ResponseCache::open(
+                framework
+                    .data
+                    .global
+                    .get::<MainConfig>()
+                    .unwrap()
+                    .cache
+                    .clone(),
+            );
             *framework.data.global.get_mut::<Message>().unwrap() =
-                match load_configs(&mut framework.split_clean().0) {
-                    Ok(()) => Message::Success(String::from("Config files have been reloaded")),
-                    Err(e) => Message::Error(e.to_string()),
-                };
+                Message::Message(cache.stats().to_string());
         }
         ["quit"] => {
             framework.data.global.get_mut::<Status>().unwrap().exit = true;
@@ -357,6 +653,7 @@ pub fn run_single_command(
                     .spawn()
             {
                 let _ = child.wait();
+                read_watch_later(framework);
             }
         }
         ["parrun", ..] => {
@@ -365,11 +662,419 @@ pub fn run_single_command(
                 Message::Success(command.clone());
             let shell = &framework.data.global.get::<MainConfig>().unwrap().shell;
             let shell_flag = shell_flag(shell);
-            let _ = Command::new(shell)
+            let Ok(mut child) = Command::new(shell)
                 .args([shell_flag, &command])
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
-                .spawn();
+                .spawn()
+            else {
+                return;
+            };
+
+            // the command may be an external player, which saves its position when it exits
+            framework
+                .data
+                .global
+                .get_mut::<BackgroundFetch>()
+                .unwrap()
+                .start_task(Box::new(move || Ok(child.wait()?)), |framework, _| {
+                    read_watch_later(framework)
+                });
+        }
+        ["queue", action @ ("add" | "addnext"), id] => {
+            let items = match LocalStore::get_info(id) {
+                Some(Item::FullPlaylist(playlist)) => playlist.videos,
+                Some(item @ (Item::MiniVideo(_) | Item::FullVideo(_))) => vec![item],
+                Some(_) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(
+                        String::from("Only videos and playlists can be added to queue"),
+                    );
+                    return;
+                }
+                None => {
+                    let next = *action == "addnext";
+                    fetch_items(framework, id, move |framework, items| {
+                        add_to_queue(framework, items, next)
+                    });
+                    return;
+                }
+            };
+
+            add_to_queue(framework, items, *action == "addnext");
+        }
+        ["queue", "rm", id] => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                if framework.data.global.get_mut::<PlayQueue>().unwrap().remove(id) {
+                    Message::Success(String::from("Removed from queue"))
+                } else {
+                    Message::Error(String::from("No item with that ID found"))
+                };
+            reload_queue_page(framework);
+        }
+        ["queue", "move", from, to] => {
+            let queue = framework.data.global.get_mut::<PlayQueue>().unwrap();
+            // positions are counted from 1, as displayed in the queue page
+            let moved = match (from.parse::<usize>(), to.parse::<usize>()) {
+                (Ok(from), Ok(to)) if from != 0 && to != 0 => queue.move_item(from - 1, to - 1),
+                _ => false,
+            };
+
+            *framework.data.global.get_mut::<Message>().unwrap() = if moved {
+                Message::Success(format!("Moved item from {from} to {to}"))
+            } else {
+                Message::Error(String::from("Invalid position"))
+            };
+            reload_queue_page(framework);
+        }
+        ["queue", "shuffle"] => {
+            framework
+                .data
+                .global
+                .get_mut::<PlayQueue>()
+                .unwrap()
+                .shuffle();
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from("Queue shuffled"));
+            reload_queue_page(framework);
+        }
+        ["queue", "clear"] => {
+            framework
+                .data
+                .global
+                .get_mut::<PlayQueue>()
+                .unwrap()
+                .items
+                .clear();
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from("Queue cleared"));
+            reload_queue_page(framework);
+        }
+        ["queue", "repeat"] => {
+            let repeat = framework.data.global.get::<PlayQueue>().unwrap().repeat;
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Message(format!("Repeat mode: {}", repeat.as_str()));
+        }
+        ["queue", "repeat", mode] => {
+            *framework.data.global.get_mut::<Message>().unwrap() = match QueueRepeat::parse(mode) {
+                Some(repeat) => {
+                    framework.data.global.get_mut::<PlayQueue>().unwrap().repeat = repeat;
+                    Message::Success(format!("Repeat mode set to {mode}"))
+                }
+                None => Message::Error(format!("Unknown repeat mode `{mode}`")),
+            };
+        }
+        #[cfg(feature = "mpv")]
+        ["queue", "next"] => queue_play_next(framework),
+        #[cfg(feature = "mpv")]
+        ["queue", "stop"] => {
+            framework.data.global.get_mut::<PlayQueue>().unwrap().stop();
+            framework
+                .data
+                .global
+                .get::<MpvWrapper>()
+                .unwrap()
+                .command(String::from("stop"), Vec::new());
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Mpv(String::from("Queue stopped"));
+        }
+        #[cfg(not(feature = "mpv"))]
+        ["queue", "next" | "stop"] => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Error(String::from("Feature `mpv` is disabled and not compiled"));
+        }
+        ["plist"] | ["plist", "list"] => {
+            let LocalPlaylists { playlists, .. } =
+                framework.data.global.get::<LocalPlaylists>().unwrap();
+            *framework.data.global.get_mut::<Message>().unwrap() = if playlists.is_empty() {
+                Message::Message(String::from("No local playlists, create one with `plist new`"))
+            } else {
+                Message::Message(format!(
+                    "Playlists: {}",
+                    playlists
+                        .iter()
+                        .map(|playlist| format!("{} ({})", playlist.name, playlist.items.len()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            };
+        }
+        ["plist", "new", name] => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                if !LocalPlaylists::valid_name(name) {
+                    Message::Error(String::from(
+                        "Playlist names can only contain letters, numbers, `-` and `_`",
+                    ))
+                } else if framework
+                    .data
+                    .global
+                    .get_mut::<LocalPlaylists>()
+                    .unwrap()
+                    .create(name)
+                {
+                    Message::Success(format!("Created playlist {name}"))
+                } else {
+                    Message::Error(format!("Playlist {name} already exists"))
+                };
+        }
+        ["plist", "rename", from, to] => {
+            let playlists = framework.data.global.get_mut::<LocalPlaylists>().unwrap();
+            let message = if !LocalPlaylists::valid_name(to) {
+                Message::Error(String::from(
+                    "Playlist names can only contain letters, numbers, `-` and `_`",
+                ))
+            } else if playlists.get(to).is_some() {
+                Message::Error(format!("Playlist {to} already exists"))
+            } else if let Some(playlist) = playlists.get_mut(from) {
+                playlist.name = to.to_string();
+                Message::Success(format!("Renamed playlist {from} to {to}"))
+            } else {
+                Message::Error(format!("Unknown playlist: {from}"))
+            };
+            *framework.data.global.get_mut::<Message>().unwrap() = message;
+        }
+        ["plist", "rm", name] => {
+            *framework.data.global.get_mut::<Message>().unwrap() = if framework
+                .data
+                .global
+                .get_mut::<LocalPlaylists>()
+                .unwrap()
+                .delete(name)
+            {
+                Message::Success(format!("Deleted playlist {name}"))
+            } else {
+                Message::Error(format!("Unknown playlist: {name}"))
+            };
+        }
+        ["plist", "add", name, id] => {
+            if framework
+                .data
+                .global
+                .get::<LocalPlaylists>()
+                .unwrap()
+                .get(name)
+                .is_none()
+            {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Error(format!("Unknown playlist: {name}"));
+                return;
+            }
+
+            let items = match LocalStore::get_info(id) {
+                Some(Item::FullPlaylist(playlist)) => playlist.videos,
+                Some(item @ (Item::MiniVideo(_) | Item::FullVideo(_))) => vec![item],
+                Some(_) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(
+                        String::from("Only videos and playlists can be added to a playlist"),
+                    );
+                    return;
+                }
+                None => {
+                    let name = name.to_string();
+                    fetch_items(framework, id, move |framework, items| {
+                        add_to_local_playlist(framework, &name, items)
+                    });
+                    return;
+                }
+            };
+
+            add_to_local_playlist(framework, name, items);
+        }
+        ["plist", "rm", name, id] => {
+            *framework.data.global.get_mut::<Message>().unwrap() = match framework
+                .data
+                .global
+                .get_mut::<LocalPlaylists>()
+                .unwrap()
+                .get_mut(name)
+            {
+                Some(playlist) => {
+                    if playlist.remove(id) {
+                        Message::Success(format!("Removed from {name}"))
+                    } else {
+                        Message::Error(String::from("No item with that ID found"))
+                    }
+                }
+                None => Message::Error(format!("Unknown playlist: {name}")),
+            };
+            reload_local_playlist_page(framework, name);
+        }
+        ["plist", "move", name, from, to] => {
+            let playlist = match framework
+                .data
+                .global
+                .get_mut::<LocalPlaylists>()
+                .unwrap()
+                .get_mut(name)
+            {
+                Some(playlist) => playlist,
+                None => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Unknown playlist: {name}"));
+                    return;
+                }
+            };
+            // positions are counted from 1, as displayed in the playlist page
+            let moved = match (from.parse::<usize>(), to.parse::<usize>()) {
+                (Ok(from), Ok(to)) if from != 0 && to != 0 => playlist.move_item(from - 1, to - 1),
+                _ => false,
+            };
+
+            *framework.data.global.get_mut::<Message>().unwrap() = if moved {
+                Message::Success(format!("Moved item from {from} to {to}"))
+            } else {
+                Message::Error(String::from("Invalid position"))
+            };
+            reload_local_playlist_page(framework, name);
+        }
+        ["plist", action @ ("queue" | "play"), name] => {
+            let items = match framework.data.global.get::<LocalPlaylists>().unwrap().get(name) {
+                Some(playlist) if !playlist.items.is_empty() => playlist.items.clone(),
+                Some(_) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Playlist {name} is empty"));
+                    return;
+                }
+                None => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Unknown playlist: {name}"));
+                    return;
+                }
+            };
+
+            let count = items.len();
+            let queue = framework.data.global.get_mut::<PlayQueue>().unwrap();
+            if *action == "play" {
+                // play the playlist right away, before anything else in the queue
+                items
+                    .into_iter()
+                    .rev()
+                    .for_each(|item| queue.push_front(item));
+                run_single_command(&["queue", "next"], framework, terminal);
+            } else {
+                items.into_iter().for_each(|item| {
+                    let _ = queue.push(item);
+                });
+                *framework.data.global.get_mut::<Message>().unwrap() = Message::Success(format!(
+                    "Added {count} video{} to queue",
+                    if count == 1 { "" } else { "s" }
+                ));
+            }
+        }
+        ["plist", "view", name] => {
+            run_single_command(&["loadpage", "localplaylist", name], framework, terminal)
+        }
+        ["captions", identifier] => {
+            let id = match from_video_url(identifier) {
+                Ok(id) => id,
+                Err(e) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(e);
+                    return;
+                }
+            };
+            let language = framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .captions_language
+                .clone();
+            let path = Transcript::path(&id, &language);
+            let fetch_id = id.clone();
+            framework
+                .data
+                .global
+                .get_mut::<BackgroundFetch>()
+                .unwrap()
+                .start_task(
+                    Box::new(move || load_transcript(&fetch_id, &language)),
+                    move |framework, res| {
+                        let message = match res {
+                            Ok(transcript) => {
+                                framework
+                                    .data
+                                    .global
+                                    .get_mut::<LocalIndex>()
+                                    .unwrap()
+                                    .add_transcript(&id, &transcript);
+                                Message::Success(format!("Captions saved to {}", path.display()))
+                            }
+                            Err(e) => Message::Error(e),
+                        };
+                        *framework.data.global.get_mut::<Message>().unwrap() = message;
+                    },
+                );
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Message(String::from("Downloading captions"));
+        }
+        ["transcript", "search", ..] => {
+            let id = match framework.data.state.get::<Page>().unwrap() {
+                Page::SingleItem(SingleItemPage::Video(id)) => id.clone(),
+                _ => {
+                    *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(
+                        String::from("Transcripts can only be searched in a video page"),
+                    );
+                    return;
+                }
+            };
+
+            // download the transcript here, so the video item only needs to read it from cache
+            let language = framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .captions_language
+                .clone();
+            let query = command[2..].join(" ");
+            let fetch_id = id.clone();
+            framework
+                .data
+                .global
+                .get_mut::<BackgroundFetch>()
+                .unwrap()
+                .start_task(
+                    Box::new(move || load_transcript(&fetch_id, &language)),
+                    move |framework, res| {
+                        let transcript = match res {
+                            Ok(transcript) => transcript,
+                            Err(e) => {
+                                *framework.data.global.get_mut::<Message>().unwrap() =
+                                    Message::Error(e);
+                                return;
+                            }
+                        };
+                        framework
+                            .data
+                            .global
+                            .get_mut::<LocalIndex>()
+                            .unwrap()
+                            .add_transcript(&id, &transcript);
+
+                        // the video page may have been left while downloading
+                        if framework.data.state.get::<Page>().unwrap()
+                            != &Page::SingleItem(SingleItemPage::Video(id))
+                        {
+                            return;
+                        }
+                        *framework.data.global.get_mut::<Message>().unwrap() = Message::None;
+                        framework
+                            .data
+                            .global
+                            .get_mut::<Status>()
+                            .unwrap()
+                            .storage
+                            .insert::<TranscriptSearch>(TranscriptSearch(query));
+                        framework
+                            .data
+                            .state
+                            .get_mut::<Tasks>()
+                            .unwrap()
+                            .priority
+                            .push(Task::RenderAll);
+                    },
+                );
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Message(String::from("Loading transcript"));
         }
         #[cfg(feature = "clipboard")]
         ["copy", ..] => {
@@ -383,6 +1088,11 @@ pub fn run_single_command(
                 "Feature `clipboard` is disabled and not compiled",
             ));
         }
+        ["sync", _] | ["syncall"] if SearchProviderWrapper::offline() => {
+            *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(String::from(
+                "Subscriptions cannot be synced in offline mode, turn it off with `offline off`",
+            ));
+        }
         ["sync", identifier] => {
             let id = if identifier.len() == 24 {
                 identifier.to_string()
@@ -417,6 +1127,12 @@ pub fn run_single_command(
                     Ok(()) => Message::Success(String::from("Channel synced")),
                     Err(e) => Message::Error(format!("Sync failed: {e}")),
                 };
+            framework
+                .data
+                .global
+                .get_mut::<LocalIndex>()
+                .unwrap()
+                .invalidate();
 
             if framework.data.state.get::<Page>().unwrap() == &Page::Feed {
                 let tasks = framework.data.state.get_mut::<Tasks>().unwrap();
@@ -468,6 +1184,12 @@ pub fn run_single_command(
             } else {
                 Message::Error(String::from("Channel not found in subscriptions"))
             };
+            framework
+                .data
+                .global
+                .get_mut::<LocalIndex>()
+                .unwrap()
+                .invalidate();
 
             if framework.data.state.get::<Page>().unwrap() == &Page::Feed {
                 let tasks = framework.data.state.get_mut::<Tasks>().unwrap();
@@ -506,6 +1228,12 @@ pub fn run_single_command(
                 .get_mut::<Subscriptions>()
                 .unwrap()
                 .sync(image_index, download_thumbnails, syncing);
+            framework
+                .data
+                .global
+                .get_mut::<LocalIndex>()
+                .unwrap()
+                .invalidate();
 
             let message = Message::Success(format!(
                 "Subscriptions synced: {success} success{} | {failed} fail | {cached} cached",
@@ -537,6 +1265,157 @@ pub fn run_single_command(
                     .push(Task::RenderAll);
             }
         }
+        ["subscriptions", "import", path @ ..] if !path.is_empty() => {
+            let message = match framework
+                .data
+                .global
+                .get_mut::<Subscriptions>()
+                .unwrap()
+                .import_opml(&paths::expand_home(&path.join(" ")))
+            {
+                Ok((added, existing)) => Message::Success(format!(
+                    "Imported {added} channels ({existing} already subscribed), run `syncall` to fetch their videos"
+                )),
+                Err(e) => Message::Error(format!("Import failed: {e}")),
+            };
+            framework
+                .data
+                .global
+                .get_mut::<LocalIndex>()
+                .unwrap()
+                .invalidate();
+
+            if framework.data.state.get::<Page>().unwrap() == &Page::Feed {
+                let tasks = framework.data.state.get_mut::<Tasks>().unwrap();
+                tasks.priority.push(Task::Reload);
+                tasks.priority.push(Task::Custom(TaskFunction::new(Arc::new(
+                    move |framework| {
+                        *framework.data.global.get_mut::<Message>().unwrap() = message.clone();
+                    },
+                ))));
+            } else {
+                *framework.data.global.get_mut::<Message>().unwrap() = message;
+            }
+        }
+        ["subscriptions", "export", path @ ..] if !path.is_empty() => {
+            *framework.data.global.get_mut::<Message>().unwrap() = match framework
+                .data
+                .global
+                .get::<Subscriptions>()
+                .unwrap()
+                .export_opml(&paths::expand_home(&path.join(" ")))
+            {
+                Ok(count) => Message::Success(format!("Exported {count} channels")),
+                Err(e) => Message::Error(format!("Export failed: {e}")),
+            };
+        }
+        ["import", "write", path @ ..] if !path.is_empty() => {
+            let import = match Import::from_path(&paths::expand_home(&path.join(" "))) {
+                Ok(import) => import,
+                Err(e) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Import failed: {e}"));
+                    return;
+                }
+            };
+
+            let history_limit = framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .limits
+                .watch_history;
+
+            let mut subscriptions = framework.data.global.remove::<Subscriptions>().unwrap();
+            let mut history = framework.data.global.remove::<WatchHistory>().unwrap();
+            let mut library = framework.data.global.remove::<Library>().unwrap();
+            let (channels, videos, bookmarks) = import.apply(
+                &mut subscriptions,
+                &mut history,
+                &mut library,
+                history_limit,
+            );
+            framework.data.global.insert::<Subscriptions>(subscriptions);
+            framework.data.global.insert::<WatchHistory>(history);
+            framework.data.global.insert::<Library>(library);
+            framework
+                .data
+                .global
+                .get_mut::<LocalIndex>()
+                .unwrap()
+                .invalidate();
+
+            let message = Message::Success(format!(
+                "Imported {channels} channels | {videos} watched videos | {bookmarks} library videos"
+            ));
+
+            if matches!(
+                framework.data.state.get::<Page>().unwrap(),
+                Page::Feed
+                    | Page::MainMenu(MainMenuPage::History)
+                    | Page::MainMenu(MainMenuPage::Library)
+            ) {
+                let tasks = framework.data.state.get_mut::<Tasks>().unwrap();
+                tasks.priority.push(Task::Reload);
+                tasks.priority.push(Task::Custom(TaskFunction::new(Arc::new(
+                    move |framework| {
+                        *framework.data.global.get_mut::<Message>().unwrap() = message.clone();
+                    },
+                ))));
+            } else {
+                *framework.data.global.get_mut::<Message>().unwrap() = message;
+            }
+        }
+        ["import", path @ ..] if !path.is_empty() => {
+            let path = path.join(" ");
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                match Import::from_path(&paths::expand_home(&path)) {
+                    Ok(import) => Message::Message(format!(
+                        "{}, run `import write {path}` to import",
+                        import.report(
+                            framework.data.global.get::<Subscriptions>().unwrap(),
+                            framework.data.global.get::<WatchHistory>().unwrap(),
+                            framework.data.global.get::<Library>().unwrap(),
+                            framework
+                                .data
+                                .global
+                                .get::<MainConfig>()
+                                .unwrap()
+                                .limits
+                                .watch_history,
+                        )
+                    )),
+                    Err(e) => Message::Error(format!("Import failed: {e}")),
+                };
+        }
+        ["instances", "probe"] => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Message(String::from("Probing instances..."));
+            terminal.draw(|frame| framework.render(frame)).unwrap();
+
+            let (up, total) = InstancePool::probe_all();
+            let message = if up == 0 {
+                Message::Error(format!("None of the {total} instances are up"))
+            } else {
+                Message::Success(format!("{up} of {total} instances are up"))
+            };
+            show_instances_message(framework, message);
+        }
+        ["instances", "pin", instance] => {
+            let message = match InstancePool::global().pin(instance) {
+                Ok(url) => Message::Success(format!("Pinned {url}")),
+                Err(e) => Message::Error(e),
+            };
+            show_instances_message(framework, message);
+        }
+        ["instances", "unpin"] => {
+            InstancePool::global().pinned = None;
+            let message = Message::Success(String::from(
+                "Unpinned, requests go to the fastest instance",
+            ));
+            show_instances_message(framework, message);
+        }
         ["key", keycode, modifier] => {
             let (keycodeserde, modifier) =
                 match (|| -> Result<(KeyCodeSerde, u8), Box<dyn Error>> {
@@ -655,6 +1534,242 @@ pub fn run_single_command(
     }
 }
 
+/// loads the next item in the play queue into the embedded mpv player
+#[cfg(feature = "mpv")]
+fn queue_play_next(framework: &mut Framework) {
+    let item = match framework.data.global.get_mut::<PlayQueue>().unwrap().advance() {
+        Some(item) => item,
+        None => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Mpv(String::from("Queue finished"));
+            reload_queue_page(framework);
+            return;
+        }
+    };
+
+    let id = item.id().unwrap();
+    let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+    // the saved file is played in offline mode
+    let url = offline_file(id, mainconfig).unwrap_or_else(|| {
+        match framework.data.global.get::<Status>().unwrap().provider {
+            Provider::Invidious => format!("{}/embed/{id}", mainconfig.invidious_instance),
+            Provider::YouTube => format!("https://youtube.com/embed/{id}"),
+        }
+    });
+
+    let mpv = framework.data.global.get::<MpvWrapper>().unwrap();
+    mpv.set_property(String::from("pause"), String::from("no"));
+    let res = mpv.command(String::from("loadfile"), vec![url]);
+
+    *framework.data.global.get_mut::<Message>().unwrap() = match res {
+        MpvResponse::Copy => Message::Mpv(format!("Now playing: {item}")),
+        MpvResponse::Error(e) => {
+            framework.data.global.get_mut::<PlayQueue>().unwrap().stop();
+            Message::Error(format!("MPV error: {e}"))
+        }
+        _ => unreachable!(),
+    };
+    reload_queue_page(framework);
+}
+
+/// the queue page displays a copy of the queue, so it needs to be reloaded when the queue changes
+/// records positions saved by an external player that has exited, and updates the envs of the
+/// current page with them
+fn read_watch_later(framework: &mut Framework) {
+    framework
+        .data
+        .global
+        .get_mut::<WatchPositions>()
+        .unwrap()
+        .read_watch_later();
+    update_provider(&mut framework.data);
+}
+
+/// fetches the info of a video, or the videos of a mix, that is not saved on a background thread,
+/// then `then` is ran with them as a task
+fn fetch_items(
+    framework: &mut Framework,
+    id: &str,
+    then: impl FnOnce(&mut Framework, Vec<Item>) + 'static,
+) {
+    let mainconfig = framework.data.global.get::<MainConfig>().unwrap().clone();
+    let id = id.to_string();
+    let fetch: Fetch<Vec<Item>> = if is_mix(&id) {
+        Box::new(move || match load_playlist(&id, &mainconfig)? {
+            Item::FullPlaylist(mix) => Ok(mix.videos),
+            _ => unreachable!(),
+        })
+    } else {
+        Box::new(move || {
+            load_video(&id, &mainconfig)
+                .map(|item| vec![item])
+                .map_err(|_| format!("Unknown item: {id}").into())
+        })
+    };
+
+    framework
+        .data
+        .global
+        .get_mut::<BackgroundFetch>()
+        .unwrap()
+        .start_task(fetch, move |framework, res| match res {
+            Ok(items) => then(framework, items),
+            Err(e) => *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(e),
+        });
+    *framework.data.global.get_mut::<Message>().unwrap() =
+        Message::Message(String::from("Loading video info"));
+}
+
+/// saves the info of items added to the queue or a local playlist, as they are saved as a list of
+/// ids and the info is needed to load them again
+fn save_items_info(items: &[Item]) {
+    for item in items.iter() {
+        let id = item.id().unwrap();
+        if LocalStore::get_info(id).is_none() {
+            LocalStore::set_info(id.to_string(), item.clone(), true);
+        }
+    }
+}
+
+fn add_to_queue(framework: &mut Framework, items: Vec<Item>, next: bool) {
+    save_items_info(&items);
+
+    let count = items.len();
+    let queue = framework.data.global.get_mut::<PlayQueue>().unwrap();
+    if next {
+        items
+            .into_iter()
+            .rev()
+            .for_each(|item| queue.push_front(item));
+    } else {
+        items.into_iter().for_each(|item| {
+            let _ = queue.push(item);
+        });
+    }
+
+    *framework.data.global.get_mut::<Message>().unwrap() =
+        Message::Success(format!("Added {count} item(s) to queue"));
+    reload_queue_page(framework);
+}
+
+fn add_to_local_playlist(framework: &mut Framework, name: &str, items: Vec<Item>) {
+    save_items_info(&items);
+
+    let count = items.len();
+    // the playlist may have been deleted while fetching
+    let Some(playlist) = framework
+        .data
+        .global
+        .get_mut::<LocalPlaylists>()
+        .unwrap()
+        .get_mut(name)
+    else {
+        *framework.data.global.get_mut::<Message>().unwrap() =
+            Message::Error(format!("Unknown playlist: {name}"));
+        return;
+    };
+    for item in items {
+        let item = match item {
+            Item::FullVideo(video) => Item::MiniVideo(MiniVideoItem::from(video)),
+            item => item,
+        };
+        let _ = playlist.push(item);
+    }
+
+    *framework.data.global.get_mut::<Message>().unwrap() = Message::Success(format!(
+        "Added {count} video{} to {name}",
+        if count == 1 { "" } else { "s" }
+    ));
+    reload_local_playlist_page(framework, name);
+}
+
+fn reload_local_playlist_page(framework: &mut Framework, name: &str) {
+    if framework.data.state.get::<Page>().unwrap()
+        == &Page::SingleItem(SingleItemPage::LocalPlaylist(name.to_string()))
+    {
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::Reload);
+    }
+}
+
+fn reload_queue_page(framework: &mut Framework) {
+    if framework.data.state.get::<Page>().unwrap() == &Page::MainMenu(MainMenuPage::Queue) {
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::Reload);
+    }
+}
+
+/// reloads the instances page if it is opened, then shows the message
+fn show_instances_message(framework: &mut Framework, message: Message) {
+    if framework.data.state.get::<Page>().unwrap() != &Page::Instances {
+        *framework.data.global.get_mut::<Message>().unwrap() = message;
+        return;
+    }
+
+    let tasks = framework.data.state.get_mut::<Tasks>().unwrap();
+    tasks.priority.push(Task::Reload);
+    tasks.priority.push(Task::Custom(TaskFunction::new(Arc::new(
+        move |framework| {
+            *framework.data.global.get_mut::<Message>().unwrap() = message.clone();
+        },
+    ))));
+}
+
+/// cancels the page being loaded and goes back to the page before it, a page being reloaded stays
+/// as it was, returns false if no page is being loaded
+pub fn cancel_load(framework: &mut Framework) -> bool {
+    let Some(load) = framework
+        .data
+        .global
+        .get_mut::<PageLoader>()
+        .unwrap()
+        .cancel()
+    else {
+        return false;
+    };
+
+    // only a reload has no page command
+    if load.command.is_some() && framework.revert_last_history().is_ok() {
+        let state_envs = framework.data.state.get_mut::<StateEnvs>().unwrap();
+        set_envs(state_envs.clone().0.into_iter(), &mut state_envs.0);
+        update_provider(&mut framework.data);
+        framework
+            .data
+            .global
+            .get_mut::<Status>()
+            .unwrap()
+            .render_image = true;
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::ClearPage);
+    }
+
+    *framework.data.global.get_mut::<Message>().unwrap() =
+        Message::Message(String::from("Loading cancelled"));
+    framework
+        .data
+        .state
+        .get_mut::<Tasks>()
+        .unwrap()
+        .priority
+        .push(Task::RenderAll);
+    true
+}
+
 /// Returns the appropriate shell flag for the given shell command.
 ///
 /// On Windows, `cmd` and `cmd.exe` use `/C`, `powershell`/`pwsh` use `-Command`,
@@ -710,6 +1825,70 @@ const HELLO_WORLDS: &[&str] = &[
     "<h1>Hello World<\\h1>",
 ];
 
+/// the rule added by `block`, without a value it uses the hovered item
+fn block_rule(args: &[&str]) -> Result<FilterRule, Box<dyn Error>> {
+    // the hovered item in lists, or the item of the page
+    let hovered = |key: &str| {
+        env::var(format!("hover-{key}"))
+            .or_else(|_| env::var(key))
+            .map_err(|_| Errors::StrError("No item is hovered"))
+    };
+
+    Ok(match args {
+        [] | ["channel"] => FilterRule {
+            channel: Some(hovered("channel-id")?),
+            ..Default::default()
+        },
+        ["channel", id] => FilterRule {
+            channel: Some(id.to_string()),
+            ..Default::default()
+        },
+        ["title"] => FilterRule {
+            title: Some(format!("^{}$", regex::escape(&hovered("title")?))),
+            ..Default::default()
+        },
+        ["title", pattern @ ..] => FilterRule {
+            title: Some(pattern.join(" ")),
+            ..Default::default()
+        },
+        ["keyword", words @ ..] if !words.is_empty() => FilterRule {
+            keywords: words.iter().map(|word| word.to_string()).collect(),
+            ..Default::default()
+        },
+        ["shorts"] => FilterRule {
+            shorts: Some(true),
+            ..Default::default()
+        },
+        ["live"] => FilterRule {
+            live: Some(true),
+            ..Default::default()
+        },
+        ["upcoming"] => FilterRule {
+            upcoming: Some(true),
+            ..Default::default()
+        },
+        _ => {
+            return Err(Errors::StrError(
+                "Usage: `block [channel/title/keyword/shorts/live/upcoming] [value]`",
+            )
+            .into())
+        }
+    })
+}
+
+/// adds a rule to the filters in use and saves it to `filters.yml`
+fn add_filter(rule: FilterRule, framework: &mut Framework) -> Result<(), Box<dyn Error>> {
+    framework
+        .data
+        .global
+        .get_mut::<Filters>()
+        .unwrap()
+        .push(rule.clone())?;
+    let config = framework.data.global.get_mut::<FiltersConfig>().unwrap();
+    config.rules.push(rule);
+    config.save()
+}
+
 fn help_msg(cmdefines: &CommandsRemapConfig) -> String {
     format!("\x1b[32mYouTube TUI commands\x1b[0m
 
@@ -730,11 +1909,17 @@ fn help_msg(cmdefines: &CommandsRemapConfig) -> String {
     \x1b[33mloadpage subscriptions\x1b[0m          Loads the subscriptions page
     \x1b[33mloadpage bookmarks\x1b[0m              Loads the bookmarks page
     \x1b[33mloadpage library\x1b[0m                Loads the library (saved items) page
+    \x1b[33mloadpage queue\x1b[0m                  Loads the play queue page
     \x1b[33mloadpage feed\x1b[0m                   Loads the library (feed) page
     \x1b[33mloadpage search [query]\x1b[0m         Loads the search page with the given query
     \x1b[33mloadpage video [identifier]\x1b[0m     Loads the video item page
     \x1b[33mloadpage playlist [identifier]\x1b[0m  Loads the playlist item page
+    \x1b[33mloadpage localplaylist [name]\x1b[0m   Loads a local playlist
     \x1b[33mloadpage channel [identifier]\x1b[0m   Loads the channel item page
+    \x1b[33mloadpage comments [identifier]\x1b[0m  Loads the comments of a video
+    \x1b[33mloadpage related [identifier]\x1b[0m   Loads videos related to a video
+    \x1b[33mloadpage localsearch [query]\x1b[0m    Searches the library, watch history and subscriptions
+    \x1b[33mloadpage instances\x1b[0m              Loads the Invidious instances page
 
 \x1b[91mHISTORY:\x1b[0m
     \x1b[33mhistory back\x1b[0m                    Revert back to previous state
@@ -743,6 +1928,8 @@ fn help_msg(cmdefines: &CommandsRemapConfig) -> String {
 \x1b[91mUTILITY:\x1b[0m
     \x1b[33mreload\x1b[0m                          Reloads the current page
     \x1b[33mreload configs\x1b[0m                  Reload all config files
+    \x1b[33mcache clear\x1b[0m                     Remove all cached responses from the search provider
+    \x1b[33mcache stats\x1b[0m                     Show the number and size of cached responses
     \x1b[33mflush\x1b[0m                           Run all tasks in queue immediately
     \x1b[33mquit\x1b[0m                            Immediately exit
     \x1b[33mrun [command]\x1b[0m                   Runs a system command (e.g. `run rm -rf / --no-preserve-root`)
@@ -750,14 +1937,55 @@ fn help_msg(cmdefines: &CommandsRemapConfig) -> String {
     \x1b[33mcopy [text]\x1b[0m                     Copies text to clipboard
     \x1b[33mkey [keycode] [keymodifier]\x1b[0m     Create a key input event
     \x1b[33mecho [mode] [message]\x1b[0m           Displays a message in message bar, mode: none, success, warn, error, mpv (can be overwritten by mpv player)
+    \x1b[33mcaptions [id or url]\x1b[0m            Download captions of a video in the preferred language
+    \x1b[33mtranscript search [query]\x1b[0m       Search the transcript in the current video page, empty query shows all lines
+    \x1b[33minstances probe\x1b[0m                 Check which Invidious instances are up, and how fast they respond
+    \x1b[33minstances pin [url or number]\x1b[0m   Only use one Invidious instance until the app is closed
+    \x1b[33minstances unpin\x1b[0m                 Use the fastest Invidious instance that is up again
 
 \x1b[91mLIBRARY:\x1b[0m
     \x1b[33mbookmark [id]\x1b[0m                   Bookmark item with ID (item must be already loaded)
     \x1b[33munmark [id]\x1b[0m                     Remove bookmark item with ID
     \x1b[33mtogglemark [id]\x1b[0m                 Toggle bookmark status
+    \x1b[33mwatched [id]\x1b[0m                    Mark video as watched
+    \x1b[33munwatched [id]\x1b[0m                  Mark video as unwatched
+    \x1b[33mtogglewatched [id]\x1b[0m              Toggle watched status
+    \x1b[33mtogglehidewatched\x1b[0m               Hide or show watched videos in feeds, search and channel videos
+    \x1b[33moffline [on/off]\x1b[0m                Read from saved data instead of the network, toggles without an argument
+    \x1b[33mblock channel [id]\x1b[0m              Hide items of a channel, the hovered item's channel without an id
+    \x1b[33mblock title [regex]\x1b[0m             Hide items with a matching title, the hovered item's title without a regex
+    \x1b[33mblock keyword [words]\x1b[0m           Hide items with any of the words in their title or description
+    \x1b[33mblock shorts/live/upcoming\x1b[0m      Hide shorts, live streams or upcoming videos
     \x1b[33msub/sync [id or url]\x1b[0m            Add channel to subscription, or sync an existing channel
     \x1b[33munsub [id or url]\x1b[0m               Remove channel from subscription
     \x1b[33msyncall\x1b[0m                         Sync all subscriptions
+    \x1b[33msubscriptions import [file]\x1b[0m     Subscribe to all YouTube channels in an OPML file
+    \x1b[33msubscriptions export [file]\x1b[0m     Save all subscribed channels to an OPML file
+    \x1b[33mimport [file or dir]\x1b[0m            Show what would be imported from NewPipe or Google Takeout exports
+    \x1b[33mimport write [file or dir]\x1b[0m      Import subscriptions, watch history and playlists into library
+
+\x1b[91mQUEUE:\x1b[0m
+    \x1b[33mqueue add [id]\x1b[0m                  Add a video, or all videos in a playlist to the end of the queue
+    \x1b[33mqueue addnext [id]\x1b[0m              Add a video, or all videos in a playlist to the front of the queue
+    \x1b[33mqueue rm [id]\x1b[0m                   Remove a video from the queue
+    \x1b[33mqueue move [from] [to]\x1b[0m          Move a video in the queue to another position
+    \x1b[33mqueue shuffle\x1b[0m                   Shuffle the queue
+    \x1b[33mqueue clear\x1b[0m                     Remove all videos from the queue
+    \x1b[33mqueue repeat [off/one/all]\x1b[0m      Set repeat mode
+    \x1b[33mqueue next\x1b[0m                      Play the next video in the queue with mpv
+    \x1b[33mqueue stop\x1b[0m                      Stop playing the queue
+
+\x1b[91mLOCAL PLAYLISTS:\x1b[0m
+    \x1b[33mplist list\x1b[0m                      List all local playlists
+    \x1b[33mplist new [name]\x1b[0m                Create an empty playlist
+    \x1b[33mplist rename [name] [new name]\x1b[0m  Rename a playlist
+    \x1b[33mplist rm [name]\x1b[0m                 Delete a playlist
+    \x1b[33mplist add [name] [id]\x1b[0m           Add a video, or all videos in a playlist to a local playlist
+    \x1b[33mplist rm [name] [id]\x1b[0m            Remove a video from a playlist
+    \x1b[33mplist move [name] [from] [to]\x1b[0m   Move a video in a playlist to another position
+    \x1b[33mplist view [name]\x1b[0m               View a playlist
+    \x1b[33mplist queue [name]\x1b[0m              Add all videos in a playlist to the queue
+    \x1b[33mplist play [name]\x1b[0m               Play all videos in a playlist with mpv, through the queue
 
 \x1b[91mMPV:\x1b[0m
     \x1b[33mmpv prop [label]\x1b[0m                Gets mpv property
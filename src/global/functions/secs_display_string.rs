@@ -27,6 +27,13 @@ pub fn secs_display_string(mut secs: u32) -> String {
     out.join(":")
 }
 
+/// undoes `secs_display_string()`, such as `1:02:43` to `3763`, 0 if the text is not a length
+pub fn parse_length(text: &str) -> u32 {
+    text.split(':')
+        .try_fold(0, |secs, part| Some(secs * 60 + part.parse::<u32>().ok()?))
+        .unwrap_or_default()
+}
+
 fn two_digit_num(num: u32) -> String {
     let out = num.to_string();
 
@@ -1,41 +1,82 @@
 use crate::{
     config::MainConfig,
-    global::{functions::download_all_images, structs::Item, traits::SearchProviderWrapper},
+    global::{
+        common::universal::Playlist,
+        functions::download_all_images,
+        structs::{Errors, Item, Transcript},
+        traits::SearchProviderWrapper,
+    },
 };
 use std::error::Error;
 
-pub fn load_playlist(id: &str, mainconfig: &MainConfig) -> Result<Item, Box<dyn Error>> {
-    let playlist =
-        Item::from_full_playlist(SearchProviderWrapper::playlist(id)?, mainconfig.image_index);
-    let videos = &playlist.fullplaylist()?.videos;
-
-    if mainconfig.images.display() {
-        download_all_images({
-            let mut items = videos.iter().map(|item| item.into()).collect::<Vec<_>>();
-            items.extend([(&playlist).into()]);
-            items
-        });
+/// mixes (radio playlists) have ids starting with `RD`, and are loaded with `mix()`
+pub fn is_mix(id: &str) -> bool {
+    id.starts_with("RD")
+}
+
+/// fetches a playlist or a mix, later pages of a playlist are loaded from its continuation
+pub fn fetch_playlist(id: &str) -> Result<Playlist, Box<dyn Error>> {
+    if is_mix(id) {
+        Ok(SearchProviderWrapper::mix(id)?.into())
+    } else {
+        SearchProviderWrapper::playlist(id, None)
+    }
+}
+
+/// downloads the thumbnails of an item, and of the videos in it for a playlist
+pub fn download_item_images(item: &Item, mainconfig: &MainConfig) {
+    if !mainconfig.images.display() {
+        return;
     }
 
+    let mut items = match item.fullplaylist() {
+        Ok(playlist) => playlist.videos.iter().map(|item| item.into()).collect(),
+        Err(_) => Vec::new(),
+    };
+    items.push(item.into());
+    download_all_images(items);
+}
+
+pub fn load_playlist(id: &str, mainconfig: &MainConfig) -> Result<Item, Box<dyn Error>> {
+    let playlist = Item::from_full_playlist(fetch_playlist(id)?, mainconfig.image_index);
+    download_item_images(&playlist, mainconfig);
     Ok(playlist)
 }
 
 pub fn load_video(id: &str, mainconfig: &MainConfig) -> Result<Item, Box<dyn Error>> {
     let video = Item::from_full_video(SearchProviderWrapper::video(id)?, mainconfig.image_index);
-    if mainconfig.images.display() {
-        download_all_images(vec![(&video).into()]);
-    }
-
+    download_item_images(&video, mainconfig);
     Ok(video)
 }
 
 pub fn load_channel(id: &str, mainconfig: &MainConfig) -> Result<Item, Box<dyn Error>> {
     let channel =
         Item::from_full_channel(SearchProviderWrapper::channel(id)?, mainconfig.image_index);
+    download_item_images(&channel, mainconfig);
+    Ok(channel)
+}
 
-    if mainconfig.images.display() {
-        download_all_images(vec![(&channel).into()]);
+/// load the transcript of a video from cache, or download it in `language` (the
+/// `captions_language` of the main config)
+pub fn load_transcript(id: &str, language: &str) -> Result<Transcript, Box<dyn Error>> {
+    if let Some(transcript) = Transcript::load(id, language) {
+        return Ok(transcript);
     }
 
-    Ok(channel)
+    let captions = SearchProviderWrapper::captions(id)?;
+    // exact match, then same language in a different region (e.g. `en-GB` for `en`), then anything
+    let caption = captions
+        .iter()
+        .find(|caption| caption.language == language)
+        .or_else(|| {
+            captions
+                .iter()
+                .find(|caption| caption.language.split('-').next() == language.split('-').next())
+        })
+        .or_else(|| captions.first())
+        .ok_or(Errors::StrError("No captions avaliable for this video"))?;
+
+    let transcript = SearchProviderWrapper::transcript(caption)?;
+    transcript.save(id, language)?;
+    Ok(transcript)
 }
@@ -1,14 +1,16 @@
-use std::env;
+use std::{env, path::Path};
 use tui_additions::framework::FrameworkData;
+use urlencoding::encode;
 
 use crate::{config::*, global::structs::*};
 
-use super::set_envs;
+use super::{offline_file, set_envs};
 
 pub fn update_provider(data: &mut FrameworkData) {
     let mainconfig = data.global.get::<MainConfig>().unwrap();
     let status = data.global.get::<Status>().unwrap();
     let page = data.state.get::<Page>().unwrap();
+    let positions = data.global.get::<WatchPositions>().unwrap();
 
     let envs = match page {
         Page::ChannelDisplay(ChannelDisplayPage { id, r#type }) => vec![(
@@ -20,9 +22,16 @@ pub fn update_provider(data: &mut FrameworkData) {
                     Provider::Invidious => &mainconfig.invidious_instance,
                 },
                 match r#type {
-                    ChannelDisplayPageType::Main => "",
-                    ChannelDisplayPageType::Videos => "/videos",
-                    ChannelDisplayPageType::Playlists => "/playlists",
+                    ChannelDisplayPageType::Main => String::new(),
+                    ChannelDisplayPageType::Videos => String::from("/videos"),
+                    ChannelDisplayPageType::Shorts => String::from("/shorts"),
+                    ChannelDisplayPageType::Streams => String::from("/streams"),
+                    ChannelDisplayPageType::Playlists => String::from("/playlists"),
+                    ChannelDisplayPageType::Podcasts => String::from("/podcasts"),
+                    ChannelDisplayPageType::Search(query) => match status.provider {
+                        Provider::YouTube => format!("/search?query={}", encode(query)),
+                        Provider::Invidious => format!("/search?q={}", encode(query)),
+                    },
                 }
             ),
         )],
@@ -82,6 +91,15 @@ pub fn update_provider(data: &mut FrameworkData) {
                 }
             },
         )],
+        Page::MainMenu(MainMenuPage::Queue) => vec![(
+            String::from("url"),
+            match status.provider {
+                Provider::YouTube => String::from("https://www.youtube.com/feed/library"),
+                Provider::Invidious => {
+                    format!("{}/feed/library", mainconfig.invidious_instance)
+                }
+            },
+        )],
         Page::SingleItem(SingleItemPage::Video(id)) => vec![
             (
                 String::from("url"),
@@ -101,6 +119,16 @@ pub fn update_provider(data: &mut FrameworkData) {
                     Provider::YouTube => format!("https://youtube.com/embed/{id}"),
                 },
             ),
+            (
+                String::from("play-url"),
+                // the saved file is played in offline mode
+                offline_file(id, mainconfig).unwrap_or_else(|| match status.provider {
+                    Provider::Invidious => {
+                        format!("{}/embed/{}", mainconfig.invidious_instance, id)
+                    }
+                    Provider::YouTube => format!("https://youtube.com/embed/{id}"),
+                }),
+            ),
             (
                 String::from("channel-url"),
                 match status.provider {
@@ -117,73 +145,57 @@ pub fn update_provider(data: &mut FrameworkData) {
                     ),
                 },
             ),
-        ],
-        Page::SingleItem(SingleItemPage::Playlist(id)) => vec![
             (
-                String::from("url"),
-                match status.provider {
-                    Provider::YouTube => {
-                        format!("https://www.youtube.com/playlist?list={id}")
-                    }
-                    Provider::Invidious => {
-                        format!("{}/playlist?list={}", mainconfig.invidious_instance, id)
-                    }
-                },
+                String::from("resume-position"),
+                positions.start(id).unwrap_or_default().to_string(),
             ),
             (
-                String::from("all-videos"),
-                match status.provider {
-                    Provider::YouTube => env::var("all-ids")
-                        .unwrap_or(String::from("env_not_found"))
-                        .split(' ')
-                        .map(|id| format!("'https://youtu.be/{id}'"))
-                        .collect::<Vec<_>>()
-                        .join(" "),
-                    Provider::Invidious => env::var("all-ids")
-                        .unwrap_or(String::from("env_not_found"))
-                        .split(' ')
-                        .map(|id| format!("'{}/watch?v={id}'", mainconfig.invidious_instance,))
-                        .collect::<Vec<_>>()
-                        .join(" "),
-                },
-            ),
-            (
-                String::from("channel-url"),
-                match status.provider {
-                    Provider::YouTube => format!(
-                        "https://www.youtube.com/channel/{}",
-                        env::var("channel-id").unwrap_or(String::from("env_not_found"))
-                    ),
-                    Provider::Invidious => format!(
-                        "{}/channel/{}",
-                        mainconfig.invidious_instance,
-                        env::var("channel-id").unwrap_or(String::from("env_not_found"))
-                    ),
-                },
-            ),
-            (
-                String::from("mpv-queuelist"),
-                match status.provider {
-                    Provider::YouTube => env::var("all-ids")
-                        .unwrap_or(String::from("env_not_found"))
-                        .split(' ')
-                        .map(|id| format!("mpv loadfile 'https://youtu.be/{id}' append"))
-                        .collect::<Vec<_>>()
-                        .join(" ;; "),
-                    Provider::Invidious => env::var("all-ids")
-                        .unwrap_or(String::from("env_not_found"))
-                        .split(' ')
-                        .map(|id| {
-                            format!(
-                                "mpv loadfile '{}/watch?v={id}' append",
-                                mainconfig.invidious_instance,
-                            )
-                        })
-                        .collect::<Vec<_>>()
-                        .join(" ;; "),
-                },
+                String::from("resume-args"),
+                resume_args(mainconfig, positions.start(id).unwrap_or_default()),
             ),
         ],
+        Page::SingleItem(SingleItemPage::Playlist(id)) => [
+            vec![
+                (
+                    String::from("url"),
+                    match status.provider {
+                        Provider::YouTube => {
+                            format!("https://www.youtube.com/playlist?list={id}")
+                        }
+                        Provider::Invidious => {
+                            format!("{}/playlist?list={}", mainconfig.invidious_instance, id)
+                        }
+                    },
+                ),
+                (
+                    String::from("channel-url"),
+                    match status.provider {
+                        Provider::YouTube => format!(
+                            "https://www.youtube.com/channel/{}",
+                            env::var("channel-id").unwrap_or(String::from("env_not_found"))
+                        ),
+                        Provider::Invidious => format!(
+                            "{}/channel/{}",
+                            mainconfig.invidious_instance,
+                            env::var("channel-id").unwrap_or(String::from("env_not_found"))
+                        ),
+                    },
+                ),
+            ],
+            playlist_envs(mainconfig, status),
+        ]
+        .concat(),
+        Page::SingleItem(SingleItemPage::LocalPlaylist(_)) => playlist_envs(mainconfig, status),
+        Page::Comments(id) | Page::Related(id) => vec![(
+            String::from("url"),
+            match status.provider {
+                Provider::Invidious => {
+                    format!("{}/watch?v={}", mainconfig.invidious_instance, id)
+                }
+                Provider::YouTube => format!("https://youtu.be/{id}"),
+            },
+        )],
+        Page::LocalSearch(_) | Page::Instances => Vec::new(),
     };
 
     data.global.get_mut::<Status>().unwrap().provider_updated = true;
@@ -192,3 +204,65 @@ pub fn update_provider(data: &mut FrameworkData) {
         &mut data.state.get_mut::<StateEnvs>().unwrap().0,
     );
 }
+
+/// envs for playing all videos of the playlist being viewed, generated from the `all-ids` env
+fn playlist_envs(mainconfig: &MainConfig, status: &Status) -> Vec<(String, String)> {
+    vec![
+        (
+            String::from("all-videos"),
+            match status.provider {
+                Provider::YouTube => env::var("all-ids")
+                    .unwrap_or(String::from("env_not_found"))
+                    .split(' ')
+                    .map(|id| format!("'https://youtu.be/{id}'"))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                Provider::Invidious => env::var("all-ids")
+                    .unwrap_or(String::from("env_not_found"))
+                    .split(' ')
+                    .map(|id| format!("'{}/watch?v={id}'", mainconfig.invidious_instance,))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            },
+        ),
+        (
+            String::from("mpv-queuelist"),
+            env::var("all-ids")
+                .unwrap_or(String::from("env_not_found"))
+                .split(' ')
+                .map(|id| {
+                    format!(
+                        "mpv loadfile '{}' append",
+                        // saved files are played in offline mode
+                        offline_file(id, mainconfig).unwrap_or_else(|| match status.provider {
+                            Provider::YouTube => format!("https://youtu.be/{id}"),
+                            Provider::Invidious =>
+                                format!("{}/watch?v={id}", mainconfig.invidious_instance),
+                        })
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ;; "),
+        ),
+    ]
+}
+
+/// `resume_args` of `video-player` from `main.yml` with the position to start from and where to
+/// save it filled in, empty if there are none for the player
+fn resume_args(mainconfig: &MainConfig, start: f64) -> String {
+    // the player may be set as a path, such as `/usr/bin/mpv` or `mpv.exe`
+    let Some(args) = mainconfig
+        .env
+        .get("video-player")
+        .and_then(|player| Path::new(player).file_stem())
+        .and_then(|player| mainconfig.resume_args.get(player.to_str()?))
+    else {
+        return String::new();
+    };
+
+    args.replace("${resume-position}", &start.to_string())
+        .replace(
+            "${watch-later-path}",
+            &WatchPositions::watch_later_dir().to_string_lossy(),
+        )
+}
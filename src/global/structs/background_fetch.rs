@@ -0,0 +1,161 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use tui_additions::framework::Framework;
+use typemap::Key;
+
+use super::{Fetch, TaskFunction};
+
+type Response = Result<Box<dyn Any + Send>, String>;
+/// turns the response of a fetch started with `start_task` into the task handling it
+type Handler = Box<dyn FnOnce(Response) -> TaskFunction>;
+
+/// makes provider calls for items that are already loaded (such as loading the next page of a
+/// list) on background threads, the response is sent to the items as a message with `"type"` set
+/// to `"fetched"`
+///
+/// commands have no item to send the response to, they start fetches with `start_task` and the
+/// response is handled by a task instead
+#[derive(Clone)]
+pub struct BackgroundFetch {
+    sender: Sender<(u64, Response)>,
+    responses: Arc<Mutex<Receiver<(u64, Response)>>>,
+    /// number of fetches that have not responded
+    running: usize,
+    next_id: u64,
+    /// handlers of fetches started with `start_task`, by id
+    handlers: Rc<RefCell<HashMap<u64, Handler>>>,
+}
+
+impl Key for BackgroundFetch {
+    type Value = Self;
+}
+
+impl Default for BackgroundFetch {
+    fn default() -> Self {
+        let (sender, responses) = mpsc::channel();
+        Self {
+            sender,
+            responses: Arc::new(Mutex::new(responses)),
+            running: 0,
+            next_id: 0,
+            handlers: Rc::default(),
+        }
+    }
+}
+
+impl BackgroundFetch {
+    /// starts a fetch, the item keeps the returned id to pick up the response with
+    /// `Fetched::take`
+    pub fn start<T: Send + 'static>(&mut self, fetch: Fetch<T>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.running += 1;
+
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let response = fetch()
+                .map(|res| Box::new(res) as Box<dyn Any + Send>)
+                .map_err(|e| e.to_string());
+            let _ = sender.send((id, response));
+        });
+
+        id
+    }
+
+    /// starts a fetch for a command, `then` is ran as a task with the response once it arrives
+    pub fn start_task<T: Send + 'static>(
+        &mut self,
+        fetch: Fetch<T>,
+        then: impl FnOnce(&mut Framework, Result<T, String>) + 'static,
+    ) {
+        let id = self.start(fetch);
+        let handler: Handler = Box::new(move |response| {
+            let response = response.map(|res| *res.downcast::<T>().unwrap());
+            // task functions can be called more than once, but the response can only be taken once
+            let then = RefCell::new(Some((then, response)));
+            TaskFunction::new(Arc::new(move |framework| {
+                if let Some((then, response)) = then.take() {
+                    then(framework, response);
+                }
+            }))
+        });
+        self.handlers.borrow_mut().insert(id, handler);
+    }
+
+    /// if any fetch has not responded
+    pub fn pending(&self) -> bool {
+        self.running != 0
+    }
+
+    /// responses that have arrived since the last call, the ones for items and the tasks
+    /// handling the ones for commands
+    pub fn receive(&mut self) -> (Vec<Fetched>, Vec<TaskFunction>) {
+        let mut handlers = self.handlers.borrow_mut();
+        let mut fetched = Vec::new();
+        let mut tasks = Vec::new();
+        for (id, response) in self.responses.lock().unwrap().try_iter() {
+            self.running -= 1;
+            match handlers.remove(&id) {
+                Some(handler) => tasks.push(handler(response)),
+                None => fetched.push(Fetched {
+                    id,
+                    response: RefCell::new(Some(response)),
+                }),
+            }
+        }
+        (fetched, tasks)
+    }
+}
+
+/// a response from `BackgroundFetch`, taken by the item that started the fetch
+pub struct Fetched {
+    id: u64,
+    response: RefCell<Option<Response>>,
+}
+
+impl Fetched {
+    /// the message sent to items
+    pub fn message(self: &Rc<Self>) -> HashMap<String, Box<dyn Any>> {
+        HashMap::from([
+            (
+                String::from("type"),
+                Box::new(String::from("fetched")) as Box<dyn Any>,
+            ),
+            (String::from("fetched"), Box::new(self.clone())),
+        ])
+    }
+
+    /// if the response has not been taken by an item
+    pub fn is_taken(&self) -> bool {
+        self.response.borrow().is_none()
+    }
+
+    /// takes the response from a message if it is for the fetch with id `loading`
+    pub fn take<T: 'static>(
+        data: &HashMap<String, Box<dyn Any>>,
+        loading: Option<u64>,
+    ) -> Option<Result<T, String>> {
+        let fetched = data.get("fetched")?.downcast_ref::<Rc<Self>>()?;
+        if Some(fetched.id) != loading {
+            return None;
+        }
+
+        Some(
+            fetched
+                .response
+                .borrow_mut()
+                .take()?
+                .map(|res| *res.downcast::<T>().unwrap()),
+        )
+    }
+}
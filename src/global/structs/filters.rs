@@ -0,0 +1,244 @@
+use std::error::Error;
+
+use regex::Regex;
+use typemap::Key;
+
+use crate::{
+    config::{BlockList, FilterAction, FilterRule, FiltersConfig},
+    global::{
+        functions::parse_length,
+        structs::{Errors, Item, MiniVideoItem},
+    },
+};
+
+/// rules from `filters.yml` and `block_list` in main config, with their regexes compiled
+#[derive(Clone, Default)]
+pub struct Filters {
+    rules: Vec<CompiledRule>,
+    /// errors of rules that are skipped for having an invalid regex
+    skipped: Vec<String>,
+}
+
+impl Key for Filters {
+    type Value = Self;
+}
+
+#[derive(Clone)]
+struct CompiledRule {
+    rule: FilterRule,
+    title: Option<Regex>,
+    description: Option<Regex>,
+    keywords: Option<Regex>,
+}
+
+/// the parts of an item that rules look at
+struct Subject<'a> {
+    title: &'a str,
+    description: Option<&'a str>,
+    channel_id: &'a str,
+    /// only videos have length, live and upcoming
+    video: Option<(u32, bool, bool)>,
+}
+
+impl Filters {
+    /// rules with an invalid regex are skipped, so a typo does not stop the other rules from
+    /// loading
+    pub fn new(config: &FiltersConfig, block_list: &BlockList) -> Self {
+        let mut skipped = Vec::new();
+        let rules = block_list
+            .channels
+            .iter()
+            .map(|channel| FilterRule {
+                channel: Some(channel.clone()),
+                ..Default::default()
+            })
+            .chain(config.rules.iter().cloned())
+            .map(CompiledRule::new)
+            .filter_map(|rule| rule.map_err(|e| skipped.push(e.to_string())).ok())
+            .collect();
+        Self { rules, skipped }
+    }
+
+    /// message to show if any rules were skipped, only returned once
+    pub fn take_skipped(&mut self) -> Option<String> {
+        (!self.skipped.is_empty()).then(|| {
+            format!(
+                "Skipped invalid filter rules - {}",
+                self.skipped.drain(..).collect::<Vec<_>>().join(", ")
+            )
+        })
+    }
+
+    /// adds a rule without saving it, fails if it has an invalid regex
+    pub fn push(&mut self, rule: FilterRule) -> Result<(), Box<dyn Error>> {
+        self.rules.push(CompiledRule::new(rule)?);
+        Ok(())
+    }
+
+    /// the highest priority rule matching the item decides, deny wins between rules of the same
+    /// priority, items without any matching rules are shown
+    pub fn allowed(&self, item: &Item) -> bool {
+        match Subject::from_item(item) {
+            Some(subject) => self.allowed_subject(&subject),
+            None => true,
+        }
+    }
+
+    pub fn allowed_video(&self, video: &MiniVideoItem) -> bool {
+        self.allowed_subject(&Subject::from_video(video))
+    }
+
+    fn allowed_subject(&self, subject: &Subject) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(subject))
+            .map(|rule| (rule.rule.priority, rule.rule.action == FilterAction::Deny))
+            .max()
+            .is_none_or(|(_, deny)| !deny)
+    }
+}
+
+impl CompiledRule {
+    fn new(rule: FilterRule) -> Result<Self, Box<dyn Error>> {
+        let regex = |pattern: &str| {
+            Regex::new(pattern).map_err(|e| {
+                // syntax errors point at the pattern over several lines, the last one describes it
+                let e = e.to_string();
+                let reason = e.lines().last().unwrap_or_default();
+                Errors::StringError(format!("filters: invalid regex `{pattern}`: {reason}"))
+            })
+        };
+
+        let keywords = if rule.keywords.is_empty() {
+            None
+        } else {
+            Some(regex(&format!(
+                r"(?i)\b(?:{})\b",
+                rule.keywords
+                    .iter()
+                    .map(|word| regex::escape(word))
+                    .collect::<Vec<_>>()
+                    .join("|")
+            ))?)
+        };
+
+        Ok(Self {
+            title: rule.title.as_deref().map(regex).transpose()?,
+            description: rule.description.as_deref().map(regex).transpose()?,
+            keywords,
+            rule,
+        })
+    }
+
+    fn matches(&self, subject: &Subject) -> bool {
+        let rule = &self.rule;
+
+        if rule
+            .channel
+            .as_ref()
+            .is_some_and(|channel| channel != subject.channel_id)
+        {
+            return false;
+        }
+
+        if self
+            .title
+            .as_ref()
+            .is_some_and(|title| !title.is_match(subject.title))
+        {
+            return false;
+        }
+
+        if self.description.as_ref().is_some_and(|description| {
+            !subject
+                .description
+                .is_some_and(|text| description.is_match(text))
+        }) {
+            return false;
+        }
+
+        if self.keywords.as_ref().is_some_and(|keywords| {
+            !keywords.is_match(subject.title)
+                && !subject
+                    .description
+                    .is_some_and(|text| keywords.is_match(text))
+        }) {
+            return false;
+        }
+
+        let has_video_conditions = rule.min_length.is_some()
+            || rule.max_length.is_some()
+            || rule.live.is_some()
+            || rule.upcoming.is_some()
+            || rule.shorts.is_some();
+        if !has_video_conditions {
+            return true;
+        }
+
+        // conditions on videos never match playlists or channels
+        let Some((length, live, upcoming)) = subject.video else {
+            return false;
+        };
+        // length is 0 when unknown, which matches no length conditions
+        let length_known = length != 0;
+
+        rule.min_length
+            .is_none_or(|min| length_known && length >= min)
+            && rule
+                .max_length
+                .is_none_or(|max| length_known && length <= max)
+            && rule
+                .shorts
+                .is_none_or(|shorts| length_known && (length <= 60) == shorts)
+            && rule.live.is_none_or(|expected| live == expected)
+            && rule.upcoming.is_none_or(|expected| upcoming == expected)
+    }
+}
+
+impl<'a> Subject<'a> {
+    fn from_video(video: &'a MiniVideoItem) -> Self {
+        Self {
+            title: &video.title,
+            description: video.description.as_deref(),
+            channel_id: &video.channel_id,
+            video: Some((video.length_secs, video.live, video.upcoming)),
+        }
+    }
+
+    fn from_item(item: &'a Item) -> Option<Self> {
+        Some(match item {
+            Item::MiniVideo(video) => Self::from_video(video),
+            Item::FullVideo(video) => Self {
+                title: &video.title,
+                description: Some(&video.description),
+                channel_id: &video.channel_id,
+                video: Some((parse_length(&video.length), false, false)),
+            },
+            Item::MiniPlaylist(playlist) => Self {
+                title: &playlist.title,
+                description: None,
+                channel_id: &playlist.channel_id,
+                video: None,
+            },
+            Item::FullPlaylist(playlist) => Self {
+                title: &playlist.title,
+                description: Some(&playlist.description),
+                channel_id: &playlist.channel_id,
+                video: None,
+            },
+            Item::MiniChannel(channel) => Self {
+                title: &channel.name,
+                description: Some(&channel.description),
+                channel_id: &channel.id,
+                video: None,
+            },
+            Item::FullChannel(channel) => Self {
+                title: &channel.name,
+                description: Some(&channel.description),
+                channel_id: &channel.id,
+                video: None,
+            },
+            Item::Page(_) => return None,
+        })
+    }
+}
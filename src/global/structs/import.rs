@@ -0,0 +1,287 @@
+use std::{error::Error, fs, path::Path};
+
+use serde_json::Value;
+
+use crate::global::{
+    functions::{from_channel_url, from_video_url},
+    structs::{Errors, Item, Library, LocalStore, Subscriptions, WatchHistory},
+    traits::Collection,
+};
+
+/// subscriptions, watch history and playlists read from NewPipe or Google Takeout exports
+///
+/// nothing is written until `apply` is called
+#[derive(Default)]
+pub struct Import {
+    /// (id, name) of channels
+    pub subscriptions: Vec<(String, String)>,
+    /// ids of watched videos, most recent first
+    pub history: Vec<String>,
+    /// ids of videos in playlists, which are added to the library
+    pub library: Vec<String>,
+    /// number of files that were recognised
+    pub files: usize,
+}
+
+impl Import {
+    /// reads a single export file, or every recognised file in a directory (such as an extracted
+    /// Takeout archive)
+    pub fn from_path(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut import = Self::default();
+
+        if path.is_dir() {
+            import.read_dir(path)?;
+        } else if !import.read_file(path)? {
+            return Err(Errors::StringError(format!(
+                "`{}` is not a NewPipe or Takeout export",
+                path.display()
+            ))
+            .into());
+        }
+
+        if import.files == 0 {
+            return Err(Errors::StrError("no NewPipe or Takeout exports found").into());
+        }
+
+        Ok(import)
+    }
+
+    fn read_dir(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        for entry in fs::read_dir(path)?.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                self.read_dir(&path)?;
+            } else {
+                // other files in the archive are simply not imported
+                let _ = self.read_file(&path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// returns false if the file is not in any of the supported formats
+    fn read_file(&mut self, path: &Path) -> Result<bool, Box<dyn Error>> {
+        let recognised = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => self.read_json(&serde_json::from_str(&fs::read_to_string(path)?)?),
+            Some("csv") => self.read_csv(&fs::read_to_string(path)?),
+            _ => false,
+        };
+
+        if recognised {
+            self.files += 1;
+        }
+
+        Ok(recognised)
+    }
+
+    fn read_json(&mut self, value: &Value) -> bool {
+        // NewPipe `subscriptions.json`
+        if let Some(subscriptions) = value.get("subscriptions").and_then(Value::as_array) {
+            for subscription in subscriptions {
+                // service id 0 is YouTube, other services such as SoundCloud are skipped
+                if subscription.get("service_id").and_then(Value::as_u64) != Some(0) {
+                    continue;
+                }
+
+                let (Some(url), Some(name)) = (
+                    subscription.get("url").and_then(Value::as_str),
+                    subscription.get("name").and_then(Value::as_str),
+                ) else {
+                    continue;
+                };
+
+                if let Ok(id) = from_channel_url(url) {
+                    self.add_channel(id, name.to_string());
+                }
+            }
+
+            return true;
+        }
+
+        // Takeout `watch-history.json`, a list of activities with the most recent on top
+        if let Some(activities) = value.as_array() {
+            if !activities
+                .iter()
+                .any(|activity| activity.get("titleUrl").is_some())
+            {
+                return false;
+            }
+
+            for url in activities
+                .iter()
+                .filter_map(|activity| activity.get("titleUrl").and_then(Value::as_str))
+            {
+                // removed videos and ads have no `watch?v=` url
+                if !url.contains("watch?v=") {
+                    continue;
+                }
+
+                if let Ok(id) = from_video_url(url) {
+                    if !self.history.contains(&id) {
+                        self.history.push(id);
+                    }
+                }
+            }
+
+            return true;
+        }
+
+        false
+    }
+
+    fn read_csv(&mut self, content: &str) -> bool {
+        let mut lines = content.lines().map(csv_fields);
+
+        // old Takeout playlists have a few lines of playlist info before the header
+        let Some(header) = lines.by_ref().find(|fields| {
+            matches!(
+                fields.first().map(|field| field.to_lowercase()).as_deref(),
+                Some("channel id" | "video id")
+            )
+        }) else {
+            return false;
+        };
+
+        let is_subscriptions = header[0].eq_ignore_ascii_case("channel id");
+
+        for fields in lines {
+            let Some(id) = fields.first().filter(|id| !id.is_empty()) else {
+                continue;
+            };
+
+            if is_subscriptions {
+                // `Channel Id,Channel Url,Channel Title`
+                self.add_channel(id.clone(), fields.get(2).cloned().unwrap_or_default());
+            } else if !self.library.contains(id) {
+                // `Video Id,Time Added` or `Video ID,Playlist Video Creation Timestamp`
+                self.library.push(id.clone());
+            }
+        }
+
+        true
+    }
+
+    fn add_channel(&mut self, id: String, name: String) {
+        if !self
+            .subscriptions
+            .iter()
+            .any(|(existing, _)| existing == &id)
+        {
+            self.subscriptions.push((id, name));
+        }
+    }
+
+    fn new_history<'a>(&'a self, history: &WatchHistory, limit: usize) -> Vec<&'a str> {
+        self.history
+            .iter()
+            .take(limit)
+            .map(String::as_str)
+            .filter(|id| !history.0.iter().any(|item| item.id() == Some(id)))
+            .collect()
+    }
+
+    fn new_library<'a>(&'a self, library: &Library) -> Vec<&'a str> {
+        self.library
+            .iter()
+            .map(String::as_str)
+            .filter(|id| !library.0.iter().any(|item| item.id() == Some(id)))
+            .collect()
+    }
+
+    /// describes what `apply` would do, without writing anything
+    pub fn report(
+        &self,
+        subscriptions: &Subscriptions,
+        history: &WatchHistory,
+        library: &Library,
+        history_limit: usize,
+    ) -> String {
+        let new_channels = self
+            .subscriptions
+            .iter()
+            .filter(|(id, _)| !subscriptions.contains(id))
+            .count();
+
+        format!(
+            "Dry run ({} files): {} channels ({new_channels} new) | {} watched videos ({} new, up to {history_limit} kept) | {} playlist videos ({} new to library)",
+            self.files,
+            self.subscriptions.len(),
+            self.history.len(),
+            self.new_history(history, history_limit).len(),
+            self.library.len(),
+            self.new_library(library).len(),
+        )
+    }
+
+    /// adds everything to subscriptions, watch history and library
+    ///
+    /// nothing is fetched, videos that are not saved are added by their id (including videos that
+    /// are no longer avaliable), and their info is fetched when they are hovered
+    ///
+    /// Res<(channels, history, library)>
+    pub fn apply(
+        &self,
+        subscriptions: &mut Subscriptions,
+        history: &mut WatchHistory,
+        library: &mut Library,
+        history_limit: usize,
+    ) -> (u32, u32, u32) {
+        let (channels, _) = subscriptions.add_unsynced(self.subscriptions.clone());
+        let _ = subscriptions.save();
+
+        let new_history = self.new_history(history, history_limit);
+        let new_library = self.new_library(library);
+
+        // imported history is older than anything already in the history, and the latest watch
+        // history is at the back
+        let history_items = new_history
+            .iter()
+            .rev()
+            .map(|id| saved_or_unresolved(id))
+            .collect::<Vec<_>>();
+        let history_added = history_items.len() as u32;
+        history.0.splice(0..0, history_items);
+
+        let mut library_added = 0;
+        for id in new_library {
+            if library.push(saved_or_unresolved(id)).is_ok() {
+                library_added += 1;
+            }
+        }
+
+        (channels, history_added, library_added)
+    }
+}
+
+/// gets info of a video from the local store, or stores it by its id to be fetched later
+fn saved_or_unresolved(id: &str) -> Item {
+    LocalStore::get_info(id).unwrap_or_else(|| {
+        let item = Item::unresolved(id.to_string());
+        LocalStore::set_info(id.to_string(), item.clone(), true);
+        item
+    })
+}
+
+/// splits a line of csv, with support for quoted fields
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+
+    fields
+}
@@ -0,0 +1,301 @@
+use std::{
+    fmt::Display,
+    sync::{Mutex, MutexGuard},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+
+use crate::global::functions::httpreq_get_timeout;
+
+static INSTANCE_POOL: Mutex<InstancePool> = Mutex::new(InstancePool::new());
+
+/// an Invidious instance, and how it responded to probes and requests
+#[derive(Clone)]
+pub struct Instance {
+    pub url: String,
+    /// response time of the last successful probe
+    pub latency: Option<Duration>,
+    /// `None` if the instance has not been probed or used yet
+    pub healthy: Option<bool>,
+    /// failed probes and requests in a row
+    pub failures: u32,
+    pub last_error: Option<String>,
+}
+
+impl Instance {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            latency: None,
+            healthy: None,
+            failures: 0,
+            last_error: None,
+        }
+    }
+
+    /// healthy instances first, then the ones that are not probed yet
+    fn rank(&self) -> (u8, Duration) {
+        (
+            match self.healthy {
+                Some(true) => 0,
+                None => 1,
+                Some(false) => 2,
+            },
+            self.latency.unwrap_or(Duration::MAX),
+        )
+    }
+}
+
+impl Display for Instance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            match self.healthy {
+                Some(true) => "up",
+                Some(false) => "down",
+                None => "unknown",
+            },
+            self.url
+        )?;
+
+        if let Some(latency) = self.latency {
+            write!(f, " ({}ms)", latency.as_millis())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Invidious instances from `invidious_instance` and `invidious_instances`
+///
+/// requests go to the fastest healthy instance, and move on to the next one if an instance
+/// cannot be reached
+pub struct InstancePool {
+    pub instances: Vec<Instance>,
+    /// url of the only instance to use, set with `instances pin`
+    pub pinned: Option<String>,
+    /// url of the instance which answered the last request
+    pub last_used: Option<String>,
+    pub timeout: Duration,
+}
+
+impl Default for InstancePool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InstancePool {
+    pub const fn new() -> Self {
+        Self {
+            instances: Vec::new(),
+            pinned: None,
+            last_used: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// the pool shared by all Invidious clients
+    pub fn global() -> MutexGuard<'static, Self> {
+        INSTANCE_POOL.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// replaces the list of instances, keeping the health of instances that are still in it
+    pub fn set_instances(&mut self, urls: Vec<String>, timeout: Duration) {
+        let mut instances = Vec::new();
+        for url in urls {
+            let url = url.trim_end_matches('/').to_string();
+            if instances
+                .iter()
+                .any(|instance: &Instance| instance.url == url)
+            {
+                continue;
+            }
+
+            instances.push(
+                self.instances
+                    .iter()
+                    .find(|instance| instance.url == url)
+                    .cloned()
+                    .unwrap_or_else(|| Instance::new(url)),
+            );
+        }
+
+        self.instances = instances;
+        self.timeout = timeout;
+        if self.pinned.as_ref().is_some_and(|pinned| {
+            !self
+                .instances
+                .iter()
+                .any(|instance| &instance.url == pinned)
+        }) {
+            self.pinned = None;
+        }
+    }
+
+    /// urls in the order they should be tried
+    pub fn order(&self) -> Vec<String> {
+        if let Some(pinned) = &self.pinned {
+            return vec![pinned.clone()];
+        }
+
+        let mut instances = self.instances.iter().collect::<Vec<_>>();
+        instances.sort_by_key(|instance| instance.rank());
+        instances
+            .into_iter()
+            .map(|instance| instance.url.clone())
+            .collect()
+    }
+
+    /// pins an instance by its url or its position in the list (starting from 1)
+    pub fn pin(&mut self, instance: &str) -> Result<String, String> {
+        let url = match instance.parse::<usize>() {
+            Ok(index) => self
+                .instances
+                .get(index.wrapping_sub(1))
+                .map(|instance| instance.url.clone()),
+            Err(_) => {
+                let instance = instance.trim_end_matches('/');
+                self.instances
+                    .iter()
+                    .find(|item| item.url == instance)
+                    .map(|instance| instance.url.clone())
+            }
+        };
+
+        match url {
+            Some(url) => {
+                self.pinned = Some(url.clone());
+                Ok(url)
+            }
+            None => Err(format!("Unknown instance: `{instance}`")),
+        }
+    }
+
+    pub fn report_success(&mut self, url: &str) {
+        self.last_used = Some(url.to_string());
+        if let Some(instance) = self.instances.iter_mut().find(|item| item.url == url) {
+            instance.healthy = Some(true);
+            instance.failures = 0;
+        }
+    }
+
+    pub fn report_failure(&mut self, url: &str, error: String) {
+        if let Some(instance) = self.instances.iter_mut().find(|item| item.url == url) {
+            instance.healthy = Some(false);
+            instance.failures += 1;
+            instance.last_error = Some(error);
+        }
+    }
+
+    /// true if no instance has been probed or used yet
+    pub fn unprobed(&self) -> bool {
+        self.instances
+            .iter()
+            .all(|instance| instance.healthy.is_none())
+    }
+
+    /// checks if an instance is up by requesting `/api/v1/stats`, returns the response time
+    pub fn probe(url: &str, timeout: Duration) -> Result<Duration, String> {
+        let start = Instant::now();
+        let (status, body) = httpreq_get_timeout(&format!("{url}/api/v1/stats"), timeout)
+            .map_err(|e| e.to_string())?;
+        let latency = start.elapsed();
+
+        if !(200..300).contains(&status) {
+            return Err(format!("HTTP {status}"));
+        }
+
+        // blocked instances may respond with a html page instead
+        if serde_json::from_slice::<Value>(&body)
+            .ok()
+            .and_then(|value| value.get("software").cloned())
+            .is_none()
+        {
+            return Err(String::from("Not an Invidious API response"));
+        }
+
+        Ok(latency)
+    }
+
+    /// probes every instance at the same time, the pool is not locked while waiting for responses
+    ///
+    /// Res<(up, total)>
+    pub fn probe_all() -> (usize, usize) {
+        let (urls, timeout) = {
+            let pool = Self::global();
+            (
+                pool.instances
+                    .iter()
+                    .map(|instance| instance.url.clone())
+                    .collect::<Vec<_>>(),
+                pool.timeout,
+            )
+        };
+
+        let results = thread::scope(|scope| {
+            urls.iter()
+                .map(|url| scope.spawn(move || Self::probe(url, timeout)))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err(String::from("Probe panicked")))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut pool = Self::global();
+        let mut up = 0;
+        for (url, result) in urls.iter().zip(results) {
+            let Some(instance) = pool.instances.iter_mut().find(|item| &item.url == url) else {
+                continue;
+            };
+
+            match result {
+                Ok(latency) => {
+                    up += 1;
+                    instance.latency = Some(latency);
+                    instance.healthy = Some(true);
+                    instance.failures = 0;
+                }
+                Err(e) => {
+                    instance.latency = None;
+                    instance.healthy = Some(false);
+                    instance.failures += 1;
+                    instance.last_error = Some(e);
+                }
+            }
+        }
+
+        (up, urls.len())
+    }
+
+    /// one line per instance, used by the `instances probe` text command
+    pub fn report(&self) -> String {
+        self.instances
+            .iter()
+            .enumerate()
+            .map(|(index, instance)| {
+                format!(
+                    "{}. {instance}{}{}",
+                    index + 1,
+                    if self.pinned.as_ref() == Some(&instance.url) {
+                        " [pinned]"
+                    } else {
+                        ""
+                    },
+                    match (&instance.last_error, instance.healthy) {
+                        (Some(e), Some(false)) => format!(" - {e}"),
+                        _ => String::new(),
+                    }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
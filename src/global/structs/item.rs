@@ -55,21 +55,31 @@ pub struct MiniVideoItem {
     pub published: Option<String>,
     pub timestamp: Option<u64>,
     pub description: Option<String>,
+    /// length in seconds, 0 if unknown
+    #[serde(default)]
+    pub length_secs: u32,
+    #[serde(default)]
+    pub live: bool,
+    #[serde(default)]
+    pub upcoming: bool,
+    /// an imported video with only its id known, its info is fetched when it is hovered
+    #[serde(default)]
+    pub unresolved: bool,
 }
 
 impl PartialOrd for MiniVideoItem {
-    #[allow(clippy::non_canonical_partial_ord_impl)]
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        // order with recent on top
-        Some(other.cmp(self))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for MiniVideoItem {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.timestamp
+        // order with recent on top
+        other
+            .timestamp
             .unwrap_or_default()
-            .cmp(&other.timestamp.unwrap_or_default())
+            .cmp(&self.timestamp.unwrap_or_default())
     }
 }
 
@@ -79,6 +89,27 @@ impl PartialEq for MiniVideoItem {
     }
 }
 
+impl From<FullVideoItem> for MiniVideoItem {
+    fn from(original: FullVideoItem) -> Self {
+        Self {
+            title: original.title,
+            id: original.id,
+            thumbnail_url: original.thumbnail_url,
+            length: original.length,
+            views: Some(original.views),
+            channel: original.channel,
+            channel_id: original.channel_id,
+            published: Some(original.published),
+            timestamp: None,
+            description: Some(original.description),
+            length_secs: 0,
+            live: false,
+            upcoming: false,
+            unresolved: false,
+        }
+    }
+}
+
 /// stores information of a previewed playlist
 #[derive(Clone, Serialize, Deserialize)]
 pub struct MiniPlaylistItem {
@@ -132,6 +163,9 @@ pub struct FullPlaylistItem {
     pub views: String,
     pub thumbnail_url: String,
     pub videos: Vec<Item>,
+    /// token for the next page of videos, loaded when the cursor reaches the last video
+    #[serde(default)]
+    pub continuation: Option<String>,
 }
 
 /// stores information of a viewed channel
@@ -296,6 +330,46 @@ impl Item {
         }
     }
 
+    /// length of a video in seconds, 0 if unknown or not a video
+    pub fn length_secs(&self) -> u32 {
+        match self {
+            Self::MiniVideo(video) => video.length_secs,
+            Self::FullVideo(video) => parse_length(&video.length),
+            _ => 0,
+        }
+    }
+
+    /// an imported video, only its id is known until its info is fetched
+    pub fn unresolved(id: String) -> Self {
+        Self::MiniVideo(MiniVideoItem {
+            title: id.clone(),
+            id,
+            thumbnail_url: String::new(),
+            length: String::new(),
+            views: None,
+            channel: String::new(),
+            channel_id: String::new(),
+            published: None,
+            timestamp: None,
+            description: None,
+            length_secs: 0,
+            live: false,
+            upcoming: false,
+            unresolved: true,
+        })
+    }
+
+    /// if self is an imported video whose info has not been fetched
+    pub fn is_unresolved(&self) -> bool {
+        matches!(
+            self,
+            Self::MiniVideo(MiniVideoItem {
+                unresolved: true,
+                ..
+            })
+        )
+    }
+
     /// parse `CommonVideo` into `Self`
     pub fn from_common_video(original: CommonVideo, image_index: usize) -> Self {
         Self::MiniVideo(MiniVideoItem {
@@ -319,6 +393,37 @@ impl Item {
                 date_text(original.published)
             )),
             description: Some(original.description),
+            length_secs: original.length,
+            live: original.live,
+            upcoming: original.upcoming,
+            unresolved: false,
+        })
+    }
+
+    /// parse `VideoShort` (related videos) into `Self`
+    pub fn from_video_short(original: VideoShort, image_index: usize) -> Self {
+        Self::MiniVideo(MiniVideoItem {
+            title: original.title,
+            id: original.id,
+            thumbnail_url: if original.thumbnails.is_empty() {
+                String::new()
+            } else {
+                original.thumbnails[image_index.min(original.thumbnails.len() - 1)]
+                    .url
+                    .clone()
+            },
+            length: secs_display_string(original.length),
+            // Invidious includes the unit, such as "1.2M views"
+            views: Some(original.views_text.trim_end_matches(" views").to_string()),
+            channel: original.author,
+            channel_id: original.author_id,
+            timestamp: None,
+            published: None,
+            description: None,
+            length_secs: original.length,
+            live: false,
+            upcoming: false,
+            unresolved: false,
         })
     }
 
@@ -381,6 +486,10 @@ impl Item {
                 date_text(original.published)
             )),
             description: None,
+            length_secs: original.length,
+            live: false,
+            upcoming: false,
+            unresolved: false,
         })
     }
 
@@ -437,6 +546,7 @@ impl Item {
                 .into_iter()
                 .map(|video| Self::from_playlist_item(video, image_index))
                 .collect(),
+            continuation: original.continuation,
         })
     }
 
@@ -459,6 +569,10 @@ impl Item {
             timestamp: None,
             published: None,
             description: None,
+            length_secs: original.length,
+            live: false,
+            upcoming: false,
+            unresolved: false,
         })
     }
 
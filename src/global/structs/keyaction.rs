@@ -46,4 +46,14 @@ pub enum KeyAction {
     PreviousEntry,
     /// Next entry
     NextEntry,
+    /// seek the embedded player forward by `seek_seconds` in main config
+    SeekForward,
+    /// seek the embedded player backward by `seek_seconds` in main config
+    SeekBackward,
+    /// increase volume of the embedded player by `volume_step` in main config
+    VolumeUp,
+    /// decrease volume of the embedded player by `volume_step` in main config
+    VolumeDown,
+    /// pause or resume the embedded player
+    TogglePause,
 }
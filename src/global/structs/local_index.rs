@@ -0,0 +1,248 @@
+use std::collections::{HashMap, HashSet};
+
+use typemap::Key;
+
+use super::{Item, Library, Subscriptions, Transcript, WatchHistory};
+
+// weight of a word by where it is found, titles count the most
+const TITLE: u32 = 8;
+const CHANNEL: u32 = 4;
+const DESCRIPTION: u32 = 2;
+const TRANSCRIPT: u32 = 1;
+
+/// Full text index over items kept on disk, used by the `localsearch` page
+///
+/// titles, channel names, descriptions and cached transcripts are split into lowercase words,
+/// each word points to the items it appears in
+///
+/// the index in the global data is built by the first search, items added afterwards are indexed
+/// with `push()`, and it is built again after items are removed or changed
+#[derive(Clone, Default)]
+pub struct LocalIndex {
+    items: Vec<Item>,
+    /// id to index of item
+    ids: HashMap<String, usize>,
+    /// indices of items with their transcript indexed
+    transcripts: HashSet<usize>,
+    /// word to (index of item, weight)
+    words: HashMap<String, Vec<(usize, u32)>>,
+    /// language of the indexed transcripts
+    language: String,
+    /// false until it is built, or after `invalidate()`
+    built: bool,
+}
+
+impl Key for LocalIndex {
+    type Value = Self;
+}
+
+impl LocalIndex {
+    /// indexes the library, watch history from the latest, then subscribed channels and their
+    /// videos, with transcripts in `language` that have been downloaded
+    pub fn build(
+        library: &Library,
+        history: &WatchHistory,
+        subscriptions: &Subscriptions,
+        language: &str,
+    ) -> Self {
+        let mut index = Self {
+            language: language.to_string(),
+            built: true,
+            ..Default::default()
+        };
+
+        let subscribed = subscriptions.0.iter().flat_map(|item| {
+            std::iter::once(Item::FullChannel(item.channel.clone()))
+                .chain(item.videos.iter().cloned().map(Item::MiniVideo))
+        });
+        for item in library
+            .0
+            .iter()
+            .rev()
+            .chain(history.0.iter().rev())
+            .cloned()
+            .chain(subscribed)
+        {
+            let transcript = match &item {
+                Item::MiniVideo(_) | Item::FullVideo(_) => {
+                    Transcript::load(item.id().unwrap(), language)
+                }
+                _ => None,
+            };
+            index.add(item, transcript.as_ref());
+        }
+
+        index
+    }
+
+    /// if the index has to be built before searching
+    pub fn is_built(&self) -> bool {
+        self.built
+    }
+
+    /// builds the index again on the next search, for when items are removed or changed
+    pub fn invalidate(&mut self) {
+        self.built = false;
+    }
+
+    /// indexes an item that was added to the library, watch history or subscriptions, with its
+    /// transcript if it has been downloaded
+    pub fn push(&mut self, item: &Item) {
+        if !self.built || item.id().is_some_and(|id| self.ids.contains_key(id)) {
+            return;
+        }
+
+        let transcript = match item {
+            Item::MiniVideo(_) | Item::FullVideo(_) => {
+                Transcript::load(item.id().unwrap(), &self.language)
+            }
+            _ => None,
+        };
+        self.add(item.clone(), transcript.as_ref());
+    }
+
+    /// indexes a transcript downloaded after its video was indexed
+    pub fn add_transcript(&mut self, id: &str, transcript: &Transcript) {
+        let Some(&index) = self.ids.get(id) else {
+            return;
+        };
+        if !self.transcripts.insert(index) {
+            return;
+        }
+
+        // words already in the item keep their higher weight, as searches use the best one
+        let words = transcript
+            .cues
+            .iter()
+            .flat_map(|cue| words(&cue.text))
+            .collect::<HashSet<_>>();
+        for word in words {
+            self.words
+                .entry(word)
+                .or_default()
+                .push((index, TRANSCRIPT));
+        }
+    }
+
+    /// adds an item if it is not indexed already
+    pub fn add(&mut self, item: Item, transcript: Option<&Transcript>) {
+        let Some(id) = item.id() else {
+            return;
+        };
+        if self.ids.contains_key(id) {
+            return;
+        }
+        let index = self.items.len();
+        self.ids.insert(id.to_string(), index);
+
+        let (title, channel, description) = match &item {
+            Item::MiniVideo(video) => (
+                &video.title,
+                video.channel.as_str(),
+                video.description.as_deref().unwrap_or_default(),
+            ),
+            Item::FullVideo(video) => (
+                &video.title,
+                video.channel.as_str(),
+                video.description.as_str(),
+            ),
+            Item::MiniPlaylist(playlist) => (&playlist.title, playlist.channel.as_str(), ""),
+            Item::FullPlaylist(playlist) => (
+                &playlist.title,
+                playlist.channel.as_str(),
+                playlist.description.as_str(),
+            ),
+            Item::MiniChannel(channel) => (&channel.name, "", channel.description.as_str()),
+            Item::FullChannel(channel) => (&channel.name, "", channel.description.as_str()),
+            Item::Page(_) => return,
+        };
+
+        // each word is counted once per item, with the weight of the best field it is in
+        let mut weights = HashMap::new();
+        let mut add_words = |text: &str, weight: u32| {
+            for word in words(text) {
+                let entry = weights.entry(word).or_insert(weight);
+                *entry = (*entry).max(weight);
+            }
+        };
+        add_words(title, TITLE);
+        add_words(channel, CHANNEL);
+        add_words(description, DESCRIPTION);
+        for cue in transcript
+            .iter()
+            .flat_map(|transcript| transcript.cues.iter())
+        {
+            add_words(&cue.text, TRANSCRIPT);
+        }
+        if transcript.is_some() {
+            self.transcripts.insert(index);
+        }
+
+        for (word, weight) in weights {
+            self.words.entry(word).or_default().push((index, weight));
+        }
+        self.items.push(item);
+    }
+
+    /// items containing every word of `query`, words match the start of indexed words, so
+    /// `astl` finds `astley`
+    ///
+    /// the best matches are on top, items that score the same keep the order they were indexed in
+    pub fn search(&self, query: &str) -> Vec<Item> {
+        let terms = words(query).collect::<Vec<_>>();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        // index of item to (number of terms matched, score)
+        let mut matches: HashMap<usize, (usize, u32)> = HashMap::new();
+        for term in terms.iter() {
+            let mut best: HashMap<usize, u32> = HashMap::new();
+            for (word, items) in self.words.iter() {
+                if !word.starts_with(term.as_str()) {
+                    continue;
+                }
+                // partial words score half
+                let full = word == term;
+                for (index, weight) in items {
+                    let weight = if full { *weight * 2 } else { *weight };
+                    let entry = best.entry(*index).or_default();
+                    *entry = (*entry).max(weight);
+                }
+            }
+
+            for (index, weight) in best {
+                let entry = matches.entry(index).or_default();
+                entry.0 += 1;
+                entry.1 += weight;
+            }
+        }
+
+        let mut results = matches
+            .into_iter()
+            .filter(|(_, (matched, _))| *matched == terms.len())
+            .map(|(index, (_, score))| (index, score))
+            .collect::<Vec<_>>();
+        results.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        results
+            .into_iter()
+            .map(|(index, _)| self.items[index].clone())
+            .collect()
+    }
+
+    /// number of items indexed
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// lowercase words of `text`, split on anything that is not a letter or number
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
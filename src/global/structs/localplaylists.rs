@@ -0,0 +1,199 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+use typemap::Key;
+
+use crate::global::{
+    functions::paths,
+    structs::{FullPlaylistItem, Item, MiniVideoItem},
+    traits::Collection,
+};
+
+/// a named list of videos created by the user
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct LocalPlaylist {
+    pub name: String,
+    pub items: Vec<Item>,
+}
+
+impl Collection<Item> for LocalPlaylist {
+    /// each playlist is saved to `playlists/{name}.json`
+    const INDEX_PATH: &'static str = "playlists";
+
+    fn index_path(&self) -> PathBuf {
+        paths::data_dir()
+            .join(Self::INDEX_PATH)
+            .join(format!("{}.json", self.name))
+    }
+
+    fn items(&self) -> &Vec<Item> {
+        &self.items
+    }
+
+    fn items_mut(&mut self) -> &mut Vec<Item> {
+        &mut self.items
+    }
+
+    fn from_items(items: Vec<Item>) -> Self {
+        Self {
+            // the cached info of a video may be a full video, but playlists only display mini videos
+            items: items
+                .into_iter()
+                .filter_map(|item| match item {
+                    Item::MiniVideo(_) => Some(item),
+                    Item::FullVideo(video) => Some(Item::MiniVideo(MiniVideoItem::from(video))),
+                    _ => None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl LocalPlaylist {
+    /// an empty playlist called `name`
+    fn named(name: String) -> Self {
+        Self {
+            name,
+            items: Vec::new(),
+        }
+    }
+
+    /// loads the playlist saved as `name`
+    pub fn load_named(name: String) -> Self {
+        let loaded = Self::load_into(Self::named(name));
+        Self {
+            name: loaded.name,
+            ..Self::from_items(loaded.items)
+        }
+    }
+
+    /// the playlist as an item, so it can be displayed like a playlist from YouTube
+    pub fn to_item(&self) -> Item {
+        Item::FullPlaylist(FullPlaylistItem {
+            title: self.name.clone(),
+            id: format!("local-{}", self.name),
+            channel: String::from("you"),
+            channel_id: String::new(),
+            video_count: self.items.len() as u32,
+            description: String::from("Local playlist"),
+            views: String::new(),
+            thumbnail_url: String::new(),
+            videos: self.items.clone(),
+            continuation: None,
+        })
+    }
+}
+
+/// all local playlists, names are saved in order to `playlists.json`
+#[derive(Clone, Default)]
+pub struct LocalPlaylists {
+    pub playlists: Vec<LocalPlaylist>,
+    /// names of playlists when they were loaded, their files are removed if they are deleted or
+    /// renamed
+    loaded: Vec<String>,
+}
+
+impl Key for LocalPlaylists {
+    type Value = Self;
+}
+
+impl LocalPlaylists {
+    const PATH: &'static str = "playlists.json";
+
+    pub fn load() -> Self {
+        let names: Vec<String> = fs::read_to_string(paths::data_dir().join(Self::PATH))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+
+        Self {
+            playlists: names
+                .iter()
+                .cloned()
+                .map(LocalPlaylist::load_named)
+                .collect(),
+            loaded: names,
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(paths::data_dir().join(LocalPlaylist::INDEX_PATH))?;
+
+        let names = self
+            .playlists
+            .iter()
+            .map(|playlist| playlist.name.as_str())
+            .collect::<Vec<_>>();
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(paths::data_dir().join(Self::PATH))?;
+        file.write_all(serde_json::to_string_pretty(&names)?.as_bytes())?;
+
+        for playlist in self.playlists.iter() {
+            playlist.save()?;
+        }
+
+        // remove files of deleted or renamed playlists
+        for name in self.loaded.iter() {
+            if !names.contains(&name.as_str()) {
+                let _ = fs::remove_file(LocalPlaylist::named(name.clone()).index_path());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LocalPlaylist> {
+        self.playlists.iter().find(|playlist| playlist.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut LocalPlaylist> {
+        self.playlists
+            .iter_mut()
+            .find(|playlist| playlist.name == name)
+    }
+
+    /// returns false if a playlist with the same name already exists
+    pub fn create(&mut self, name: &str) -> bool {
+        if self.get(name).is_some() {
+            return false;
+        }
+
+        self.playlists.push(LocalPlaylist::named(name.to_string()));
+        true
+    }
+
+    /// returns false if the playlist does not exist
+    pub fn delete(&mut self, name: &str) -> bool {
+        let len = self.playlists.len();
+        self.playlists.retain(|playlist| playlist.name != name);
+        self.playlists.len() != len
+    }
+
+    /// ids of all videos in any of the playlists
+    pub fn ids(&self) -> HashSet<String> {
+        self.playlists
+            .iter()
+            .flat_map(|playlist| playlist.items.iter())
+            .filter_map(|item| item.id())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// playlist names are used as file names and cannot contain spaces
+    pub fn valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    }
+}
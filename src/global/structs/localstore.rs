@@ -56,11 +56,12 @@ impl LocalStore {
         res.is_some()
     }
 
+    /// imported videos whose info has not been fetched are treated as not stored
     pub fn get_info(id: &str) -> Option<Item> {
         let localstore = unsafe { LOCALSTORE.get_mut() }.unwrap();
 
-        match localstore.info.get(id) {
-            Some(LocalRecord { item, .. }) => Some(item.clone()),
+        let item: Item = match localstore.info.get(id) {
+            Some(LocalRecord { item, .. }) => item.clone(),
             None => {
                 let path = paths::data_dir()
                     .join(format!("info/{id}.json"));
@@ -68,10 +69,20 @@ impl LocalStore {
                 if path.exists() {
                     serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?
                 } else {
-                    None
+                    return None;
                 }
             }
-        }
+        };
+
+        (!item.is_unresolved()).then_some(item)
+    }
+
+    /// info of an item held in memory, `None` if the store is not initialised
+    pub fn get_loaded(id: &str) -> Option<Item> {
+        unsafe { LOCALSTORE.get() }?
+            .info
+            .get(id)
+            .map(|LocalRecord { item, .. }| item.clone())
     }
 
     pub fn set_info(id: String, item: Item, is_new: bool) {
@@ -1,36 +1,66 @@
 //! enums and structs
+mod background_fetch;
 mod errors;
+mod filters;
 mod history;
+mod import;
+mod instances;
 mod item;
 mod keyaction;
 mod library;
+mod local_index;
+mod localplaylists;
 mod localstore;
 mod message;
 #[cfg(feature = "mpv")]
 mod mpv;
 mod page;
+mod page_loader;
+mod playqueue;
+mod response_cache;
+mod search_suggestions;
 mod state_env;
 mod status;
 mod subscriptions;
 mod tasks;
+mod transcript;
+mod watched;
+mod watchpositions;
 
 mod providers;
 
+pub use background_fetch::*;
 pub use errors::*;
+pub use filters::*;
 pub use history::*;
+pub use import::*;
+pub use instances::*;
 pub use item::*;
 pub use keyaction::*;
 pub use library::*;
+pub use local_index::*;
+pub use localplaylists::*;
 pub use localstore::LocalStore;
 pub use message::*;
 #[cfg(feature = "mpv")]
 pub use mpv::*;
 pub use page::*;
+pub use page_loader::*;
+pub use playqueue::*;
+pub use providers::fixture::*;
 #[cfg(feature = "invidious")]
 pub use providers::invidiousclient::*;
+pub use providers::local::*;
+#[cfg(feature = "piped")]
+pub use providers::piped::*;
 #[cfg(feature = "rustypipe")]
 pub use providers::rustypipe::*;
+pub use response_cache::*;
+pub use search_suggestions::*;
 pub use state_env::*;
 pub use status::*;
 pub use subscriptions::*;
 pub use tasks::*;
+pub use transcript::*;
+pub use watched::*;
+pub use watchpositions::*;
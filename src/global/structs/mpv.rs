@@ -1,10 +1,29 @@
-use std::{sync::mpsc, thread};
+use std::{
+    sync::{mpsc, Arc},
+    thread,
+};
 
-use libmpv::Mpv;
+use libmpv::{
+    events::{Event, PropertyData},
+    mpv_end_file_reason, Format, Mpv,
+};
 use typemap::Key;
 
+use crate::global::functions::path_video_id;
+
+/// properties observed by the event thread, the index is used as the reply id
+const OBSERVED_PROPERTIES: [(&str, Format); 5] = [
+    ("time-pos", Format::Double),
+    ("duration", Format::Double),
+    ("pause", Format::Flag),
+    ("media-title", Format::String),
+    ("volume", Format::Double),
+];
+
 pub struct MpvWrapper {
     pub sender: mpsc::Sender<MpvAction>,
+    /// events from the event thread, drained into the task queue by the main loop
+    pub events: mpsc::Receiver<MpvEvent>,
     // pub copier: mpsc::Receiver<MpvResponse>,
 }
 
@@ -23,10 +42,13 @@ impl Clone for MpvWrapper {
 impl MpvWrapper {
     pub fn spawn() -> Self {
         let (sender, receiver) = mpsc::channel();
+        let (event_sender, events) = mpsc::channel();
         // let (responder, copier) = mpsc::channel();
         thread::spawn(move || {
-            let mpv = Mpv::new().unwrap();
+            let mpv = Arc::new(Mpv::new().unwrap());
             mpv.set_property("video", "no").unwrap();
+            let event_mpv = mpv.clone();
+            thread::spawn(move || Self::event_loop(&event_mpv, event_sender));
             loop {
                 let action = match receiver.recv() {
                     Ok(res) => res,
@@ -61,7 +83,50 @@ impl MpvWrapper {
                 }
             }
         });
-        Self { sender }
+        Self { sender, events }
+    }
+
+    /// forwards mpv events to the main thread until mpv shuts down or the receiver is dropped
+    fn event_loop(mpv: &Mpv, sender: mpsc::Sender<MpvEvent>) {
+        let mut ctx = mpv.create_event_context();
+        let _ = ctx.disable_deprecated_events();
+        for (id, (name, format)) in OBSERVED_PROPERTIES.into_iter().enumerate() {
+            let _ = ctx.observe_property(name, format, id as u64);
+        }
+
+        loop {
+            let event = match ctx.wait_event(-1.) {
+                Some(Ok(event)) => event,
+                _ => continue,
+            };
+
+            let event = match event {
+                Event::PropertyChange { name, change, .. } => match (name, change) {
+                    ("time-pos", PropertyData::Double(secs)) => MpvEvent::TimePos(secs),
+                    ("duration", PropertyData::Double(secs)) => MpvEvent::Duration(secs),
+                    ("pause", PropertyData::Flag(paused)) => MpvEvent::Pause(paused),
+                    ("media-title", PropertyData::Str(title)) => {
+                        MpvEvent::MediaTitle(title.to_string())
+                    }
+                    ("volume", PropertyData::Double(volume)) => MpvEvent::Volume(volume),
+                    _ => continue,
+                },
+                Event::StartFile => MpvEvent::StartFile,
+                // the path is read here as property changes may arrive after the file is loaded
+                Event::FileLoaded => MpvEvent::FileLoaded {
+                    path: mpv.get_property::<String>("path").unwrap_or_default(),
+                },
+                Event::EndFile(reason) => MpvEvent::EndFile {
+                    eof: reason == mpv_end_file_reason::Eof,
+                },
+                Event::Shutdown => break,
+                _ => continue,
+            };
+
+            if sender.send(event).is_err() {
+                break;
+            }
+        }
     }
 
     pub fn command(&self, name: String, args: Vec<String>) -> MpvResponse {
@@ -107,13 +172,6 @@ impl MpvWrapper {
     }
 }
 
-impl MpvWrapper {
-    pub fn playing(&self) -> bool {
-        self.property("core-idle".to_string())
-            .is_some_and(|s| s.as_str() == "no" || s.as_str() == "false")
-    }
-}
-
 #[derive(Debug)]
 pub enum MpvAction {
     Command {
@@ -141,3 +199,84 @@ pub enum MpvResponse {
 impl Key for MpvWrapper {
     type Value = Self;
 }
+
+/// an event from the mpv event thread, passed to the main thread through `Task::MpvEvent`
+#[derive(Clone, Debug, PartialEq)]
+pub enum MpvEvent {
+    TimePos(f64),
+    Duration(f64),
+    Pause(bool),
+    MediaTitle(String),
+    Volume(f64),
+    StartFile,
+    FileLoaded {
+        path: String,
+    },
+    /// `eof` is false if the file is stopped or replaced before it finished playing
+    EndFile {
+        eof: bool,
+    },
+}
+
+/// playback state of the embedded player, updated from `MpvEvent`s
+#[derive(Clone, Default)]
+pub struct MpvState {
+    pub media_title: String,
+    pub time_pos: f64,
+    pub duration: f64,
+    pub paused: bool,
+    /// in percent, 100 is the original volume
+    pub volume: f64,
+    /// true from when a file starts until it ends
+    pub loaded: bool,
+    /// id of the video being played, if it is a YouTube video or a saved file
+    pub video_id: Option<String>,
+}
+
+impl MpvState {
+    pub fn update(&mut self, event: &MpvEvent) {
+        match event {
+            MpvEvent::TimePos(secs) => self.time_pos = *secs,
+            MpvEvent::Duration(secs) => self.duration = *secs,
+            MpvEvent::Pause(paused) => self.paused = *paused,
+            MpvEvent::MediaTitle(title) => self.media_title.clone_from(title),
+            MpvEvent::Volume(volume) => self.volume = *volume,
+            MpvEvent::StartFile => self.loaded = true,
+            MpvEvent::FileLoaded { path } => self.video_id = path_video_id(path),
+            MpvEvent::EndFile { .. } => {
+                self.loaded = false;
+                self.video_id = None;
+                self.time_pos = 0.;
+                self.duration = 0.;
+            }
+        }
+    }
+
+    /// a file is loaded and not paused
+    pub fn playing(&self) -> bool {
+        self.loaded && !self.paused
+    }
+
+    /// media title with the `[id].ext` suffix added by yt-dlp removed
+    pub fn display_title(&self) -> &str {
+        if let Some((name, ext)) = self.media_title.rsplit_once('.') {
+            if ext.len() < 5
+                && name.len() > 13
+                && name.ends_with(']')
+                && name.is_char_boundary(name.len() - 12)
+                && name[name.len() - 12..name.len() - 1]
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+                && name[..name.len() - 12].ends_with('[')
+            {
+                return &name[0..name.len() - 13];
+            }
+        }
+
+        &self.media_title
+    }
+}
+
+impl Key for MpvState {
+    type Value = Self;
+}
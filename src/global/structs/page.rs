@@ -14,6 +14,14 @@ pub enum Page {
     Search(Search),
     SingleItem(SingleItemPage),
     ChannelDisplay(ChannelDisplayPage),
+    /// comments of a video, holds the video id
+    Comments(String),
+    /// videos related to a video, holds the video id
+    Related(String),
+    /// items in the library, watch history and subscriptions matching the query
+    LocalSearch(String),
+    /// Invidious instances in the instance pool
+    Instances,
 }
 
 impl Debug for Page {
@@ -26,6 +34,10 @@ impl Debug for Page {
                 Self::Search(_) => "Search",
                 Self::SingleItem(_) => "SingleItem",
                 Self::ChannelDisplay(_) => "ChannelDisplay",
+                Self::Comments(_) => "Comments",
+                Self::Related(_) => "Related",
+                Self::LocalSearch(_) => "LocalSearch",
+                Self::Instances => "Instances",
             }
         ))
     }
@@ -59,6 +71,7 @@ pub enum MainMenuPage {
     Popular,
     History,
     Library,
+    Queue,
 }
 
 /// variants of the coannel display page
@@ -68,11 +81,18 @@ pub struct ChannelDisplayPage {
     pub r#type: ChannelDisplayPageType,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum ChannelDisplayPageType {
     Main,
     Videos,
+    Shorts,
+    /// live streams, including past ones
+    Streams,
     Playlists,
+    /// playlists of the channel's podcasts
+    Podcasts,
+    /// videos of the channel matching the query
+    Search(String),
 }
 
 /// Different items to be displayed on a single item page
@@ -80,6 +100,8 @@ pub enum ChannelDisplayPageType {
 pub enum SingleItemPage {
     Video(String),
     Playlist(String),
+    /// a playlist created by the user, holds its name
+    LocalPlaylist(String),
 }
 
 impl Page {
@@ -91,6 +113,10 @@ impl Page {
             Self::Search(_) => pages_config.search.clone(),
             Self::SingleItem(_) => pages_config.singleitem.clone(),
             Self::ChannelDisplay(_) => pages_config.channeldisplay.clone(),
+            Self::Comments(_) => pages_config.comments.clone(),
+            Self::Related(_) => pages_config.related.clone(),
+            Self::LocalSearch(_) => pages_config.localsearch.clone(),
+            Self::Instances => pages_config.instances.clone(),
         }
     }
 
@@ -103,6 +129,10 @@ impl Page {
             Self::Search(_) => pages_config.search.message.clone(),
             Self::SingleItem(_) => pages_config.singleitem.message.clone(),
             Self::ChannelDisplay(_) => pages_config.channeldisplay.message.clone(),
+            Self::Comments(_) => pages_config.comments.message.clone(),
+            Self::Related(_) => pages_config.related.message.clone(),
+            Self::LocalSearch(_) => pages_config.localsearch.message.clone(),
+            Self::Instances => pages_config.instances.message.clone(),
         }
     }
 }
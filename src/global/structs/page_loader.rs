@@ -0,0 +1,111 @@
+use std::{
+    error::Error,
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use typemap::Key;
+
+use crate::global::traits::{Prefetched, SearchProviderWrapper};
+
+/// the provider calls an item makes when it is loaded, `load_item` runs it to get its data, and
+/// `PageLoader` runs the same calls before the page is loaded
+pub type Fetch<T> = Box<dyn FnOnce() -> Result<T, Box<dyn Error>> + Send>;
+
+/// frames of the spinner shown in the message bar
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+/// makes the provider calls of the page being loaded on a background thread, so the interface
+/// keeps responding and the load can be cancelled
+#[derive(Clone, Default)]
+pub struct PageLoader {
+    pending: Option<PendingLoad>,
+    /// current frame of the spinner
+    frame: usize,
+}
+
+impl Key for PageLoader {
+    type Value = Self;
+}
+
+#[derive(Clone)]
+pub struct PendingLoad {
+    responses: Arc<Mutex<Receiver<Prefetched>>>,
+    /// shown next to the spinner
+    pub message: String,
+    /// the page command to run once the page is loaded, `None` when reloading
+    pub command: Option<String>,
+}
+
+impl PageLoader {
+    /// starts making the provider calls of a page (from `PageConfig::prefetch`), replacing the
+    /// load before it, returns false if the page makes no provider calls and can be loaded right
+    /// away
+    pub fn start(
+        &mut self,
+        requests: Option<impl FnOnce() + Send + 'static>,
+        message: String,
+        command: Option<String>,
+    ) -> bool {
+        self.pending = None;
+        let Some(requests) = requests else {
+            return false;
+        };
+
+        // reloading skips the response cache
+        let refresh = command.is_none();
+        let (sender, responses) = mpsc::channel();
+        thread::spawn(move || {
+            // the receiver is gone if the load has been cancelled
+            let _ = sender.send(SearchProviderWrapper::prefetch(refresh, requests));
+        });
+
+        self.frame = 0;
+        self.pending = Some(PendingLoad {
+            responses: Arc::new(Mutex::new(responses)),
+            message,
+            command,
+        });
+        true
+    }
+
+    /// if a page is being loaded
+    pub fn pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// returns the load if its responses have arrived, they are handed over to the provider
+    /// wrapper so the page loads without waiting
+    pub fn receive(&mut self) -> Option<PendingLoad> {
+        let prefetched = match self.pending.as_ref()?.responses.lock().unwrap().try_recv() {
+            Ok(prefetched) => prefetched,
+            Err(TryRecvError::Empty) => return None,
+            // the requests panicked, whatever is missing is fetched while loading the page
+            Err(TryRecvError::Disconnected) => Prefetched::default(),
+        };
+
+        SearchProviderWrapper::use_prefetched(prefetched);
+        self.pending.take()
+    }
+
+    /// stops waiting for the page being loaded, its responses are dropped once they arrive
+    pub fn cancel(&mut self) -> Option<PendingLoad> {
+        self.pending.take()
+    }
+
+    /// the next frame of the spinner, followed by the loading message
+    pub fn spinner(&mut self) -> String {
+        self.frame = (self.frame + 1) % SPINNER.len();
+        format!(
+            "{} {}",
+            SPINNER[self.frame],
+            self.pending
+                .as_ref()
+                .map(|load| load.message.as_str())
+                .unwrap_or_default()
+        )
+    }
+}
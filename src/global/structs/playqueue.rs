@@ -0,0 +1,107 @@
+use crate::global::{functions::fake_shuffle, structs::Item, traits::Collection};
+use serde::{Deserialize, Serialize};
+use typemap::Key;
+
+/// videos waiting to be played by the embedded mpv player, the first item is played next
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct PlayQueue {
+    pub items: Vec<Item>,
+    /// item loaded into mpv by the queue
+    #[serde(skip)]
+    pub playing: Option<Item>,
+    #[serde(skip)]
+    pub repeat: QueueRepeat,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum QueueRepeat {
+    /// items are removed from the queue after playing
+    #[default]
+    Off,
+    /// replay the current item
+    One,
+    /// played items are moved to the back of the queue
+    All,
+}
+
+impl QueueRepeat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "off" => Some(Self::Off),
+            "one" => Some(Self::One),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::One => "one",
+            Self::All => "all",
+        }
+    }
+}
+
+impl Key for PlayQueue {
+    type Value = Self;
+}
+
+impl Collection<Item> for PlayQueue {
+    const INDEX_PATH: &'static str = "queue.json";
+
+    fn items(&self) -> &Vec<Item> {
+        &self.items
+    }
+
+    fn items_mut(&mut self) -> &mut Vec<Item> {
+        &mut self.items
+    }
+
+    fn from_items(items: Vec<Item>) -> Self {
+        Self {
+            items,
+            ..Default::default()
+        }
+    }
+}
+
+impl PlayQueue {
+    /// finish the current item and returns the item to play next, according to the repeat mode
+    pub fn advance(&mut self) -> Option<Item> {
+        if let Some(previous) = self.playing.take() {
+            match self.repeat {
+                QueueRepeat::One => {
+                    self.playing = Some(previous);
+                    return self.playing.clone();
+                }
+                QueueRepeat::All => self.items.push(previous),
+                QueueRepeat::Off => {}
+            }
+        }
+
+        if self.items.is_empty() {
+            return None;
+        }
+
+        self.playing = Some(self.items.remove(0));
+        self.playing.clone()
+    }
+
+    /// add an item to the front of the queue
+    pub fn push_front(&mut self, item: Item) {
+        if let Some(id) = item.id() {
+            self.remove(id);
+        }
+        self.items.insert(0, item);
+    }
+
+    pub fn shuffle(&mut self) {
+        fake_shuffle(&mut self.items);
+    }
+
+    /// stop tracking the current item, so it will not advance when it finishes playing
+    pub fn stop(&mut self) {
+        self.playing = None;
+    }
+}
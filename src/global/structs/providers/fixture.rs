@@ -0,0 +1,237 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Deserialize};
+use urlencoding::encode;
+
+use crate::{
+    config::Search,
+    global::{
+        common::{
+            channel::{Channel, ChannelPlaylists, ChannelSearch, ChannelVideos},
+            hidden::{Caption, PopularItem, SearchItem},
+            universal::{Mix, Playlist, Suggestions, Trending},
+            video::{Comments, Video},
+            CommonVideo,
+        },
+        structs::{Errors, Transcript},
+        traits::SearchProviderTrait,
+    },
+};
+
+/// Serves captured responses from a directory of JSON files instead of the network
+///
+/// ```text
+/// video/{id}.json               Video
+/// channel/{id}.json             Channel
+/// channel_videos/{id}.json      ChannelVideos (or channel_videos/{continuation}.json)
+/// channel_playlists/{id}.json   ChannelPlaylists (or channel_playlists/{continuation}.json)
+/// channel_shorts/{id}.json      ChannelVideos (or channel_shorts/{continuation}.json)
+/// channel_streams/{id}.json     ChannelVideos (or channel_streams/{continuation}.json)
+/// channel_podcasts/{id}.json    ChannelPlaylists (or channel_podcasts/{continuation}.json)
+/// channel_search/{id}/{query}.json  ChannelSearch
+/// playlist/{id}.json            Playlist (or playlist/{continuation}.json)
+/// mix/{id}.json                 Mix
+/// comments/{id}.json            Comments (or comments/{continuation}.json)
+/// captions/{id}.json            [Caption], urls are paths to a Transcript relative to the directory
+/// search/{query}.json           [SearchItem] (search/{query}-{page}.json for later pages)
+/// search.json                   [SearchItem] for queries without their own file
+/// suggestions/{query}.json      Suggestions, queries without their own file have none
+/// trending.json                 [CommonVideo] or Trending (or trending/{continuation}.json)
+/// popular.json                  [PopularItem]
+/// ```
+///
+/// ids and queries are url encoded in file names
+#[derive(Clone)]
+pub struct FixtureProvider {
+    pub dir: PathBuf,
+}
+
+impl FixtureProvider {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn read<T: DeserializeOwned>(&self, path: &Path) -> Result<T, Box<dyn Error>> {
+        let path = self.dir.join(path);
+        let content = fs::read_to_string(&path).map_err(|e| {
+            Errors::StringError(format!("Cannot read fixture `{}`: {e}", path.display()))
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn read_id<T: DeserializeOwned>(&self, folder: &str, id: &str) -> Result<T, Box<dyn Error>> {
+        self.read(&Path::new(folder).join(format!("{}.json", encode(id))))
+    }
+}
+
+/// `trending.json` is a list like in the Invidious API, or a `Trending` with a continuation
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TrendingFixture {
+    List(Vec<CommonVideo>),
+    Trending(Trending),
+}
+
+impl SearchProviderTrait for FixtureProvider {
+    fn supports_channel(&self) -> bool {
+        true
+    }
+    fn channel(&self, id: &str) -> Result<Channel, Box<dyn Error>> {
+        self.read_id("channel", id)
+    }
+
+    fn supports_channel_videos(&self) -> bool {
+        true
+    }
+    fn channel_videos(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        self.read_id("channel_videos", continuation.unwrap_or(id))
+    }
+
+    fn supports_channel_playlists(&self) -> bool {
+        true
+    }
+    fn channel_playlists(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelPlaylists, Box<dyn Error>> {
+        self.read_id("channel_playlists", continuation.unwrap_or(id))
+    }
+
+    fn supports_channel_shorts(&self) -> bool {
+        true
+    }
+    fn channel_shorts(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        self.read_id("channel_shorts", continuation.unwrap_or(id))
+    }
+
+    fn supports_channel_streams(&self) -> bool {
+        true
+    }
+    fn channel_streams(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        self.read_id("channel_streams", continuation.unwrap_or(id))
+    }
+
+    fn supports_channel_podcasts(&self) -> bool {
+        true
+    }
+    fn channel_podcasts(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelPlaylists, Box<dyn Error>> {
+        self.read_id("channel_podcasts", continuation.unwrap_or(id))
+    }
+
+    fn supports_channel_search(&self) -> bool {
+        true
+    }
+    fn channel_search(&self, id: &str, query: &str) -> Result<ChannelSearch, Box<dyn Error>> {
+        self.read_id(&format!("channel_search/{}", encode(id)), query)
+    }
+
+    fn supports_trending(&self) -> bool {
+        true
+    }
+    fn trending(&self, continuation: Option<&str>) -> Result<Trending, Box<dyn Error>> {
+        if let Some(continuation) = continuation {
+            return self.read_id("trending", continuation);
+        }
+
+        Ok(match self.read(Path::new("trending.json"))? {
+            TrendingFixture::List(videos) => Trending {
+                videos,
+                continuation: None,
+            },
+            TrendingFixture::Trending(trending) => trending,
+        })
+    }
+
+    fn supports_popular(&self) -> bool {
+        true
+    }
+    fn popular(&self) -> Result<Vec<PopularItem>, Box<dyn Error>> {
+        self.read(Path::new("popular.json"))
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+    fn search(&self, filters: &Search) -> Result<Vec<SearchItem>, Box<dyn Error>> {
+        if filters.page > 1 {
+            // there are no more results if a later page is not captured
+            return Ok(self
+                .read_id("search", &format!("{}-{}", filters.query, filters.page))
+                .unwrap_or_default());
+        }
+
+        self.read_id("search", &filters.query)
+            .or_else(|_| self.read(Path::new("search.json")))
+    }
+
+    fn supports_suggestions(&self) -> bool {
+        true
+    }
+    fn suggestions(&self, query: &str) -> Result<Suggestions, Box<dyn Error>> {
+        Ok(self
+            .read_id("suggestions", query)
+            .unwrap_or_else(|_| Suggestions {
+                query: query.to_string(),
+                suggestions: Vec::new(),
+            }))
+    }
+
+    fn supports_video(&self) -> bool {
+        true
+    }
+    fn video(&self, id: &str) -> Result<Video, Box<dyn Error>> {
+        self.read_id("video", id)
+    }
+
+    fn supports_playlist(&self) -> bool {
+        true
+    }
+    fn playlist(&self, id: &str, continuation: Option<&str>) -> Result<Playlist, Box<dyn Error>> {
+        self.read_id("playlist", continuation.unwrap_or(id))
+    }
+
+    fn supports_mix(&self) -> bool {
+        true
+    }
+    fn mix(&self, id: &str) -> Result<Mix, Box<dyn Error>> {
+        self.read_id("mix", id)
+    }
+
+    fn supports_comments(&self) -> bool {
+        true
+    }
+    fn comments(&self, id: &str, continuation: Option<&str>) -> Result<Comments, Box<dyn Error>> {
+        self.read_id("comments", continuation.unwrap_or(id))
+    }
+
+    fn supports_captions(&self) -> bool {
+        true
+    }
+    fn captions(&self, id: &str) -> Result<Vec<Caption>, Box<dyn Error>> {
+        self.read_id("captions", id)
+    }
+    fn transcript(&self, caption: &Caption) -> Result<Transcript, Box<dyn Error>> {
+        self.read(Path::new(&caption.url))
+    }
+}
@@ -1,15 +1,130 @@
-use invidious::{ClientSync, ClientSyncTrait};
+use std::{error::Error, time::Duration};
 
-use crate::global::traits::SearchProviderTrait;
+use invidious::{ClientSyncTrait, InvidiousError, PublicItems};
+use serde::de::DeserializeOwned;
 
-// used in `data.global`
-/// Holds the crate::global::common client
+use crate::global::{
+    functions::httpreq_get_timeout,
+    structs::{Errors, InstancePool},
+    traits::SearchProviderTrait,
+};
+
+/// Fetches from Invidious, moving on to the next instance in the pool if one cannot be reached
 #[derive(Clone)]
-pub struct InvidiousClient(pub ClientSync);
+pub struct InvidiousClient {
+    /// the only instance to use, or `None` to use `InstancePool`
+    pub instance: Option<String>,
+}
 
 impl InvidiousClient {
     pub fn new(instance: String) -> Self {
-        Self(ClientSync::default().instance(instance))
+        Self {
+            instance: Some(instance),
+        }
+    }
+
+    pub fn pool() -> Self {
+        Self { instance: None }
+    }
+
+    /// tries each instance until one of them responds
+    ///
+    /// errors returned by the API (such as an unavaliable video) are not retried, as every
+    /// instance would respond with the same error
+    fn call<T>(
+        &self,
+        f: impl Fn(&InstanceClient) -> Result<T, InvidiousError>,
+    ) -> Result<T, Box<dyn Error>> {
+        let (urls, timeout) = {
+            let pool = InstancePool::global();
+            (
+                match &self.instance {
+                    Some(instance) => vec![instance.clone()],
+                    None => pool.order(),
+                },
+                pool.timeout,
+            )
+        };
+
+        let mut failed = Vec::new();
+        let mut last_error = None;
+
+        for url in urls {
+            match f(&InstanceClient {
+                instance: url.clone(),
+                timeout,
+            }) {
+                Ok(value) => {
+                    InstancePool::global().report_success(&url);
+                    return Ok(value);
+                }
+                Err(e @ InvidiousError::ApiError { .. }) => {
+                    InstancePool::global().report_success(&url);
+                    return Err(e.into());
+                }
+                Err(e) => {
+                    InstancePool::global().report_failure(&url, e.to_string());
+                    failed.push(format!("{url}: {e}"));
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match last_error {
+            None => Err(Errors::StrError("No Invidious instances configured").into()),
+            Some(e) if failed.len() == 1 => Err(e.into()),
+            Some(_) => Err(Errors::StringError(format!(
+                "All Invidious instances failed - {}",
+                failed.join(", ")
+            ))
+            .into()),
+        }
+    }
+
+    /// a channel tab without a method in the Invidious crate, such as `shorts`
+    fn channel_tab<T: PublicItems + DeserializeOwned>(
+        &self,
+        id: &str,
+        tab: &str,
+        continuation: Option<&str>,
+    ) -> Result<T, Box<dyn Error>> {
+        let url = match continuation_param(continuation) {
+            Some(params) => format!("/api/v1/channels/{id}/{tab}?{params}"),
+            None => format!("/api/v1/channels/{id}/{tab}"),
+        };
+        self.call(|client| T::from_str(InvidiousError::as_fetch_error(client.fetch(&url))?))
+    }
+}
+
+/// a single Invidious instance, requests give up after `timeout` so dead instances fail quickly
+#[derive(Clone)]
+pub struct InstanceClient {
+    pub instance: String,
+    pub timeout: Duration,
+}
+
+impl ClientSyncTrait for InstanceClient {
+    fn new(instance: String) -> Self {
+        Self {
+            instance,
+            timeout: InstancePool::global().timeout,
+        }
+    }
+
+    fn set_instance(&mut self, instance: String) {
+        self.instance = instance;
+    }
+
+    fn get_instance(&self) -> &str {
+        &self.instance
+    }
+
+    fn fetch(&self, url: &str) -> Result<String, Box<dyn Error>> {
+        let (_, body) = httpreq_get_timeout(
+            &format!("{}/{}", self.instance, url.trim_start_matches('/')),
+            self.timeout,
+        )?;
+        Ok(String::from_utf8(body)?)
     }
 }
 
@@ -364,6 +479,8 @@ impl From<invidious::hidden::VideoShort> for crate::global::common::hidden::Vide
             title: value.title,
             thumbnails: value.thumbnails.into_iter().map(|v| v.into()).collect(),
             author: value.author,
+            // not included in the Invidious crate
+            author_id: String::new(),
             length: value.length,
             views_text: value.views_text,
         }
@@ -584,6 +701,16 @@ impl From<invidious::universal::Playlist> for crate::global::common::universal::
             updated: value.updated,
             listed: value.listed,
             videos: value.videos.into_iter().map(|v| v.into()).collect(),
+            continuation: None,
+        }
+    }
+}
+
+impl From<invidious::universal::Suggestions> for crate::global::common::universal::Suggestions {
+    fn from(original: invidious::universal::Suggestions) -> Self {
+        Self {
+            query: original.query,
+            suggestions: original.suggestions,
         }
     }
 }
@@ -603,6 +730,59 @@ impl From<invidious::hidden::PlaylistItem> for crate::global::common::hidden::Pl
     }
 }
 
+impl From<invidious::hidden::CreatorHeart> for crate::global::common::hidden::CreatorHeart {
+    fn from(value: invidious::hidden::CreatorHeart) -> Self {
+        Self {
+            thumbnail: value.thumbnail,
+            name: value.name,
+        }
+    }
+}
+
+impl From<invidious::hidden::Comment> for crate::global::common::hidden::Comment {
+    fn from(value: invidious::hidden::Comment) -> Self {
+        Self {
+            verified: value.verified,
+            id: value.id,
+            likes: value.likes,
+            edited: value.edited,
+            content: value.content,
+            content_html: value.content_html,
+            published: value.published,
+            published_text: value.published_text,
+            author: value.author,
+            author_thumbnails: value
+                .author_thumbnails
+                .into_iter()
+                .map(|v| v.into())
+                .collect(),
+            author_id: value.author_id,
+            author_url: value.author_url,
+            channel_owner: value.channel_owner,
+            heart: value.heart.map(|v| v.into()),
+            // fields of `invidious::hidden::Replies` are private, but it can still be serialised
+            replies: value
+                .replies
+                .and_then(|v| serde_json::from_value(serde_json::to_value(v).ok()?).ok()),
+        }
+    }
+}
+
+impl From<invidious::video::Comments> for crate::global::common::video::Comments {
+    fn from(value: invidious::video::Comments) -> Self {
+        Self {
+            comment_count: value.comment_count,
+            id: value.id,
+            comments: value.comments.into_iter().map(|v| v.into()).collect(),
+            continuation: value.continuation,
+        }
+    }
+}
+
+fn continuation_param(continuation: Option<&str>) -> Option<String> {
+    continuation.map(|continuation| format!("continuation={}", urlencoding::encode(continuation)))
+}
+
 impl SearchProviderTrait for InvidiousClient {
     fn supports_video(&self) -> bool {
         true
@@ -612,10 +792,7 @@ impl SearchProviderTrait for InvidiousClient {
         &self,
         id: &str,
     ) -> Result<crate::global::common::video::Video, Box<dyn std::error::Error>> {
-        match self.0.video(id, None) {
-            Ok(k) => Ok(k.into()),
-            Err(e) => Err(e.into()),
-        }
+        Ok(self.call(|client| client.video(id, None))?.into())
     }
 
     fn supports_search(&self) -> bool {
@@ -626,10 +803,26 @@ impl SearchProviderTrait for InvidiousClient {
         &self,
         filters: &crate::config::Search,
     ) -> Result<Vec<crate::global::common::hidden::SearchItem>, Box<dyn std::error::Error>> {
-        match self.0.search(Some(&filters.to_string())) {
-            Ok(k) => Ok(k.items.into_iter().map(|v| v.into()).collect()),
-            Err(e) => Err(e.into()),
-        }
+        Ok(self
+            .call(|client| client.search(Some(&filters.to_string())))?
+            .items
+            .into_iter()
+            .map(|v| v.into())
+            .collect())
+    }
+
+    fn supports_suggestions(&self) -> bool {
+        true
+    }
+
+    fn suggestions(
+        &self,
+        query: &str,
+    ) -> Result<crate::global::common::universal::Suggestions, Box<dyn std::error::Error>> {
+        let params = format!("q={}", urlencoding::encode(query));
+        Ok(self
+            .call(|client| client.search_suggestions(Some(&params)))?
+            .into())
     }
 
     fn supports_channel(&self) -> bool {
@@ -640,10 +833,7 @@ impl SearchProviderTrait for InvidiousClient {
         &self,
         id: &str,
     ) -> Result<crate::global::common::channel::Channel, Box<dyn std::error::Error>> {
-        match self.0.channel(id, None) {
-            Ok(k) => Ok(k.into()),
-            Err(e) => Err(e.into()),
-        }
+        Ok(self.call(|client| client.channel(id, None))?.into())
     }
 
     fn supports_popular(&self) -> bool {
@@ -653,10 +843,12 @@ impl SearchProviderTrait for InvidiousClient {
     fn popular(
         &self,
     ) -> Result<Vec<crate::global::common::hidden::PopularItem>, Box<dyn std::error::Error>> {
-        match self.0.popular(None) {
-            Ok(k) => Ok(k.items.into_iter().map(|v| v.into()).collect()),
-            Err(e) => Err(e.into()),
-        }
+        Ok(self
+            .call(|client| client.popular(None))?
+            .items
+            .into_iter()
+            .map(|v| v.into())
+            .collect())
     }
 
     fn supports_trending(&self) -> bool {
@@ -665,11 +857,22 @@ impl SearchProviderTrait for InvidiousClient {
 
     fn trending(
         &self,
-    ) -> Result<Vec<crate::global::common::CommonVideo>, Box<dyn std::error::Error>> {
-        match self.0.trending(None) {
-            Ok(k) => Ok(k.videos.into_iter().map(|v| v.into()).collect()),
-            Err(e) => Err(e.into()),
+        continuation: Option<&str>,
+    ) -> Result<crate::global::common::universal::Trending, Box<dyn std::error::Error>> {
+        // trending is a single page
+        if continuation.is_some() {
+            return Ok(Default::default());
         }
+
+        Ok(crate::global::common::universal::Trending {
+            videos: self
+                .call(|client| client.trending(None))?
+                .videos
+                .into_iter()
+                .map(|v| v.into())
+                .collect(),
+            continuation: None,
+        })
     }
 
     fn supports_playlist(&self) -> bool {
@@ -679,11 +882,47 @@ impl SearchProviderTrait for InvidiousClient {
     fn playlist(
         &self,
         id: &str,
+        continuation: Option<&str>,
     ) -> Result<crate::global::common::universal::Playlist, Box<dyn std::error::Error>> {
-        match self.0.playlist(id, None) {
-            Ok(k) => Ok(k.into()),
-            Err(e) => Err(e.into()),
+        // playlists are paged by page number instead of a token
+        let page = continuation
+            .and_then(|page| page.parse::<u32>().ok())
+            .unwrap_or(1);
+        let params = continuation.map(|_| format!("page={page}"));
+        let mut playlist: crate::global::common::universal::Playlist = self
+            .call(|client| client.playlist(id, params.as_deref()))?
+            .into();
+
+        if playlist
+            .videos
+            .last()
+            .is_some_and(|video| video.index + 1 < playlist.video_count)
+        {
+            playlist.continuation = Some((page + 1).to_string());
         }
+        Ok(playlist)
+    }
+
+    fn supports_mix(&self) -> bool {
+        true
+    }
+
+    fn mix(
+        &self,
+        id: &str,
+    ) -> Result<crate::global::common::universal::Mix, Box<dyn std::error::Error>> {
+        // videos in the Invidious crate's `Mix` have private fields, so the response is parsed here
+        self.call(|client| {
+            let res = InvidiousError::as_fetch_error(client.fetch(&format!("/api/v1/mixes/{id}")))?;
+            let value: serde_json::Value =
+                InvidiousError::as_serde_error(serde_json::from_str(&res), Some(res))?;
+            if let Some(message) = value["error"].as_str() {
+                return Err(InvidiousError::ApiError {
+                    message: message.to_string(),
+                });
+            }
+            InvidiousError::as_serde_error(serde_json::from_value(value), None)
+        })
     }
 
     fn supports_channel_videos(&self) -> bool {
@@ -693,11 +932,15 @@ impl SearchProviderTrait for InvidiousClient {
     fn channel_videos(
         &self,
         id: &str,
-    ) -> Result<Vec<crate::global::common::CommonVideo>, Box<dyn std::error::Error>> {
-        match self.0.channel_videos(id, None) {
-            Ok(k) => Ok(k.videos.into_iter().map(|v| v.into()).collect()),
-            Err(e) => Err(e.into()),
-        }
+        continuation: Option<&str>,
+    ) -> Result<crate::global::common::channel::ChannelVideos, Box<dyn std::error::Error>> {
+        let res = self.call(|client| {
+            client.channel_videos(id, continuation_param(continuation).as_deref())
+        })?;
+        Ok(crate::global::common::channel::ChannelVideos {
+            videos: res.videos.into_iter().map(|v| v.into()).collect(),
+            continuation: res.continuation,
+        })
     }
 
     fn supports_channel_playlists(&self) -> bool {
@@ -707,10 +950,127 @@ impl SearchProviderTrait for InvidiousClient {
     fn channel_playlists(
         &self,
         id: &str,
-    ) -> Result<Vec<crate::global::common::CommonPlaylist>, Box<dyn std::error::Error>> {
-        match self.0.channel_playlists(id, None) {
-            Ok(k) => Ok(k.playlists.into_iter().map(|v| v.into()).collect()),
-            Err(e) => Err(e.into()),
-        }
+        continuation: Option<&str>,
+    ) -> Result<crate::global::common::channel::ChannelPlaylists, Box<dyn std::error::Error>> {
+        let res = self.call(|client| {
+            client.channel_playlists(id, continuation_param(continuation).as_deref())
+        })?;
+        Ok(crate::global::common::channel::ChannelPlaylists {
+            playlists: res.playlists.into_iter().map(|v| v.into()).collect(),
+            continuation: res.continuation,
+        })
+    }
+
+    fn supports_channel_shorts(&self) -> bool {
+        true
+    }
+
+    fn channel_shorts(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<crate::global::common::channel::ChannelVideos, Box<dyn std::error::Error>> {
+        let res: invidious::channel::ChannelVideos =
+            self.channel_tab(id, "shorts", continuation)?;
+        Ok(crate::global::common::channel::ChannelVideos {
+            videos: res.videos.into_iter().map(|v| v.into()).collect(),
+            continuation: res.continuation,
+        })
+    }
+
+    fn supports_channel_streams(&self) -> bool {
+        true
+    }
+
+    fn channel_streams(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<crate::global::common::channel::ChannelVideos, Box<dyn std::error::Error>> {
+        let res: invidious::channel::ChannelVideos =
+            self.channel_tab(id, "streams", continuation)?;
+        Ok(crate::global::common::channel::ChannelVideos {
+            videos: res.videos.into_iter().map(|v| v.into()).collect(),
+            continuation: res.continuation,
+        })
+    }
+
+    fn supports_channel_podcasts(&self) -> bool {
+        true
+    }
+
+    fn channel_podcasts(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<crate::global::common::channel::ChannelPlaylists, Box<dyn std::error::Error>> {
+        let res: invidious::channel::ChannelPlaylists =
+            self.channel_tab(id, "podcasts", continuation)?;
+        Ok(crate::global::common::channel::ChannelPlaylists {
+            playlists: res.playlists.into_iter().map(|v| v.into()).collect(),
+            continuation: res.continuation,
+        })
+    }
+
+    fn supports_channel_search(&self) -> bool {
+        true
+    }
+
+    fn channel_search(
+        &self,
+        id: &str,
+        query: &str,
+    ) -> Result<crate::global::common::channel::ChannelSearch, Box<dyn std::error::Error>> {
+        let params = format!("q={}", urlencoding::encode(query));
+        Ok(crate::global::common::channel::ChannelSearch {
+            items: self
+                .call(|client| client.channel_search(id, Some(&params)))?
+                .items
+                .into_iter()
+                .map(|v| v.into())
+                .collect(),
+        })
+    }
+
+    fn supports_comments(&self) -> bool {
+        true
+    }
+
+    fn comments(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<crate::global::common::video::Comments, Box<dyn std::error::Error>> {
+        Ok(self
+            .call(|client| client.comments(id, continuation_param(continuation).as_deref()))?
+            .into())
+    }
+
+    fn supports_captions(&self) -> bool {
+        true
+    }
+
+    fn captions(
+        &self,
+        id: &str,
+    ) -> Result<Vec<crate::global::common::hidden::Caption>, Box<dyn std::error::Error>> {
+        Ok(self
+            .call(|client| client.captions(id, None))?
+            .captions
+            .into_iter()
+            .map(|v| v.into())
+            .collect())
+    }
+
+    fn transcript(
+        &self,
+        caption: &crate::global::common::hidden::Caption,
+    ) -> Result<crate::global::structs::Transcript, Box<dyn std::error::Error>> {
+        // caption urls are relative to the instance, and are always in WebVTT
+        let res = self.call(|client| InvidiousError::as_fetch_error(client.fetch(&caption.url)))?;
+        Ok(crate::global::structs::Transcript::parse(
+            &res,
+            caption.language.clone(),
+        )?)
     }
 }
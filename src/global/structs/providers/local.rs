@@ -0,0 +1,439 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    config::MainConfig,
+    global::{
+        common::{
+            channel::{Channel, ChannelVideos},
+            hidden::{Caption, PlaylistItem},
+            universal::Playlist,
+            video::Video,
+            CommonImage, CommonThumbnail, CommonVideo,
+        },
+        functions::{find_in_library, library_dir, parse_length, paths},
+        structs::{
+            Errors, FullChannelItem, FullPlaylistItem, FullVideoItem, Item, LocalStore,
+            MiniVideoItem, Subscriptions, Transcript,
+        },
+        traits::SearchProviderTrait,
+    },
+};
+
+/// Serves data saved on disk instead of the network, used in offline mode
+///
+/// ```text
+/// info/{id}.json          videos, playlists and channels that have been viewed
+/// channels/{id}.json      channels synced from subscriptions
+/// subscriptions.json      subscribed channels and their latest videos
+/// captions/{id}.*.vtt     downloaded captions
+/// ```
+///
+/// videos saved to the library without their info are named after their file
+#[derive(Clone)]
+pub struct LocalProvider {
+    /// the data directory
+    pub dir: PathBuf,
+    /// where videos are saved to, from the `save-path` env
+    pub library: Option<PathBuf>,
+}
+
+impl LocalProvider {
+    pub fn new(dir: PathBuf, library: Option<PathBuf>) -> Self {
+        Self { dir, library }
+    }
+
+    /// reads from the data directory and the library in `mainconfig`
+    pub fn open(mainconfig: &MainConfig) -> Self {
+        Self::new(paths::data_dir(), library_dir(mainconfig))
+    }
+
+    fn read<T: DeserializeOwned>(&self, path: &Path) -> Option<T> {
+        serde_json::from_str(&fs::read_to_string(self.dir.join(path)).ok()?).ok()
+    }
+
+    /// info of a viewed item, items that are not written to disk yet are in `LocalStore`
+    fn info(&self, id: &str) -> Option<Item> {
+        LocalStore::get_loaded(id)
+            .or_else(|| self.read(&Path::new("info").join(format!("{id}.json"))))
+    }
+
+    fn subscriptions(&self) -> Subscriptions {
+        self.read(Path::new("subscriptions.json"))
+            .unwrap_or_default()
+    }
+
+    fn subscription_video(&self, id: &str) -> Option<MiniVideoItem> {
+        self.subscriptions()
+            .0
+            .into_iter()
+            .flat_map(|item| item.videos)
+            .find(|video| video.id == id)
+    }
+}
+
+impl SearchProviderTrait for LocalProvider {
+    fn supports_channel(&self) -> bool {
+        true
+    }
+    fn channel(&self, id: &str) -> Result<Channel, Box<dyn Error>> {
+        let subscription = self
+            .subscriptions()
+            .0
+            .into_iter()
+            .find(|item| item.channel.id == id);
+        let channel = match self
+            .info(id)
+            .or_else(|| self.read(&Path::new("channels").join(format!("{id}.json"))))
+        {
+            Some(Item::FullChannel(channel)) => channel,
+            _ => subscription
+                .as_ref()
+                .map(|item| item.channel.clone())
+                .ok_or_else(|| not_saved("Channel", id))?,
+        };
+
+        Ok(channel_convert(
+            channel,
+            subscription
+                .map(|item| item.videos.into_iter().map(common_video_convert).collect())
+                .unwrap_or_default(),
+        ))
+    }
+
+    fn supports_channel_videos(&self) -> bool {
+        true
+    }
+    /// latest videos of a subscribed channel, from the last time it was synced
+    fn channel_videos(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        if continuation.is_some() {
+            return Ok(ChannelVideos::default());
+        }
+
+        let item = self
+            .subscriptions()
+            .0
+            .into_iter()
+            .find(|item| item.channel.id == id)
+            .ok_or(Errors::StrError(
+                "Videos are only avaliable offline for subscribed channels",
+            ))?;
+
+        Ok(ChannelVideos {
+            videos: item.videos.into_iter().map(common_video_convert).collect(),
+            continuation: None,
+        })
+    }
+
+    fn supports_video(&self) -> bool {
+        true
+    }
+    fn video(&self, id: &str) -> Result<Video, Box<dyn Error>> {
+        let video = match self.info(id) {
+            Some(Item::FullVideo(video)) => video,
+            Some(Item::MiniVideo(video)) => mini_to_full(video),
+            _ => match self.subscription_video(id) {
+                Some(video) => mini_to_full(video),
+                None => library_video(
+                    id,
+                    &self
+                        .library
+                        .as_ref()
+                        .and_then(|library| find_in_library(library, id))
+                        .ok_or_else(|| not_saved("Video", id))?,
+                ),
+            },
+        };
+
+        Ok(video_convert(video))
+    }
+
+    fn supports_playlist(&self) -> bool {
+        true
+    }
+    fn playlist(&self, id: &str, continuation: Option<&str>) -> Result<Playlist, Box<dyn Error>> {
+        // all videos of a saved playlist are in the first page
+        if continuation.is_some() {
+            return Ok(Playlist::default());
+        }
+
+        match self.info(id) {
+            Some(Item::FullPlaylist(playlist)) => Ok(playlist_convert(playlist)),
+            _ => Err(not_saved("Playlist", id).into()),
+        }
+    }
+
+    fn supports_captions(&self) -> bool {
+        true
+    }
+    fn captions(&self, id: &str) -> Result<Vec<Caption>, Box<dyn Error>> {
+        let prefix = format!("{id}.");
+        let captions = fs::read_dir(self.dir.join("captions"))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| Some(entry.ok()?.path()))
+                    .filter_map(|path| {
+                        let language = path
+                            .file_name()?
+                            .to_str()?
+                            .strip_prefix(&prefix)?
+                            .strip_suffix(".vtt")?
+                            .to_string();
+                        Some(Caption {
+                            label: language.clone(),
+                            language,
+                            url: path.to_string_lossy().to_string(),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if captions.is_empty() {
+            return Err(Errors::StrError("No captions have been downloaded for this video").into());
+        }
+        Ok(captions)
+    }
+    fn transcript(&self, caption: &Caption) -> Result<Transcript, Box<dyn Error>> {
+        Ok(Transcript::parse(
+            &fs::read_to_string(&caption.url)?,
+            caption.language.clone(),
+        )?)
+    }
+}
+
+fn not_saved(kind: &str, id: &str) -> Errors {
+    Errors::StringError(format!("{kind} {id} is not saved for offline use"))
+}
+
+/// a video in the library with no info saved, files are named `{title}[{id}].{ext}`
+fn library_video(id: &str, path: &Path) -> FullVideoItem {
+    let stem = path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    mini_to_full(MiniVideoItem {
+        title: stem
+            .strip_suffix(&format!("[{id}]"))
+            .unwrap_or(&stem)
+            .trim()
+            .to_string(),
+        id: id.to_string(),
+        thumbnail_url: String::new(),
+        length: String::new(),
+        views: None,
+        channel: String::new(),
+        channel_id: String::new(),
+        published: None,
+        timestamp: None,
+        description: None,
+        length_secs: 0,
+        live: false,
+        upcoming: false,
+        unresolved: false,
+    })
+}
+
+fn mini_to_full(video: MiniVideoItem) -> FullVideoItem {
+    FullVideoItem {
+        title: video.title,
+        id: video.id,
+        thumbnail_url: video.thumbnail_url,
+        length: video.length,
+        views: video.views.unwrap_or_default(),
+        channel: video.channel,
+        channel_id: video.channel_id,
+        sub_count: String::new(),
+        published: video.published.unwrap_or_default(),
+        description: video.description.unwrap_or_default(),
+        likes: String::new(),
+        genre: String::new(),
+    }
+}
+
+fn thumbnails(url: String) -> Vec<CommonThumbnail> {
+    if url.is_empty() {
+        return Vec::new();
+    }
+
+    vec![CommonThumbnail {
+        quality: String::new(),
+        url,
+        width: 0,
+        height: 0,
+    }]
+}
+
+/// undoes `viewcount_text()`, such as `1.56M` to `1560000`
+fn parse_count(text: &str) -> u64 {
+    let text = text.trim();
+    let (number, unit) = match text.chars().last() {
+        Some('B') => (&text[..text.len() - 1], 1_000_000_000_f64),
+        Some('M') => (&text[..text.len() - 1], 1_000_000_f64),
+        Some('K') => (&text[..text.len() - 1], 1_000_f64),
+        _ => (text, 1_f64),
+    };
+    number
+        .parse::<f64>()
+        .map(|number| (number * unit).round() as u64)
+        .unwrap_or_default()
+}
+
+/// splits a published string such as `3 days ago [1/2/2024]` into its text and timestamp
+fn parse_published(text: &str) -> (String, u64) {
+    match text.rsplit_once(" [") {
+        Some((published_text, date)) => (
+            published_text.to_string(),
+            parse_date(date.trim_end_matches(']')),
+        ),
+        None => (text.to_string(), 0),
+    }
+}
+
+/// undoes `date_text()`, such as `1/2/2024` to its timestamp
+fn parse_date(text: &str) -> u64 {
+    NaiveDate::parse_from_str(text, "%d/%m/%Y")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc().timestamp().max(0) as u64)
+        .unwrap_or_default()
+}
+
+fn common_video_convert(video: MiniVideoItem) -> CommonVideo {
+    let (published_text, published) = parse_published(&video.published.unwrap_or_default());
+    CommonVideo {
+        title: video.title,
+        id: video.id,
+        author: video.channel,
+        author_id: video.channel_id,
+        thumbnails: thumbnails(video.thumbnail_url),
+        description: video.description.unwrap_or_default(),
+        views: parse_count(&video.views.unwrap_or_default()),
+        length: if video.length_secs == 0 {
+            parse_length(&video.length)
+        } else {
+            video.length_secs
+        },
+        published: video.timestamp.unwrap_or(published),
+        published_text,
+        live: video.live,
+        upcoming: video.upcoming,
+        ..Default::default()
+    }
+}
+
+fn video_convert(video: FullVideoItem) -> Video {
+    let (published_text, published) = parse_published(&video.published);
+    Video {
+        r#type: String::from("video"),
+        title: video.title,
+        id: video.id,
+        thumbnails: thumbnails(video.thumbnail_url),
+        storyboards: Vec::new(),
+        description_html: video.description.clone(),
+        description: video.description,
+        published,
+        published_text,
+        keywords: Vec::new(),
+        views: parse_count(&video.views),
+        likes: parse_count(&video.likes) as u32,
+        dislikes: 0,
+        paid: false,
+        premium: false,
+        family_friendly: true,
+        allowed_regions: Vec::new(),
+        genre: video.genre,
+        genre_url: None,
+        author_url: format!("/channel/{}", video.channel_id),
+        author: video.channel,
+        author_id: video.channel_id,
+        author_thumbnails: Vec::new(),
+        sub_count_text: video.sub_count,
+        length: parse_length(&video.length),
+        allow_ratings: true,
+        rating: 0.0,
+        listed: true,
+        live: false,
+        upcoming: false,
+        premiere_timestamp: 0,
+        dash: String::new(),
+        adaptive_formats: Vec::new(),
+        format_streams: Vec::new(),
+        captions: Vec::new(),
+        recommended_videos: Vec::new(),
+    }
+}
+
+fn playlist_convert(playlist: FullPlaylistItem) -> Playlist {
+    Playlist {
+        title: playlist.title,
+        id: playlist.id,
+        thumbnail: playlist.thumbnail_url,
+        author: playlist.channel,
+        author_id: playlist.channel_id,
+        description_html: playlist.description.clone(),
+        description: playlist.description,
+        video_count: playlist.video_count,
+        views: parse_count(&playlist.views),
+        listed: true,
+        videos: playlist
+            .videos
+            .into_iter()
+            .filter_map(|video| video.into_minivideo().ok())
+            .enumerate()
+            .map(|(index, video)| PlaylistItem {
+                title: video.title,
+                id: video.id,
+                author: video.channel,
+                author_url: format!("/channel/{}", video.channel_id),
+                author_id: video.channel_id,
+                thumbnails: thumbnails(video.thumbnail_url),
+                index: index as u32,
+                length: if video.length_secs == 0 {
+                    parse_length(&video.length)
+                } else {
+                    video.length_secs
+                },
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn channel_convert(channel: FullChannelItem, latest_videos: Vec<CommonVideo>) -> Channel {
+    Channel {
+        url: format!("/channel/{}", channel.id),
+        name: channel.name,
+        id: channel.id,
+        banners: Vec::new(),
+        thumbnails: thumbnails(channel.thumbnail_url)
+            .into_iter()
+            .map(|thumbnail| CommonImage {
+                url: thumbnail.url,
+                width: 0,
+                height: 0,
+            })
+            .collect(),
+        subscribers: channel.sub_count,
+        total_views: parse_count(&channel.total_views),
+        joined: parse_date(&channel.created),
+        auto_generated: channel.autogenerated,
+        family_friendly: true,
+        description_html: channel.description.clone(),
+        description: channel.description,
+        allowed_regions: Vec::new(),
+        latest_videos,
+        related_channels: Vec::new(),
+    }
+}
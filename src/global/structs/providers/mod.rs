@@ -1,4 +1,8 @@
+pub mod fixture;
 #[cfg(feature = "invidious")]
 pub mod invidiousclient;
+pub mod local;
+#[cfg(feature = "piped")]
+pub mod piped;
 #[cfg(feature = "rustypipe")]
 pub mod rustypipe;
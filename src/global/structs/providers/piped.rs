@@ -0,0 +1,696 @@
+use std::{error::Error, time::Duration};
+
+use chrono::NaiveDate;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use urlencoding::encode;
+
+use crate::{
+    config::{Search, SearchFilterType},
+    global::{
+        common::{
+            channel::{Channel, ChannelPlaylists, ChannelVideos},
+            hidden::{Caption, PlaylistItem, SearchItem, VideoShort},
+            universal::{Playlist, Suggestions, Trending},
+            video::Video,
+            CommonChannel, CommonImage, CommonPlaylist, CommonThumbnail, CommonVideo,
+        },
+        functions::{httpreq_get_timeout, viewcount_text},
+        structs::Errors,
+        traits::SearchProviderTrait,
+    },
+};
+
+/// Piped lists trending videos by region, and has no default
+const TRENDING_REGION: &str = "US";
+
+/// a Piped API instance, such as `piped_instance`
+#[derive(Clone)]
+pub struct PipedClient {
+    pub instance: String,
+    pub timeout: Duration,
+}
+
+impl PipedClient {
+    pub fn new(instance: String, timeout: Duration) -> Self {
+        Self {
+            instance: instance.trim_end_matches('/').to_string(),
+            timeout,
+        }
+    }
+
+    fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Box<dyn Error>> {
+        let (status, body) =
+            httpreq_get_timeout(&format!("{}{path}", self.instance), self.timeout)?;
+
+        if !(200..300).contains(&status) {
+            // Piped puts a short reason in `message`, and a stack trace in `error`
+            let message = serde_json::from_slice::<Value>(&body)
+                .ok()
+                .and_then(|value| {
+                    value
+                        .get("message")
+                        .or(value.get("error"))
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                })
+                .unwrap_or(format!("HTTP {status}"));
+            return Err(Errors::StringError(format!("Piped: {message}")).into());
+        }
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// items of a channel tab, such as `playlists` or `shorts`
+    ///
+    /// the continuation is the tab data and the next page token, on separate lines
+    fn channel_tab(
+        &self,
+        id: &str,
+        name: &str,
+        continuation: Option<&str>,
+    ) -> Result<(Vec<PipedItem>, Option<String>), Box<dyn Error>> {
+        let (data, path) = match continuation.and_then(|continuation| continuation.split_once('\n'))
+        {
+            Some((data, nextpage)) => (
+                data.to_string(),
+                format!(
+                    "/channels/tabs?data={}&nextpage={}",
+                    encode(data),
+                    encode(nextpage)
+                ),
+            ),
+            None => {
+                let res: PipedChannel = self.get(&format!("/channel/{}", encode(id)))?;
+                // channels without any items of this kind do not have the tab
+                let Some(tab) = res.tabs.into_iter().find(|tab| tab.name == name) else {
+                    return Ok((Vec::new(), None));
+                };
+                let path = format!("/channels/tabs?data={}", encode(&tab.data));
+                (tab.data, path)
+            }
+        };
+
+        let res: PipedTabContent = self.get(&path)?;
+        Ok((
+            res.content,
+            res.nextpage.map(|nextpage| format!("{data}\n{nextpage}")),
+        ))
+    }
+}
+
+// Piped responses, only the fields we use
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum PipedItem {
+    Stream(PipedStreamItem),
+    Channel(PipedChannelItem),
+    Playlist(PipedPlaylistItem),
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PipedStreamItem {
+    url: String,
+    title: String,
+    thumbnail: String,
+    uploader_name: Option<String>,
+    uploader_url: Option<String>,
+    uploaded_date: Option<String>,
+    short_description: Option<String>,
+    /// -1 for live streams
+    duration: i64,
+    views: i64,
+    /// milliseconds since epoch, -1 if unknown
+    uploaded: i64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PipedChannelItem {
+    url: String,
+    name: String,
+    thumbnail: String,
+    description: Option<String>,
+    subscribers: i64,
+    videos: i64,
+    verified: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PipedPlaylistItem {
+    url: String,
+    name: String,
+    thumbnail: String,
+    uploader_name: Option<String>,
+    uploader_url: Option<String>,
+    uploader_verified: bool,
+    videos: i64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PipedSearch {
+    items: Vec<PipedItem>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PipedSubtitle {
+    url: String,
+    name: String,
+    code: String,
+    auto_generated: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PipedStreams {
+    title: String,
+    description: String,
+    upload_date: String,
+    uploader: String,
+    uploader_url: String,
+    uploader_avatar: Option<String>,
+    thumbnail_url: String,
+    category: Option<String>,
+    tags: Vec<String>,
+    duration: i64,
+    views: i64,
+    likes: i64,
+    dislikes: i64,
+    uploader_subscriber_count: i64,
+    livestream: bool,
+    dash: Option<String>,
+    subtitles: Vec<PipedSubtitle>,
+    related_streams: Vec<PipedItem>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PipedTab {
+    name: String,
+    data: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PipedChannel {
+    id: String,
+    name: String,
+    avatar_url: Option<String>,
+    banner_url: Option<String>,
+    description: Option<String>,
+    subscriber_count: i64,
+    related_streams: Vec<PipedItem>,
+    tabs: Vec<PipedTab>,
+    nextpage: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PipedTabContent {
+    content: Vec<PipedItem>,
+    nextpage: Option<String>,
+}
+
+/// later pages of channel videos and playlists
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PipedNextpage {
+    related_streams: Vec<PipedItem>,
+    nextpage: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct PipedPlaylist {
+    name: String,
+    thumbnail_url: String,
+    description: Option<String>,
+    uploader: Option<String>,
+    uploader_url: Option<String>,
+    uploader_avatar: Option<String>,
+    videos: i64,
+    related_streams: Vec<PipedItem>,
+    nextpage: Option<String>,
+}
+
+// conversions
+
+/// the id in `/watch?v={id}`, `/channel/{id}` or `/playlist?list={id}`
+fn id_from_url(url: &str) -> String {
+    url.rsplit(['/', '='])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn channel_url(url: &str) -> String {
+    format!("https://www.youtube.com/channel/{}", id_from_url(url))
+}
+
+/// Piped only gives one thumbnail, without its size
+fn thumbnail(url: String) -> Vec<CommonThumbnail> {
+    if url.is_empty() {
+        return Vec::new();
+    }
+
+    vec![CommonThumbnail {
+        quality: "unknown".to_string(),
+        url,
+        width: 0,
+        height: 0,
+    }]
+}
+
+fn image(url: Option<String>) -> Vec<CommonImage> {
+    url.filter(|url| !url.is_empty())
+        .map(|url| CommonImage {
+            url,
+            width: 0,
+            height: 0,
+        })
+        .into_iter()
+        .collect()
+}
+
+/// descriptions are html, with `<br>` for line breaks
+fn plaintext(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for (i, c) in html.char_indices() {
+        match c {
+            '<' => {
+                in_tag = true;
+                if html[i..].starts_with("<br") {
+                    text.push('\n');
+                }
+            }
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+}
+
+/// `2009-10-24` or `2009-10-24T23:57:33.000Z` into a timestamp
+fn date_timestamp(date: &str) -> u64 {
+    date.get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc().timestamp().max(0) as u64)
+        .unwrap_or_default()
+}
+
+fn video_convert(video: PipedStreamItem) -> CommonVideo {
+    let description = video.short_description.unwrap_or_default();
+    CommonVideo {
+        id: id_from_url(&video.url),
+        title: video.title,
+        author: video.uploader_name.unwrap_or("Unknown channel".to_string()),
+        author_id: video
+            .uploader_url
+            .as_deref()
+            .map(id_from_url)
+            .unwrap_or("Unknown channel id".to_string()),
+        author_url: video
+            .uploader_url
+            .as_deref()
+            .map(channel_url)
+            .unwrap_or("Unknown channel id".to_string()),
+        thumbnails: thumbnail(video.thumbnail),
+        description_html: description.clone(),
+        description,
+        views: video.views.max(0) as u64,
+        length: video.duration.max(0) as u32,
+        published: (video.uploaded.max(0) / 1000) as u64,
+        published_text: video
+            .uploaded_date
+            .unwrap_or("Unknown publish date".to_string()),
+        premiere_timestamp: 0,
+        live: video.duration == -1,
+        premium: false,
+        upcoming: false,
+    }
+}
+
+fn channel_convert(channel: PipedChannelItem) -> CommonChannel {
+    let description = channel.description.unwrap_or_default();
+    CommonChannel {
+        id: id_from_url(&channel.url),
+        url: channel_url(&channel.url),
+        name: channel.name,
+        verified: channel.verified,
+        thumbnails: image(Some(channel.thumbnail)),
+        auto_generated: false,
+        subscribers: channel.subscribers.max(0) as u32,
+        video_count: channel.videos.max(0) as u32,
+        description_html: description.clone(),
+        description,
+    }
+}
+
+fn playlist_convert(playlist: PipedPlaylistItem) -> CommonPlaylist {
+    CommonPlaylist {
+        id: id_from_url(&playlist.url),
+        title: playlist.name,
+        thumbnail: playlist.thumbnail,
+        author: playlist
+            .uploader_name
+            .unwrap_or("Unknown channel".to_string()),
+        author_id: playlist
+            .uploader_url
+            .as_deref()
+            .map(id_from_url)
+            .unwrap_or("Unknown channel id".to_string()),
+        author_verified: playlist.uploader_verified,
+        video_count: playlist.videos.max(0) as i32,
+        videos: Vec::new(),
+    }
+}
+
+fn playlist_videos(items: Vec<PipedItem>) -> Vec<PlaylistItem> {
+    videos(items)
+        .into_iter()
+        .enumerate()
+        .map(|(index, video)| PlaylistItem {
+            title: video.title,
+            id: video.id,
+            author: video.author,
+            author_id: video.author_id,
+            author_url: video.author_url,
+            thumbnails: video.thumbnails,
+            index: index as u32,
+            length: video.length,
+        })
+        .collect()
+}
+
+fn videos(items: Vec<PipedItem>) -> Vec<CommonVideo> {
+    items
+        .into_iter()
+        .filter_map(|item| match item {
+            PipedItem::Stream(video) => Some(video_convert(video)),
+            _ => None,
+        })
+        .collect()
+}
+
+impl SearchProviderTrait for PipedClient {
+    fn supports_video(&self) -> bool {
+        true
+    }
+
+    fn video(&self, id: &str) -> Result<Video, Box<dyn Error>> {
+        let res: PipedStreams = self.get(&format!("/streams/{}", encode(id)))?;
+
+        Ok(Video {
+            r#type: "video".to_string(),
+            title: res.title,
+            id: id.to_string(),
+            thumbnails: thumbnail(res.thumbnail_url),
+            storyboards: Vec::new(),
+            description: plaintext(&res.description),
+            description_html: res.description,
+            published: date_timestamp(&res.upload_date),
+            published_text: res.upload_date.get(..10).unwrap_or_default().to_string(),
+            keywords: res.tags,
+            views: res.views.max(0) as u64,
+            likes: res.likes.max(0) as u32,
+            dislikes: res.dislikes.max(0) as u32,
+            paid: false,
+            premium: false,
+            family_friendly: false,
+            allowed_regions: Vec::new(),
+            genre: res.category.unwrap_or("Unknown genre".to_string()),
+            genre_url: None,
+            author: res.uploader,
+            author_id: id_from_url(&res.uploader_url),
+            author_url: channel_url(&res.uploader_url),
+            author_thumbnails: image(res.uploader_avatar),
+            sub_count_text: viewcount_text(res.uploader_subscriber_count.max(0) as u64),
+            length: res.duration.max(0) as u32,
+            allow_ratings: true,
+            rating: 0_f32,
+            listed: true,
+            live: res.livestream,
+            upcoming: false,
+            premiere_timestamp: 0,
+            dash: res.dash.unwrap_or("No dash".to_string()),
+            adaptive_formats: Vec::new(),
+            format_streams: Vec::new(),
+            captions: res
+                .subtitles
+                .into_iter()
+                .map(|subtitle| Caption {
+                    label: if subtitle.auto_generated {
+                        format!("{} (auto-generated)", subtitle.name)
+                    } else {
+                        subtitle.name
+                    },
+                    language: subtitle.code,
+                    url: subtitle.url,
+                })
+                .collect(),
+            recommended_videos: videos(res.related_streams)
+                .into_iter()
+                .map(|video| VideoShort {
+                    id: video.id,
+                    title: video.title,
+                    thumbnails: video.thumbnails,
+                    author: video.author,
+                    author_id: video.author_id,
+                    length: video.length,
+                    views_text: viewcount_text(video.views),
+                })
+                .collect(),
+        })
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    /// Piped only filters by type, other filters are ignored
+    fn search(&self, filters: &Search) -> Result<Vec<SearchItem>, Box<dyn Error>> {
+        let filter = match filters.filters.r#type {
+            SearchFilterType::All => "all",
+            SearchFilterType::Video => "videos",
+            SearchFilterType::Playlist => "playlists",
+            SearchFilterType::Channel => "channels",
+        };
+
+        let res: PipedSearch = self.get(&format!(
+            "/search?q={}&filter={filter}",
+            encode(&filters.query)
+        ))?;
+
+        Ok(res
+            .items
+            .into_iter()
+            .filter_map(|item| match item {
+                PipedItem::Stream(video) => Some(SearchItem::Video(video_convert(video))),
+                PipedItem::Channel(channel) => Some(SearchItem::Channel(channel_convert(channel))),
+                PipedItem::Playlist(playlist) => {
+                    Some(SearchItem::Playlist(playlist_convert(playlist)))
+                }
+                PipedItem::Unknown => None,
+            })
+            .collect())
+    }
+
+    fn supports_suggestions(&self) -> bool {
+        true
+    }
+
+    fn suggestions(&self, query: &str) -> Result<Suggestions, Box<dyn Error>> {
+        Ok(Suggestions {
+            query: query.to_string(),
+            suggestions: self.get(&format!("/suggestions?query={}", encode(query)))?,
+        })
+    }
+
+    fn supports_trending(&self) -> bool {
+        true
+    }
+
+    fn trending(&self, continuation: Option<&str>) -> Result<Trending, Box<dyn Error>> {
+        // trending is a single page
+        if continuation.is_some() {
+            return Ok(Trending::default());
+        }
+
+        Ok(Trending {
+            videos: videos(self.get(&format!("/trending?region={TRENDING_REGION}"))?),
+            continuation: None,
+        })
+    }
+
+    fn supports_channel(&self) -> bool {
+        true
+    }
+
+    fn channel(&self, id: &str) -> Result<Channel, Box<dyn Error>> {
+        let res: PipedChannel = self.get(&format!("/channel/{}", encode(id)))?;
+        let description = res.description.unwrap_or_default();
+
+        Ok(Channel {
+            url: format!("https://www.youtube.com/channel/{}", res.id),
+            id: res.id,
+            name: res.name,
+            banners: image(res.banner_url),
+            thumbnails: image(res.avatar_url),
+            subscribers: res.subscriber_count.max(0) as u32,
+            total_views: 0,
+            joined: 0,
+            auto_generated: false,
+            family_friendly: false,
+            description_html: description.clone(),
+            description,
+            allowed_regions: Vec::new(),
+            latest_videos: videos(res.related_streams),
+            related_channels: Vec::new(),
+        })
+    }
+
+    fn supports_channel_videos(&self) -> bool {
+        true
+    }
+
+    fn channel_videos(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        let (related_streams, nextpage) = match continuation {
+            Some(nextpage) => {
+                let res: PipedNextpage = self.get(&format!(
+                    "/nextpage/channel/{}?nextpage={}",
+                    encode(id),
+                    encode(nextpage)
+                ))?;
+                (res.related_streams, res.nextpage)
+            }
+            None => {
+                let res: PipedChannel = self.get(&format!("/channel/{}", encode(id)))?;
+                (res.related_streams, res.nextpage)
+            }
+        };
+
+        Ok(ChannelVideos {
+            videos: videos(related_streams),
+            continuation: nextpage,
+        })
+    }
+
+    fn supports_channel_playlists(&self) -> bool {
+        true
+    }
+
+    fn channel_playlists(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelPlaylists, Box<dyn Error>> {
+        let (items, continuation) = self.channel_tab(id, "playlists", continuation)?;
+        Ok(ChannelPlaylists {
+            playlists: items
+                .into_iter()
+                .filter_map(|item| match item {
+                    PipedItem::Playlist(playlist) => Some(playlist_convert(playlist)),
+                    _ => None,
+                })
+                .collect(),
+            continuation,
+        })
+    }
+
+    fn supports_channel_shorts(&self) -> bool {
+        true
+    }
+
+    fn channel_shorts(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        let (items, continuation) = self.channel_tab(id, "shorts", continuation)?;
+        Ok(ChannelVideos {
+            videos: videos(items),
+            continuation,
+        })
+    }
+
+    fn supports_channel_streams(&self) -> bool {
+        true
+    }
+
+    fn channel_streams(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        let (items, continuation) = self.channel_tab(id, "livestreams", continuation)?;
+        Ok(ChannelVideos {
+            videos: videos(items),
+            continuation,
+        })
+    }
+
+    fn supports_playlist(&self) -> bool {
+        true
+    }
+
+    fn playlist(&self, id: &str, continuation: Option<&str>) -> Result<Playlist, Box<dyn Error>> {
+        if let Some(nextpage) = continuation {
+            let res: PipedNextpage = self.get(&format!(
+                "/nextpage/playlists/{}?nextpage={}",
+                encode(id),
+                encode(nextpage)
+            ))?;
+            return Ok(Playlist {
+                id: id.to_string(),
+                videos: playlist_videos(res.related_streams),
+                continuation: res.nextpage,
+                ..Default::default()
+            });
+        }
+
+        let res: PipedPlaylist = self.get(&format!("/playlists/{}", encode(id)))?;
+        let description = res.description.unwrap_or_default();
+        let author = res.uploader.unwrap_or("Unknown channel".to_string());
+        let author_id = res
+            .uploader_url
+            .as_deref()
+            .map(id_from_url)
+            .unwrap_or("Unknown channel id".to_string());
+
+        Ok(Playlist {
+            title: res.name,
+            id: id.to_string(),
+            thumbnail: res.thumbnail_url,
+            author,
+            author_id,
+            author_thumbnails: image(res.uploader_avatar),
+            description: plaintext(&description),
+            description_html: description,
+            video_count: res.videos.max(0) as u32,
+            views: 0,
+            updated: 0,
+            listed: true,
+            videos: playlist_videos(res.related_streams),
+            continuation: res.nextpage,
+        })
+    }
+}
@@ -1,19 +1,30 @@
 use crate::global::common::{
-    channel::Channel, hidden::SearchItem, universal::Playlist, video::Video, CommonChannel,
-    CommonImage, CommonPlaylist, CommonThumbnail, CommonVideo,
+    channel::{Channel, ChannelPlaylists, ChannelSearch, ChannelVideos},
+    hidden::{Caption, CreatorHeart, Replies, SearchItem, VideoShort},
+    universal::{Playlist, Suggestions, Trending},
+    video::{Comments, Video},
+    CommonChannel, CommonImage, CommonPlaylist, CommonThumbnail, CommonVideo,
 };
 use rustypipe::{
     client::RustyPipe,
     model::{
+        paginator::ContinuationEndpoint,
         richtext::{ToHtml, ToPlaintext},
-        ChannelItem, PlaylistItem, Thumbnail, VideoItem, YouTubeItem,
+        ChannelItem, Comment, PlaylistItem, Subtitle, Thumbnail, VideoItem, YouTubeItem,
+    },
+    param::{
+        search_filter::{ItemType, Length, Order, SearchFilter, UploadDate},
+        ChannelVideoTab,
     },
-    param::search_filter::{ItemType, Length, Order, SearchFilter, UploadDate},
 };
 
 use crate::{
     config::{SearchFilterDate, SearchFilterDuration, SearchFilterSort, SearchFilterType},
-    global::{functions::viewcount_text, traits::SearchProviderTrait},
+    global::{
+        functions::{httpreq_get, viewcount_text},
+        structs::Transcript,
+        traits::SearchProviderTrait,
+    },
     RUNTIME,
 };
 
@@ -31,6 +42,40 @@ impl Default for RustyPipeWrapper {
     }
 }
 
+impl RustyPipeWrapper {
+    /// videos, shorts or live streams of a channel
+    fn videos_tab(
+        &self,
+        id: &str,
+        tab: ChannelVideoTab,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn std::error::Error>> {
+        let query = self.0.query();
+        let res = match continuation {
+            Some(ctoken) => RUNTIME
+                .get()
+                .unwrap()
+                .block_on(query.continuation::<VideoItem, _>(
+                    ctoken,
+                    ContinuationEndpoint::Browse,
+                    None,
+                ))?,
+            None => {
+                RUNTIME
+                    .get()
+                    .unwrap()
+                    .block_on(query.channel_videos_tab(id, tab))?
+                    .content
+            }
+        };
+
+        Ok(ChannelVideos {
+            videos: res.items.into_iter().map(video_item_convert).collect(),
+            continuation: res.ctoken,
+        })
+    }
+}
+
 fn thumbnail_convert(thumb: Thumbnail) -> CommonThumbnail {
     CommonThumbnail {
         quality: "unknown".to_string(),
@@ -48,6 +93,18 @@ fn image_convert(thumb: Thumbnail) -> CommonImage {
     }
 }
 
+fn caption_convert(subtitle: Subtitle) -> Caption {
+    Caption {
+        label: if subtitle.auto_generated {
+            format!("{} (auto-generated)", subtitle.lang_name)
+        } else {
+            subtitle.lang_name
+        },
+        language: subtitle.lang,
+        url: subtitle.url,
+    }
+}
+
 fn video_item_convert(video: VideoItem) -> CommonVideo {
     crate::global::common::CommonVideo {
         title: video.name,
@@ -85,6 +142,22 @@ fn video_item_convert(video: VideoItem) -> CommonVideo {
     }
 }
 
+fn video_short_convert(video: VideoItem) -> VideoShort {
+    VideoShort {
+        id: video.id,
+        title: video.name,
+        thumbnails: video.thumbnail.into_iter().map(thumbnail_convert).collect(),
+        author: video
+            .channel
+            .clone()
+            .map(|c| c.name)
+            .unwrap_or("Unknown channel".to_string()),
+        author_id: video.channel.map(|c| c.id).unwrap_or_default(),
+        length: video.duration.unwrap_or_default(),
+        views_text: viewcount_text(video.view_count.unwrap_or_default()),
+    }
+}
+
 fn playlist_item_convert(playlist: PlaylistItem) -> CommonPlaylist {
     crate::global::common::CommonPlaylist {
         title: playlist.name,
@@ -128,6 +201,58 @@ fn channel_item_convert(channel: ChannelItem) -> CommonChannel {
     }
 }
 
+fn comment_convert(comment: Comment) -> crate::global::common::hidden::Comment {
+    crate::global::common::hidden::Comment {
+        verified: comment
+            .author
+            .as_ref()
+            .map(|c| c.verification.verified())
+            .unwrap_or_default(),
+        id: comment.id,
+        likes: comment.like_count.unwrap_or_default(),
+        edited: false,
+        content: comment.text.to_plaintext(),
+        content_html: comment.text.to_html(),
+        published: comment
+            .publish_date
+            .map(|t| t.unix_timestamp() as u64)
+            .unwrap_or_default(),
+        published_text: comment.publish_date_txt,
+        author: comment
+            .author
+            .clone()
+            .map(|c| c.name)
+            .unwrap_or("Unknown author".to_string()),
+        author_thumbnails: comment
+            .author
+            .clone()
+            .map(|c| c.avatar.into_iter().map(image_convert).collect())
+            .unwrap_or_default(),
+        author_id: comment
+            .author
+            .clone()
+            .map(|c| c.id)
+            .unwrap_or("Unknown channel id".to_string()),
+        author_url: comment
+            .author
+            .map(|c| format!("https://www.youtube.com/channel/{}", c.id))
+            .unwrap_or("Unknown channel id".to_string()),
+        channel_owner: comment.by_owner,
+        // rustypipe does not tell who gave the heart
+        heart: comment.hearted.then(|| CreatorHeart {
+            thumbnail: String::new(),
+            name: String::from("Creator"),
+        }),
+        replies: match comment.replies.ctoken {
+            Some(continuation) if comment.reply_count != 0 => Some(Replies {
+                replies: comment.reply_count,
+                continuation,
+            }),
+            _ => None,
+        },
+    }
+}
+
 impl SearchProviderTrait for RustyPipeWrapper {
     fn supports_video(&self) -> bool {
         true
@@ -199,10 +324,15 @@ impl SearchProviderTrait for RustyPipeWrapper {
             upcoming: false,
             premiere_timestamp: 0,
             dash: "No dash".to_string(),
-            adaptive_formats: Vec::new(),   // TODO
-            format_streams: Vec::new(),     // TODO
-            captions: Vec::new(),           // TODO
-            recommended_videos: Vec::new(), // TODO
+            adaptive_formats: Vec::new(), // TODO
+            format_streams: Vec::new(),   // TODO
+            captions: player.subtitles.into_iter().map(caption_convert).collect(),
+            recommended_videos: details
+                .recommended
+                .items
+                .into_iter()
+                .map(video_short_convert)
+                .collect(),
         })
     }
 
@@ -269,6 +399,20 @@ impl SearchProviderTrait for RustyPipeWrapper {
             .collect())
     }
 
+    fn supports_suggestions(&self) -> bool {
+        true
+    }
+
+    fn suggestions(&self, query: &str) -> Result<Suggestions, Box<dyn std::error::Error>> {
+        Ok(Suggestions {
+            query: query.to_string(),
+            suggestions: RUNTIME
+                .get()
+                .unwrap()
+                .block_on(self.0.query().search_suggestion(query))?,
+        })
+    }
+
     fn supports_channel(&self) -> bool {
         true
     }
@@ -312,12 +456,18 @@ impl SearchProviderTrait for RustyPipeWrapper {
         true
     }
 
-    fn trending(
-        &self,
-    ) -> Result<Vec<crate::global::common::CommonVideo>, Box<dyn std::error::Error>> {
+    fn trending(&self, continuation: Option<&str>) -> Result<Trending, Box<dyn std::error::Error>> {
+        // trending is a single page
+        if continuation.is_some() {
+            return Ok(Trending::default());
+        }
+
         let res = RUNTIME.get().unwrap().block_on(self.0.query().trending())?;
 
-        Ok(res.into_iter().map(video_item_convert).collect())
+        Ok(Trending {
+            videos: res.into_iter().map(video_item_convert).collect(),
+            continuation: None,
+        })
     }
 
     fn supports_playlist(&self) -> bool {
@@ -327,7 +477,43 @@ impl SearchProviderTrait for RustyPipeWrapper {
     fn playlist(
         &self,
         id: &str,
+        continuation: Option<&str>,
     ) -> Result<crate::global::common::universal::Playlist, Box<dyn std::error::Error>> {
+        if let Some(ctoken) = continuation {
+            let res =
+                RUNTIME
+                    .get()
+                    .unwrap()
+                    .block_on(self.0.query().continuation::<VideoItem, _>(
+                        ctoken,
+                        ContinuationEndpoint::Browse,
+                        None,
+                    ))?;
+
+            return Ok(Playlist {
+                id: id.to_string(),
+                videos: res
+                    .items
+                    .into_iter()
+                    .map(|item| {
+                        let video = video_item_convert(item);
+                        crate::global::common::hidden::PlaylistItem {
+                            title: video.title,
+                            id: video.id,
+                            author: video.author,
+                            author_id: video.author_id,
+                            author_url: video.author_url,
+                            thumbnails: video.thumbnails,
+                            index: 0,
+                            length: video.length,
+                        }
+                    })
+                    .collect(),
+                continuation: res.ctoken,
+                ..Default::default()
+            });
+        }
+
         let res = RUNTIME
             .get()
             .unwrap()
@@ -388,6 +574,7 @@ impl SearchProviderTrait for RustyPipeWrapper {
                     length: res.video_count as u32,
                 })
                 .collect(),
+            continuation: res.videos.ctoken,
         })
     }
 
@@ -395,18 +582,60 @@ impl SearchProviderTrait for RustyPipeWrapper {
         true
     }
 
-    fn channel_videos(&self, id: &str) -> Result<Vec<CommonVideo>, Box<dyn std::error::Error>> {
+    fn channel_videos(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn std::error::Error>> {
+        self.videos_tab(id, ChannelVideoTab::Videos, continuation)
+    }
+
+    fn supports_channel_shorts(&self) -> bool {
+        true
+    }
+
+    fn channel_shorts(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn std::error::Error>> {
+        self.videos_tab(id, ChannelVideoTab::Shorts, continuation)
+    }
+
+    fn supports_channel_streams(&self) -> bool {
+        true
+    }
+
+    fn channel_streams(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn std::error::Error>> {
+        self.videos_tab(id, ChannelVideoTab::Live, continuation)
+    }
+
+    fn supports_channel_search(&self) -> bool {
+        true
+    }
+
+    fn channel_search(
+        &self,
+        id: &str,
+        query: &str,
+    ) -> Result<ChannelSearch, Box<dyn std::error::Error>> {
         let res = RUNTIME
             .get()
             .unwrap()
-            .block_on(self.0.query().channel_videos(id))?;
+            .block_on(self.0.query().channel_search(id, query))?;
 
-        Ok(res
-            .content
-            .items
-            .into_iter()
-            .map(video_item_convert)
-            .collect())
+        Ok(ChannelSearch {
+            items: res
+                .content
+                .items
+                .into_iter()
+                .map(|video| SearchItem::Video(video_item_convert(video)))
+                .collect(),
+        })
     }
 
     fn supports_channel_playlists(&self) -> bool {
@@ -416,17 +645,92 @@ impl SearchProviderTrait for RustyPipeWrapper {
     fn channel_playlists(
         &self,
         id: &str,
-    ) -> Result<Vec<CommonPlaylist>, Box<dyn std::error::Error>> {
-        let res = RUNTIME
-            .get()
-            .unwrap()
-            .block_on(self.0.query().channel_playlists(id))?;
+        continuation: Option<&str>,
+    ) -> Result<ChannelPlaylists, Box<dyn std::error::Error>> {
+        let query = self.0.query();
+        let res = match continuation {
+            Some(ctoken) => {
+                RUNTIME
+                    .get()
+                    .unwrap()
+                    .block_on(query.continuation::<PlaylistItem, _>(
+                        ctoken,
+                        ContinuationEndpoint::Browse,
+                        None,
+                    ))?
+            }
+            None => {
+                RUNTIME
+                    .get()
+                    .unwrap()
+                    .block_on(query.channel_playlists(id))?
+                    .content
+            }
+        };
 
-        Ok(res
-            .content
-            .items
-            .into_iter()
-            .map(playlist_item_convert)
-            .collect())
+        Ok(ChannelPlaylists {
+            playlists: res.items.into_iter().map(playlist_item_convert).collect(),
+            continuation: res.ctoken,
+        })
+    }
+
+    fn supports_comments(&self) -> bool {
+        true
+    }
+
+    fn comments(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<Comments, Box<dyn std::error::Error>> {
+        let query = self.0.query();
+        let res = match continuation {
+            Some(ctoken) => RUNTIME
+                .get()
+                .unwrap()
+                .block_on(query.video_comments(ctoken, None))?,
+            None => {
+                // the first page of comments is not included in video details, only the token to it
+                let details = RUNTIME.get().unwrap().block_on(query.video_details(id))?;
+                match details.top_comments.ctoken.as_deref() {
+                    Some(ctoken) if details.top_comments.items.is_empty() => {
+                        let mut res = RUNTIME
+                            .get()
+                            .unwrap()
+                            .block_on(query.video_comments(ctoken, None))?;
+                        res.count = res.count.or(details.top_comments.count);
+                        res
+                    }
+                    _ => details.top_comments,
+                }
+            }
+        };
+
+        Ok(Comments {
+            comment_count: res.count.map(|count| count as u32),
+            id: id.to_string(),
+            comments: res.items.into_iter().map(comment_convert).collect(),
+            continuation: res.ctoken,
+        })
+    }
+
+    fn supports_captions(&self) -> bool {
+        true
+    }
+
+    fn captions(&self, id: &str) -> Result<Vec<Caption>, Box<dyn std::error::Error>> {
+        let player = RUNTIME.get().unwrap().block_on(self.0.query().player(id))?;
+        Ok(player.subtitles.into_iter().map(caption_convert).collect())
+    }
+
+    fn transcript(&self, caption: &Caption) -> Result<Transcript, Box<dyn std::error::Error>> {
+        // the default format is srv1, which does not include durations
+        let url = if caption.url.contains("fmt=") {
+            caption.url.clone()
+        } else {
+            format!("{}&fmt=srv3", caption.url)
+        };
+        let res = String::from_utf8(httpreq_get(&url)?)?;
+        Ok(Transcript::parse(&res, caption.language.clone())?)
     }
 }
@@ -0,0 +1,237 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{config::CacheConfig, global::functions::paths};
+
+/// total size of the responses in each cache directory, the directory is only read on the first
+/// write and when it grows past `max_size`
+static SIZES: Mutex<BTreeMap<PathBuf, u64>> = Mutex::new(BTreeMap::new());
+
+/// responses from the search provider saved in `~/.cache/youtube-tui/responses/`, one file per
+/// request, named `{operation}-{hash of the key}.json`
+#[derive(Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    config: CacheConfig,
+}
+
+/// a response read from the cache
+#[derive(Debug, PartialEq, Eq)]
+pub enum Cached<T> {
+    Fresh(T),
+    /// past its ttl, but can be shown while it is fetched again
+    Stale(T),
+    Missing,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    /// the full key, in case two keys have the same hash
+    key: String,
+    value: T,
+}
+
+/// numbers shown by `cache stats`
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct CacheStats {
+    pub responses: usize,
+    /// responses past their ttl
+    pub stale: usize,
+    pub bytes: u64,
+}
+
+impl Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{} cached responses ({:.1} MB), {} stale",
+            self.responses,
+            self.bytes as f64 / 1_000_000.,
+            self.stale
+        ))
+    }
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf, config: CacheConfig) -> Self {
+        Self { dir, config }
+    }
+
+    /// the cache in the cache directory
+    pub fn open(config: CacheConfig) -> Self {
+        Self::new(paths::cache_dir().join("responses"), config)
+    }
+
+    /// the key is hashed with 64 bit FNV-1a, which unlike `DefaultHasher` stays the same across
+    /// releases so files written by older versions are still found
+    fn path(&self, operation: &str, key: &str) -> PathBuf {
+        let hash = key.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        self.dir.join(format!("{operation}-{hash:016x}.json"))
+    }
+
+    /// seconds since the file was last written
+    fn age(path: &Path) -> Option<u64> {
+        let modified = fs::metadata(path).ok()?.modified().ok()?;
+        Some(
+            SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or(Duration::ZERO)
+                .as_secs(),
+        )
+    }
+
+    /// the operation a file is for, from its name
+    fn operation(path: &Path) -> Option<&str> {
+        Some(path.file_stem()?.to_str()?.rsplit_once('-')?.0)
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, operation: &str, key: &str) -> Cached<T> {
+        let ttl = self.config.ttl(operation);
+        if ttl == 0 {
+            return Cached::Missing;
+        }
+
+        let path = self.path(operation, key);
+        let Some(age) = Self::age(&path) else {
+            return Cached::Missing;
+        };
+        if age >= ttl + self.config.stale {
+            return Cached::Missing;
+        }
+
+        let Some(entry) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Entry<T>>(&content).ok())
+            .filter(|entry| entry.key == key)
+        else {
+            return Cached::Missing;
+        };
+
+        if age < ttl {
+            Cached::Fresh(entry.value)
+        } else {
+            Cached::Stale(entry.value)
+        }
+    }
+
+    /// saves a response if its operation is cached, then removes the oldest responses if the
+    /// cache is larger than `max_size`, which is checked with the size of the written responses
+    /// instead of reading the directory every time
+    pub fn put<T: Serialize>(
+        &self,
+        operation: &str,
+        key: &str,
+        value: &T,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.config.ttl(operation) == 0 {
+            return Ok(());
+        }
+
+        let path = self.path(operation, key);
+        let content = serde_json::to_string(&Entry {
+            key: key.to_string(),
+            value,
+        })?;
+        let replaced = fs::metadata(&path).map(|metadata| metadata.len()).ok();
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(&path, &content)?;
+
+        let mut sizes = SIZES.lock().unwrap_or_else(PoisonError::into_inner);
+        let total = match sizes.get(&self.dir) {
+            Some(total) => {
+                (total + content.len() as u64).saturating_sub(replaced.unwrap_or_default())
+            }
+            None => u64::MAX,
+        };
+        let total = if total > self.config.max_size * 1_000_000 {
+            self.trim()
+        } else {
+            total
+        };
+        sizes.insert(self.dir.clone(), total);
+        Ok(())
+    }
+
+    /// removes responses that are too old to be shown, then the oldest ones until the cache fits
+    /// in `max_size`, returns the size of what is left
+    fn trim(&self) -> u64 {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return 0;
+        };
+
+        let mut files = Vec::new();
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let (Some(age), Ok(metadata)) = (Self::age(&path), fs::metadata(&path)) else {
+                continue;
+            };
+            let ttl = Self::operation(&path)
+                .map(|operation| self.config.ttl(operation))
+                .unwrap_or_default();
+            if age >= ttl + self.config.stale {
+                let _ = fs::remove_file(&path);
+            } else {
+                files.push((age, metadata.len(), path));
+            }
+        }
+
+        let max = self.config.max_size * 1_000_000;
+        let mut total = files.iter().map(|(_, len, _)| len).sum::<u64>();
+        // oldest first
+        files.sort_by_key(|(age, _, _)| std::cmp::Reverse(*age));
+        for (_, len, path) in files {
+            if total <= max {
+                break;
+            }
+            if fs::remove_file(path).is_ok() {
+                total -= len;
+            }
+        }
+
+        total
+    }
+
+    /// removes every response, returns how many there were
+    pub fn clear(&self) -> Result<usize, Box<dyn Error>> {
+        if !self.dir.exists() {
+            return Ok(0);
+        }
+
+        let count = fs::read_dir(&self.dir)?.count();
+        fs::remove_dir_all(&self.dir)?;
+        if let Ok(mut sizes) = SIZES.lock() {
+            sizes.remove(&self.dir);
+        }
+        Ok(count)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let mut stats = CacheStats::default();
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return stats;
+        };
+
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let (Some(age), Ok(metadata)) = (Self::age(&path), fs::metadata(&path)) else {
+                continue;
+            };
+            stats.responses += 1;
+            stats.bytes += metadata.len();
+            if Self::operation(&path).is_none_or(|operation| age >= self.config.ttl(operation)) {
+                stats.stale += 1;
+            }
+        }
+
+        stats
+    }
+}
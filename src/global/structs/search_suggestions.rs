@@ -0,0 +1,112 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use typemap::Key;
+
+use crate::global::{common::universal::Suggestions, traits::SearchProviderWrapper};
+
+/// how long typing has to pause before suggestions are fetched
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// search suggestions from the search provider, fetched on a background thread so typing never
+/// waits for them
+#[derive(Clone)]
+pub struct SearchSuggestions {
+    requests: Sender<String>,
+    responses: Arc<Mutex<Receiver<Suggestions>>>,
+    /// the query suggestions were last requested for
+    requested: String,
+    /// the latest suggestions that arrived
+    pub suggestions: Suggestions,
+}
+
+impl Key for SearchSuggestions {
+    type Value = Self;
+}
+
+impl Default for SearchSuggestions {
+    fn default() -> Self {
+        let (requests, worker_requests) = mpsc::channel();
+        let (worker_responses, responses) = mpsc::channel();
+        thread::spawn(move || Self::worker(worker_requests, worker_responses));
+
+        Self {
+            requests,
+            responses: Arc::new(Mutex::new(responses)),
+            requested: String::new(),
+            suggestions: Suggestions::default(),
+        }
+    }
+}
+
+impl SearchSuggestions {
+    /// stops when `SearchSuggestions` is dropped
+    fn worker(requests: Receiver<String>, responses: Sender<Suggestions>) {
+        while let Ok(mut query) = requests.recv() {
+            // only the last query is fetched once typing pauses
+            loop {
+                match requests.recv_timeout(DEBOUNCE) {
+                    Ok(newer) => query = newer,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+
+            let suggestions = SearchProviderWrapper::suggestions(&query)
+                .map(|res| res.suggestions)
+                .unwrap_or_default();
+            if responses
+                .send(Suggestions {
+                    query,
+                    suggestions,
+                })
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    /// asks for suggestions of `query`, they are picked up by `receive()`
+    pub fn request(&mut self, query: &str) {
+        if self.requested == query {
+            return;
+        }
+
+        self.requested = query.to_string();
+        if query.trim().is_empty() {
+            // nothing is fetched, so there is nothing to wait for
+            self.suggestions = Suggestions {
+                query: self.requested.clone(),
+                suggestions: Vec::new(),
+            };
+            return;
+        }
+
+        let _ = self.requests.send(self.requested.clone());
+    }
+
+    /// if suggestions for the last request have not arrived yet
+    pub fn pending(&self) -> bool {
+        self.requested != self.suggestions.query
+    }
+
+    /// picks up suggestions that arrived, returns true if they are for the last request
+    pub fn receive(&mut self) -> bool {
+        let mut updated = false;
+        for suggestions in self.responses.lock().unwrap().try_iter() {
+            if suggestions.query == self.requested {
+                self.suggestions = suggestions;
+                updated = true;
+            }
+        }
+
+        updated
+    }
+}
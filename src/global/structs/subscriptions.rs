@@ -2,7 +2,7 @@ use super::*;
 use crate::{
     config::SyncConfig,
     global::{
-        functions::{download_all_images, DownloadRequest},
+        functions::{download_all_images, from_opml, to_opml, DownloadRequest},
         traits::{Collection, CollectionItem, SearchProviderWrapper},
     },
 };
@@ -13,6 +13,7 @@ use std::{
     fmt::{Display, Formatter},
     fs::{self, OpenOptions},
     io::Write,
+    path::Path,
     sync::{atomic::AtomicU32, mpsc, Arc},
     thread,
 };
@@ -86,7 +87,7 @@ impl Subscriptions {
                         failed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
                     }
                 }
-                tx.send(item)
+                let _ = tx.send(item);
             });
         });
 
@@ -174,6 +175,76 @@ impl Subscriptions {
     pub fn get_channels(&self) -> Vec<FullChannelItem> {
         self.0.iter().map(|item| item.channel.clone()).collect()
     }
+
+    /// adds channels from an OPML file, returns (added, already subscribed)
+    pub fn import_opml(&mut self, path: &Path) -> Result<(u32, u32), Box<dyn Error>> {
+        let channels = from_opml(&fs::read_to_string(path)?);
+        if channels.is_empty() {
+            return Err(Errors::StrError("no channels found in file").into());
+        }
+
+        let res = self.add_unsynced(channels);
+        self.save()?;
+
+        Ok(res)
+    }
+
+    /// adds channels from their (id, name), returns (added, already subscribed)
+    ///
+    /// channels are added without videos or channel info, which are fetched in the next sync
+    pub fn add_unsynced(&mut self, channels: Vec<(String, String)>) -> (u32, u32) {
+        let (mut added, mut existing) = (0, 0);
+        for (id, name) in channels {
+            if self.contains(&id) {
+                existing += 1;
+                continue;
+            }
+
+            self.0.push(SubItem {
+                channel: FullChannelItem {
+                    name,
+                    id,
+                    thumbnail_url: String::new(),
+                    sub_count: 0,
+                    sub_count_text: String::new(),
+                    total_views: String::new(),
+                    created: String::new(),
+                    autogenerated: false,
+                    description: String::new(),
+                },
+                videos: Vec::new(),
+                last_sync: 0,
+                last_sync_channel: 0,
+                has_new: false,
+            });
+            added += 1;
+        }
+
+        self.0.sort();
+        (added, existing)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.0.iter().any(|item| item.channel.id == id)
+    }
+
+    /// writes all subscribed channels to an OPML file, returns the number of channels written
+    pub fn export_opml(&self, path: &Path) -> Result<usize, Box<dyn Error>> {
+        let opml = to_opml(
+            self.0
+                .iter()
+                .map(|item| (item.channel.id.as_str(), item.channel.name.as_str())),
+        );
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.write_all(opml.as_bytes())?;
+
+        Ok(self.0.len())
+    }
 }
 
 fn sync_one(
@@ -187,14 +258,15 @@ fn sync_one(
     if sync_channel_info {
         let id2 = id.to_string();
         thread::spawn(move || {
-            tx.send(
+            let _ = tx.send(
                 SearchProviderWrapper::channel(&id2)
                     .map(|channel| Item::from_full_channel(channel, image_index).into_fullchannel())
                     .ok(),
-            )
+            );
         });
     }
-    let mut videos = SearchProviderWrapper::channel_videos(id)?
+    let mut videos = SearchProviderWrapper::channel_videos(id, None)?
+        .videos
         .into_iter()
         .map(|video| {
             Item::from_common_video(video, image_index)
@@ -1,12 +1,12 @@
 use crate::{
     config::*,
-    global::{functions::*, structs::*},
+    global::{functions::*, structs::*, traits::SearchProviderWrapper},
 };
 
 use ratatui::{
-    backend::CrosstermBackend, layout::Alignment, style::Style, widgets::Paragraph, Frame, Terminal,
+    backend::Backend, layout::Alignment, style::Style, widgets::Paragraph, Frame, Terminal,
 };
-use std::{error::Error, fmt::Debug, io::Stdout, mem, sync::Arc};
+use std::{error::Error, fmt::Debug, mem, sync::Arc};
 use tui_additions::framework::{CursorState, Framework};
 use typemap::Key;
 
@@ -17,10 +17,14 @@ pub enum Task {
     Reload,
     RenderOnly(usize, usize),
     LoadPage(Page),
+    /// loads the page once `PageLoader` has the responses for it
+    FinishLoad,
     ClearPage,
     LazyRendered,
     Command(String),
     Custom(TaskFunction),
+    #[cfg(feature = "mpv")]
+    MpvEvent(MpvEvent),
 }
 
 #[derive(Clone)]
@@ -53,15 +57,18 @@ impl TaskFunction {
 impl Eq for TaskFunction {}
 
 /// multiple tasks joined together, with duplicates removed
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct TaskQueue {
     pub render: RenderTask,
     pub reload: bool,
     pub load_page: Option<Page>,
+    pub finish_load: bool,
     pub clear_all: bool,
     pub lazy_rendered: bool,
     pub commands: Vec<String>,
     pub custom_functions: Vec<TaskFunction>,
+    #[cfg(feature = "mpv")]
+    pub mpv_events: Vec<MpvEvent>,
 }
 
 impl Default for TaskQueue {
@@ -70,10 +77,13 @@ impl Default for TaskQueue {
             render: RenderTask::None,
             reload: false,
             load_page: None,
+            finish_load: false,
             clear_all: false,
             lazy_rendered: false,
             commands: Vec::new(),
             custom_functions: Vec::new(),
+            #[cfg(feature = "mpv")]
+            mpv_events: Vec::new(),
         }
     }
 }
@@ -85,19 +95,20 @@ impl TaskQueue {
             Task::RenderAll => self.render = RenderTask::All,
             Task::Reload => self.reload = true,
             Task::RenderOnly(x, y) => match &mut self.render {
-                RenderTask::Only(renders) => {
-                    if !renders.contains(&(x, y)) {
-                        renders.push((x, y));
-                    }
+                RenderTask::Only(renders) if !renders.contains(&(x, y)) => {
+                    renders.push((x, y));
                 }
                 RenderTask::None => self.render = RenderTask::Only(vec![(x, y)]),
                 _ => {}
             },
             Task::LoadPage(page) => self.load_page = Some(page),
+            Task::FinishLoad => self.finish_load = true,
             Task::ClearPage => self.clear_all = true,
             Task::LazyRendered => self.lazy_rendered = true,
             Task::Command(s) => self.commands.push(s),
             Task::Custom(f) => self.custom_functions.push(f),
+            #[cfg(feature = "mpv")]
+            Task::MpvEvent(event) => self.mpv_events.push(event),
         }
     }
 
@@ -106,11 +117,21 @@ impl TaskQueue {
     }
 
     /// runs all tasks in a task queue
-    pub fn run(
+    pub fn run<B: Backend>(
         mut self,
         framework: &mut Framework,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-    ) -> Result<(), Box<dyn Error>> {
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>>
+    where
+        B::Error: 'static,
+    {
+        // mpv events are applied before anything else so commands and renders see the latest
+        // playback state
+        #[cfg(feature = "mpv")]
+        for event in mem::take(&mut self.mpv_events) {
+            self.apply_mpv_event(framework, event);
+        }
+
         // if there is any commands, run them first as they may modify data, which is rendered
         // later in this function
         for command in self.commands.iter() {
@@ -130,10 +151,31 @@ impl TaskQueue {
                 .render_image = false;
         }
 
+        if self.finish_load {
+            *framework.data.global.get_mut::<Message>().unwrap() = Self::load(framework);
+            SearchProviderWrapper::clear_prefetched();
+            let status = framework.data.global.get_mut::<Status>().unwrap();
+            status.provider_updated = true;
+            status.render_image = true;
+            self.render = RenderTask::All;
+        }
+
         // save state in history, then replace all items by whats in the new page and run `.load()` on them
         if let Some(page) = self.load_page {
+            let load_msg = page.load_msg(framework);
             *framework.data.global.get_mut::<Message>().unwrap() =
-                Message::Message(page.load_msg(framework));
+                Message::Message(load_msg.clone());
+            // a page that has not finished loading is not kept in history
+            if framework
+                .data
+                .global
+                .get_mut::<PageLoader>()
+                .unwrap()
+                .cancel()
+                .is_some_and(|load| load.command.is_some())
+            {
+                let _ = framework.revert_last_history();
+            }
             framework.push_history();
 
             // clear all envs modified by the previous state (keeping ones that are there when the
@@ -162,19 +204,28 @@ impl TaskQueue {
             framework.data.global.get_mut::<Status>().unwrap().reset();
             *framework.data.state.get_mut::<Page>().unwrap() = page;
             Self::render_force_clear(framework, terminal)?;
-            *framework.data.global.get_mut::<Message>().unwrap() = Message::None;
 
-            *framework.data.global.get_mut::<Message>().unwrap() = if let Err(e) = framework.load()
+            // provider calls are made in the background, the rest of the page is loaded by
+            // `Task::FinishLoad` once they are done
+            let _ = SearchProviderWrapper::take_fallback_notice();
+            let requests = page_config.prefetch(
+                framework.data.state.get::<Page>().unwrap(),
+                framework.data.global.get::<MainConfig>().unwrap(),
+            );
+            if !framework
+                .data
+                .global
+                .get_mut::<PageLoader>()
+                .unwrap()
+                .start(requests, load_msg, Some(page_config.command.clone()))
             {
-                Message::Error(e.to_string())
-            } else {
-                Message::None
-            };
-            let status = framework.data.global.get_mut::<Status>().unwrap();
-            status.provider_updated = true;
-            status.render_image = true;
-            self.render = RenderTask::All;
-            run_command(&page_config.command, framework, terminal);
+                *framework.data.global.get_mut::<Message>().unwrap() = Self::load(framework);
+                let status = framework.data.global.get_mut::<Status>().unwrap();
+                status.provider_updated = true;
+                status.render_image = true;
+                self.render = RenderTask::All;
+                run_command(&page_config.command, framework, terminal);
+            }
         }
 
         if self.reload {
@@ -194,20 +245,36 @@ impl TaskQueue {
 
             Self::render_force_clear(framework, terminal)?;
             // reload simply runs `.load()` on all items
+            let load_msg = String::from("Reloading page...");
             *framework.data.global.get_mut::<Message>().unwrap() =
-                Message::Message(String::from("Reloading page..."));
+                Message::Message(load_msg.clone());
             Self::render_force_clear(framework, terminal)?;
-            *framework.data.global.get_mut::<Message>().unwrap() = if let Err(e) = framework.load()
+
+            let _ = SearchProviderWrapper::take_fallback_notice();
+            let requests = framework
+                .data
+                .state
+                .get::<Page>()
+                .unwrap()
+                .to_page_config(framework)
+                .prefetch(
+                    framework.data.state.get::<Page>().unwrap(),
+                    framework.data.global.get::<MainConfig>().unwrap(),
+                );
+            if !framework
+                .data
+                .global
+                .get_mut::<PageLoader>()
+                .unwrap()
+                .start(requests, load_msg, None)
             {
-                Message::Error(e.to_string())
-            } else {
-                Message::None
-            };
-            self.render = RenderTask::All;
+                *framework.data.global.get_mut::<Message>().unwrap() = Self::load(framework);
+                self.render = RenderTask::All;
 
-            let status = framework.data.global.get_mut::<Status>().unwrap();
-            status.provider_updated = true;
-            status.render_image = true;
+                let status = framework.data.global.get_mut::<Status>().unwrap();
+                status.provider_updated = true;
+                status.render_image = true;
+            }
         }
 
         match self.render {
@@ -233,11 +300,95 @@ impl TaskQueue {
         Ok(())
     }
 
+    /// updates the playback state and watch position, and queues the tasks the event causes
+    #[cfg(feature = "mpv")]
+    fn apply_mpv_event(&mut self, framework: &mut Framework, event: MpvEvent) {
+        let state = framework.data.global.get::<MpvState>().unwrap();
+        let watching = state
+            .video_id
+            .clone()
+            .map(|id| (id, state.time_pos, state.duration));
+
+        // positions are recorded before the state is updated, as the end of file event clears it
+        if let Some((id, time_pos, duration)) = watching {
+            let positions = framework.data.global.get_mut::<WatchPositions>().unwrap();
+            match event {
+                MpvEvent::TimePos(secs) => positions.set(&id, secs, duration),
+                MpvEvent::EndFile { eof: true } => positions.set(&id, duration, duration),
+                MpvEvent::EndFile { eof: false } => positions.set(&id, time_pos, duration),
+                _ => {}
+            }
+
+            // mark as watched once enough of the video has been played
+            let threshold = framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .watched_threshold as f64;
+            let watched = match event {
+                MpvEvent::TimePos(secs) => duration > 0. && secs * 100. >= duration * threshold,
+                MpvEvent::EndFile { eof } => eof,
+                _ => false,
+            };
+            if watched {
+                framework
+                    .data
+                    .global
+                    .get_mut::<WatchedVideos>()
+                    .unwrap()
+                    .mark(&id);
+            }
+        }
+
+        let state = framework.data.global.get_mut::<MpvState>().unwrap();
+        state.update(&event);
+
+        match event {
+            // position updates are rendered by the main loop every tick
+            MpvEvent::TimePos(_) | MpvEvent::Duration(_) => {}
+            // continue from where the video was left off
+            MpvEvent::FileLoaded { .. } => {
+                if let Some(start) = state.video_id.clone().and_then(|id| {
+                    framework
+                        .data
+                        .global
+                        .get::<WatchPositions>()
+                        .unwrap()
+                        .start(&id)
+                }) {
+                    framework.data.global.get::<MpvWrapper>().unwrap().command(
+                        String::from("seek"),
+                        vec![start.to_string(), String::from("absolute")],
+                    );
+                }
+                self.render = RenderTask::All;
+            }
+            // play the next item in queue if the one started by the queue has ended
+            MpvEvent::EndFile { eof: true }
+                if framework
+                    .data
+                    .global
+                    .get::<PlayQueue>()
+                    .unwrap()
+                    .playing
+                    .is_some() =>
+            {
+                self.commands.push(String::from("queue next"));
+                self.render = RenderTask::All;
+            }
+            _ => self.render = RenderTask::All,
+        }
+    }
+
     /// the render task runs this function
-    pub fn render(
+    pub fn render<B: Backend>(
         framework: &mut Framework,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-    ) -> Result<(), Box<dyn Error>> {
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>>
+    where
+        B::Error: 'static,
+    {
         terminal.draw(|frame| {
             Self::render_with_frame(framework, frame);
         })?;
@@ -245,11 +396,36 @@ impl TaskQueue {
         Ok(())
     }
 
+    /// loads the current page, the message tells which provider answered if the first provider in
+    /// the chain did not, since the fallback notice was last taken
+    ///
+    /// filter rules skipped when the configs were loaded at startup are reported with the first
+    /// page, as nothing is shown before that
+    fn load(framework: &mut Framework) -> Message {
+        let skipped = framework
+            .data
+            .global
+            .get_mut::<Filters>()
+            .unwrap()
+            .take_skipped();
+
+        match (framework.load(), skipped) {
+            (Err(e), _) => Message::Error(e.to_string()),
+            (Ok(()), Some(skipped)) => Message::Error(skipped),
+            (Ok(()), None) => SearchProviderWrapper::take_fallback_notice()
+                .map(Message::Message)
+                .unwrap_or(Message::None),
+        }
+    }
+
     /// this function force clears the terminal before rendering, removing sixels and halfblock images
-    pub fn render_force_clear(
+    pub fn render_force_clear<B: Backend>(
         framework: &mut Framework,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-    ) -> Result<(), Box<dyn Error>> {
+        terminal: &mut Terminal<B>,
+    ) -> Result<(), Box<dyn Error>>
+    where
+        B::Error: 'static,
+    {
         terminal.clear()?;
         terminal.draw(|frame| {
             Self::render_with_frame(framework, frame);
@@ -258,11 +434,14 @@ impl TaskQueue {
         Ok(())
     }
 
-    pub fn render_onlys(
+    pub fn render_onlys<B: Backend>(
         framework: &mut Framework,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut Terminal<B>,
         locations: Vec<(usize, usize)>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), Box<dyn Error>>
+    where
+        B::Error: 'static,
+    {
         Self::render_onlys_with_frame(framework, &mut terminal.get_frame(), locations);
         terminal.flush()?;
         Ok(())
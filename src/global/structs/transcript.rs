@@ -0,0 +1,257 @@
+use std::{error::Error, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use typemap::Key;
+
+use crate::global::{functions::paths, structs::Errors};
+
+/// A single line of captions, times are in seconds
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Cue {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// A parsed caption track, regardless of the format it is downloaded in
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Transcript {
+    pub language: String,
+    pub cues: Vec<Cue>,
+}
+
+/// Search query for the transcript panel, stored in `Status.storage` by the `transcript search` command
+#[derive(Clone)]
+pub struct TranscriptSearch(pub String);
+
+impl Key for TranscriptSearch {
+    type Value = Self;
+}
+
+impl Transcript {
+    /// Detects whether the file is WebVTT or SRV3 (YouTube timedtext format 3), and parses it accordingly
+    pub fn parse(s: &str, language: String) -> Result<Self, Errors> {
+        let s = s.replace("\r\n", "\n");
+        let trimmed = s.trim_start_matches('\u{feff}').trim_start();
+
+        let cues = if trimmed.starts_with("WEBVTT") {
+            parse_webvtt(trimmed)
+        } else if trimmed.contains("<timedtext") {
+            parse_srv3(trimmed)
+        } else {
+            return Err(Errors::StrError("Unknown caption format"));
+        };
+
+        Ok(Self { language, cues })
+    }
+
+    /// Serialise to WebVTT, which can be loaded by mpv
+    pub fn to_webvtt(&self) -> String {
+        let mut out = format!("WEBVTT\nLanguage: {}\n", self.language);
+
+        for cue in self.cues.iter() {
+            out.push_str(&format!(
+                "\n{} --> {}\n{}\n",
+                vtt_timestamp(cue.start),
+                vtt_timestamp(cue.end),
+                cue.text
+            ));
+        }
+
+        out
+    }
+
+    /// Path to the cached caption file, `language` is the preferred language in `main.yml`
+    pub fn path(id: &str, language: &str) -> PathBuf {
+        paths::data_dir()
+            .join("captions")
+            .join(format!("{id}.{language}.vtt"))
+    }
+
+    /// Load a previously saved caption file
+    pub fn load(id: &str, language: &str) -> Option<Self> {
+        let content = fs::read_to_string(Self::path(id, language)).ok()?;
+        let language = content
+            .lines()
+            .take_while(|line| !line.is_empty())
+            .find_map(|line| line.strip_prefix("Language: "))
+            .unwrap_or(language)
+            .to_string();
+        Self::parse(&content, language).ok()
+    }
+
+    /// Save as WebVTT, returns the path it is saved to
+    pub fn save(&self, id: &str, language: &str) -> Result<PathBuf, Box<dyn Error>> {
+        let path = Self::path(id, language);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, self.to_webvtt())?;
+        Ok(path)
+    }
+
+    /// Indices of cues containing the query (case insensitive)
+    pub fn search(&self, query: &str) -> Vec<usize> {
+        let query = query.to_lowercase();
+        self.cues
+            .iter()
+            .enumerate()
+            .filter(|(_, cue)| cue.text.to_lowercase().contains(&query))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+fn parse_webvtt(s: &str) -> Vec<Cue> {
+    let mut cues: Vec<Cue> = Vec::new();
+    // auto generated captions repeat the previous line at the start of each cue
+    let mut previous_lines: Vec<String> = Vec::new();
+
+    for block in s.split("\n\n") {
+        let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+        let timing = match lines.next() {
+            Some(line) => line,
+            None => continue,
+        };
+
+        let (start, end) = match timing.split_once("-->") {
+            Some((start, end)) => (
+                parse_vtt_timestamp(start.trim()),
+                // anything after the end time are cue settings
+                parse_vtt_timestamp(end.split_whitespace().next().unwrap_or_default()),
+            ),
+            None => continue,
+        };
+        let (start, end) = match (start, end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => continue,
+        };
+
+        let current_lines = lines
+            .map(|line| decode_entities(&strip_tags(line)).trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let text = current_lines
+            .iter()
+            .filter(|line| !previous_lines.contains(line))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
+        previous_lines = current_lines;
+
+        if text.is_empty() {
+            continue;
+        }
+
+        cues.push(Cue { start, end, text });
+    }
+
+    cues
+}
+
+fn parse_srv3(s: &str) -> Vec<Cue> {
+    let mut cues = Vec::new();
+    let mut rest = s;
+
+    while let Some(index) = rest.find("<p ") {
+        rest = &rest[index..];
+        let tag_end = match rest.find('>') {
+            Some(index) => index,
+            None => break,
+        };
+        let attributes = &rest[3..tag_end];
+
+        // self closing tags contains no text
+        if attributes.ends_with('/') {
+            rest = &rest[tag_end..];
+            continue;
+        }
+
+        let content_end = match rest.find("</p>") {
+            Some(index) => index,
+            None => break,
+        };
+        let content = &rest[tag_end + 1..content_end];
+        rest = &rest[content_end..];
+
+        let start = match attribute(attributes, "t").and_then(|t| t.parse::<f64>().ok()) {
+            Some(start) => start / 1000.,
+            None => continue,
+        };
+        let duration = attribute(attributes, "d")
+            .and_then(|d| d.parse::<f64>().ok())
+            .unwrap_or_default()
+            / 1000.;
+        let text = decode_entities(&strip_tags(content))
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if text.is_empty() {
+            continue;
+        }
+
+        cues.push(Cue {
+            start,
+            end: start + duration,
+            text,
+        });
+    }
+
+    cues
+}
+
+/// value of `name="value"` in a list of xml attributes
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{name}=\"");
+    let start = attributes
+        .match_indices(&pattern)
+        .find(|(index, _)| *index == 0 || attributes[..*index].ends_with(char::is_whitespace))?
+        .0
+        + pattern.len();
+    let len = attributes[start..].find('"')?;
+    Some(&attributes[start..start + len])
+}
+
+/// `hh:mm:ss.mmm` or `mm:ss.mmm` to seconds
+fn parse_vtt_timestamp(s: &str) -> Option<f64> {
+    let mut secs = 0.;
+    for part in s.split(':') {
+        secs = secs * 60. + part.parse::<f64>().ok()?;
+    }
+    Some(secs)
+}
+
+fn vtt_timestamp(secs: f64) -> String {
+    let millis = (secs * 1000.).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+fn strip_tags(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_tag = false;
+
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+
+    out
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
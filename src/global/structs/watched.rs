@@ -0,0 +1,65 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs::{self, OpenOptions},
+    io::Write,
+};
+
+use serde::{Deserialize, Serialize};
+use typemap::Key;
+
+use crate::global::functions::paths;
+
+/// ids of videos marked as watched, either manually or by playing them to the end
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct WatchedVideos {
+    pub ids: HashSet<String>,
+    /// whether watched videos are hidden from video lists, starts as `hide_watched` in main config
+    #[serde(skip)]
+    pub hide: bool,
+}
+
+impl Key for WatchedVideos {
+    type Value = Self;
+}
+
+impl WatchedVideos {
+    const PATH: &'static str = "watched.json";
+
+    pub fn load() -> Self {
+        fs::read_to_string(paths::data_dir().join(Self::PATH))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(paths::data_dir().join(Self::PATH))?;
+
+        file.write_all(serde_json::to_string(self)?.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.contains(id)
+    }
+
+    /// returns false if the video is already marked as watched
+    pub fn mark(&mut self, id: &str) -> bool {
+        self.ids.insert(id.to_string())
+    }
+
+    /// returns false if the video is not marked as watched
+    pub fn unmark(&mut self, id: &str) -> bool {
+        self.ids.remove(id)
+    }
+
+    /// if the item should be left out of video lists
+    pub fn hidden(&self, id: &str) -> bool {
+        self.hide && self.contains(id)
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+use typemap::Key;
+
+use crate::global::{
+    functions::{path_video_id, paths},
+    structs::LocalStore,
+};
+
+/// playback position of a video when it was last played, in seconds
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct WatchPosition {
+    pub position: f64,
+    pub duration: f64,
+}
+
+impl WatchPosition {
+    pub fn percentage(&self) -> u8 {
+        (self.position * 100. / self.duration).clamp(0., 100.) as u8
+    }
+
+    /// close enough to the end that playing it again should start from the beginning
+    pub fn finished(&self) -> bool {
+        self.position >= self.duration * 0.95
+    }
+}
+
+/// last watch position of each video by id, saved next to `watch_history.json`
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct WatchPositions(pub HashMap<String, WatchPosition>);
+
+impl Key for WatchPositions {
+    type Value = Self;
+}
+
+impl WatchPositions {
+    const PATH: &'static str = "watch_positions.json";
+
+    pub fn load() -> Self {
+        fs::read_to_string(paths::data_dir().join(Self::PATH))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(paths::data_dir().join(Self::PATH))?;
+
+        file.write_all(serde_json::to_string(self)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// record the position of a video, live streams (with no duration) are ignored
+    pub fn set(&mut self, id: &str, position: f64, duration: f64) {
+        if duration <= 0. {
+            return;
+        }
+
+        self.0
+            .insert(id.to_string(), WatchPosition { position, duration });
+    }
+
+    pub fn percentage(&self, id: &str) -> Option<u8> {
+        self.0.get(id).map(WatchPosition::percentage)
+    }
+
+    /// where to start playing a video from, `None` if it should start from the beginning
+    pub fn start(&self, id: &str) -> Option<f64> {
+        self.0
+            .get(id)
+            .filter(|position| !position.finished())
+            .map(|position| position.position)
+    }
+
+    /// where external players save the position of videos they are quit from, with the
+    /// `--watch-later-directory` of `resume_args` in `main.yml`
+    pub fn watch_later_dir() -> PathBuf {
+        paths::data_dir().join("watch_later")
+    }
+
+    /// record positions saved by mpv to the watch later directory, the files are removed once
+    /// read so older positions do not replace newer ones
+    pub fn read_watch_later(&mut self) {
+        let Ok(entries) = fs::read_dir(Self::watch_later_dir()) else {
+            return;
+        };
+
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let _ = fs::remove_file(&path);
+
+            // the first line is `# {url}` with `--write-filename-in-watch-later-config`
+            let id = content
+                .lines()
+                .next()
+                .and_then(|line| line.strip_prefix("# "))
+                .and_then(path_video_id);
+            let start = content
+                .lines()
+                .find_map(|line| line.strip_prefix("start=")?.parse::<f64>().ok());
+
+            // the duration is not saved by mpv, it is taken from the stored video info
+            if let (Some(id), Some(start)) = (id, start) {
+                let duration = LocalStore::get_info(&id)
+                    .map(|item| item.length_secs())
+                    .unwrap_or_default();
+                self.set(&id, start, duration as f64);
+            }
+        }
+    }
+
+    /// remove positions of videos that are no longer stored
+    pub fn retain(&mut self, ids: &HashSet<String>) {
+        self.0.retain(|id, _| ids.contains(id));
+    }
+}
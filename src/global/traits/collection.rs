@@ -3,6 +3,7 @@ use std::{
     error::Error,
     fs::{self, OpenOptions},
     io::Write,
+    path::PathBuf,
 };
 
 use crate::global::functions::paths;
@@ -31,6 +32,11 @@ where
         }
     }
 
+    /// path of the index file, collections saved to a file per instance override this
+    fn index_path(&self) -> PathBuf {
+        paths::data_dir().join(Self::INDEX_PATH)
+    }
+
     /// saves the current state of watch history into a file
     fn save(&self) -> Result<(), Box<dyn Error>> {
         let save_string = serde_json::to_string_pretty(
@@ -41,13 +47,12 @@ where
                 .filter(|id| !id.is_empty())
                 .collect::<Vec<&str>>(),
         )?;
-        let path = paths::data_dir().join(Self::INDEX_PATH);
 
         let mut file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(path)?;
+            .open(self.index_path())?;
 
         file.write_all(save_string.as_bytes())?;
 
@@ -89,7 +94,12 @@ where
 
     /// loads watch history from file
     fn load() -> Self {
-        let path = paths::data_dir().join(Self::INDEX_PATH);
+        Self::load_into(Self::default())
+    }
+
+    /// loads items from the index file of `collection`, keeping its other fields
+    fn load_into(mut collection: Self) -> Self {
+        let path = collection.index_path();
         let res = (|| -> Result<Vec<String>, Box<dyn Error>> {
             let file_string = fs::read_to_string(&path)?;
             let deserialized = serde_json::from_str(&file_string)?;
@@ -112,7 +122,7 @@ where
             new_path.pop();
             new_path.push(format!(
                 "{}.{}.old",
-                Self::INDEX_PATH,
+                path.file_name().unwrap_or_default().to_string_lossy(),
                 chrono::offset::Local::now()
             ));
             let _ = fs::rename(&path, &new_path);
@@ -120,7 +130,8 @@ where
             Vec::new()
         };
 
-        Self::from_items(items)
+        *collection.items_mut() = items;
+        collection
     }
 
     /// moves thumbnails of videos in watch history from cache back to storage when exiting, so that thumbnails can be viewed offline
@@ -168,6 +179,18 @@ where
             false
         }
     }
+
+    /// move an item by its index, returns false if any of the indices are out of bounds
+    fn move_item(&mut self, from: usize, to: usize) -> bool {
+        let items = self.items_mut();
+        if from >= items.len() || to >= items.len() {
+            return false;
+        }
+
+        let item = items.remove(from);
+        items.insert(to, item);
+        true
+    }
 }
 
 pub trait CollectionNoId<T>
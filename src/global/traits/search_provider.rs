@@ -1,21 +1,28 @@
 use std::{
-    collections::HashMap,
+    any::Any,
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
     error::Error,
     fmt::{Debug, Display},
-    sync::OnceLock,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard, OnceLock,
+    },
+    thread,
 };
 
 use crate::global::common::{
-    channel::Channel,
-    hidden::{PopularItem, SearchItem},
-    universal::Playlist,
-    video::Video,
-    CommonPlaylist, CommonVideo,
+    channel::{Channel, ChannelPlaylists, ChannelSearch, ChannelVideos},
+    hidden::{Caption, PopularItem, SearchItem},
+    universal::{Mix, Playlist, Suggestions, Trending},
+    video::{Comments, Video},
 };
 use dyn_clone::DynClone;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    config::{Search, SearchProvider},
+    config::{ProviderEntry, Search, SearchProvider},
+    global::structs::{Cached, Errors, LocalProvider, ResponseCache, Transcript},
     MAIN_CONFIG,
 };
 
@@ -31,21 +38,73 @@ pub trait SearchProviderTrait: DynClone + Send {
     fn supports_channel_videos(&self) -> bool {
         false
     }
-    fn channel_videos(&self, id: &str) -> Result<Vec<CommonVideo>, Box<dyn Error>> {
+    /// `continuation` is the token of the next page, from the `continuation` of the previous page
+    fn channel_videos(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
         unimplemented!("channel_videos not implemented")
     }
 
     fn supports_channel_playlists(&self) -> bool {
         false
     }
-    fn channel_playlists(&self, id: &str) -> Result<Vec<CommonPlaylist>, Box<dyn Error>> {
+    fn channel_playlists(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelPlaylists, Box<dyn Error>> {
         unimplemented!("channel_playlists not implemented")
     }
 
+    fn supports_channel_shorts(&self) -> bool {
+        false
+    }
+    fn channel_shorts(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        unimplemented!("channel_shorts not implemented")
+    }
+
+    fn supports_channel_streams(&self) -> bool {
+        false
+    }
+    /// live streams of a channel, including past ones
+    fn channel_streams(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        unimplemented!("channel_streams not implemented")
+    }
+
+    fn supports_channel_podcasts(&self) -> bool {
+        false
+    }
+    /// podcasts of a channel, each podcast is a playlist
+    fn channel_podcasts(
+        &self,
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelPlaylists, Box<dyn Error>> {
+        unimplemented!("channel_podcasts not implemented")
+    }
+
+    fn supports_channel_search(&self) -> bool {
+        false
+    }
+    /// videos of a channel matching `query`
+    fn channel_search(&self, id: &str, query: &str) -> Result<ChannelSearch, Box<dyn Error>> {
+        unimplemented!("channel_search not implemented")
+    }
+
     fn supports_trending(&self) -> bool {
         false
     }
-    fn trending(&self) -> Result<Vec<CommonVideo>, Box<dyn Error>> {
+    fn trending(&self, continuation: Option<&str>) -> Result<Trending, Box<dyn Error>> {
         unimplemented!("trending not implemented")
     }
 
@@ -63,6 +122,14 @@ pub trait SearchProviderTrait: DynClone + Send {
         unimplemented!("search not implemented")
     }
 
+    fn supports_suggestions(&self) -> bool {
+        false
+    }
+    /// search queries starting with or related to `query`
+    fn suggestions(&self, query: &str) -> Result<Suggestions, Box<dyn Error>> {
+        unimplemented!("suggestions not implemented")
+    }
+
     fn supports_video(&self) -> bool {
         false
     }
@@ -73,10 +140,40 @@ pub trait SearchProviderTrait: DynClone + Send {
     fn supports_playlist(&self) -> bool {
         false
     }
-    fn playlist(&self, id: &str) -> Result<Playlist, Box<dyn Error>> {
+    /// only `videos` and `continuation` are used when loading a `continuation`
+    fn playlist(&self, id: &str, continuation: Option<&str>) -> Result<Playlist, Box<dyn Error>> {
         unimplemented!("playlist not implemented")
     }
 
+    fn supports_mix(&self) -> bool {
+        false
+    }
+    /// a radio playlist of videos related to a video, `id` is `RD` followed by the video id
+    fn mix(&self, id: &str) -> Result<Mix, Box<dyn Error>> {
+        unimplemented!("mix not implemented")
+    }
+
+    fn supports_comments(&self) -> bool {
+        false
+    }
+    /// `continuation` is either the token of the next page of top level comments, or the token
+    /// of a comment's replies (`Replies.continuation`)
+    fn comments(&self, id: &str, continuation: Option<&str>) -> Result<Comments, Box<dyn Error>> {
+        unimplemented!("comments not implemented")
+    }
+
+    fn supports_captions(&self) -> bool {
+        false
+    }
+    /// list of caption tracks avaliable for a video
+    fn captions(&self, id: &str) -> Result<Vec<Caption>, Box<dyn Error>> {
+        unimplemented!("captions not implemented")
+    }
+    /// download and parse a caption track returned by `captions()`
+    fn transcript(&self, caption: &Caption) -> Result<Transcript, Box<dyn Error>> {
+        unimplemented!("transcript not implemented")
+    }
+
     /*
     fn supports_album(&self) -> bool {
         false
@@ -89,19 +186,77 @@ pub trait SearchProviderTrait: DynClone + Send {
 
 dyn_clone::clone_trait_object!(SearchProviderTrait);
 
+/// responses with tokens that only work with the provider that issued them, such as
+/// continuations for loading more of them
+trait Tagged {
+    fn tokens(&mut self) -> Vec<&mut String>;
+}
+
+impl Tagged for ChannelVideos {
+    fn tokens(&mut self) -> Vec<&mut String> {
+        self.continuation.iter_mut().collect()
+    }
+}
+
+impl Tagged for ChannelPlaylists {
+    fn tokens(&mut self) -> Vec<&mut String> {
+        self.continuation.iter_mut().collect()
+    }
+}
+
+impl Tagged for Trending {
+    fn tokens(&mut self) -> Vec<&mut String> {
+        self.continuation.iter_mut().collect()
+    }
+}
+
+impl Tagged for Playlist {
+    fn tokens(&mut self) -> Vec<&mut String> {
+        self.continuation.iter_mut().collect()
+    }
+}
+
+impl Tagged for Comments {
+    /// the next page of comments, and replies of each comment
+    fn tokens(&mut self) -> Vec<&mut String> {
+        self.continuation
+            .iter_mut()
+            .chain(
+                self.comments
+                    .iter_mut()
+                    .filter_map(|comment| comment.replies.as_mut())
+                    .map(|replies| &mut replies.continuation),
+            )
+            .collect()
+    }
+}
+
+impl Tagged for Vec<Caption> {
+    /// caption tracks are downloaded from their urls
+    fn tokens(&mut self) -> Vec<&mut String> {
+        self.iter_mut().map(|caption| &mut caption.url).collect()
+    }
+}
+
+impl Tagged for Transcript {
+    fn tokens(&mut self) -> Vec<&mut String> {
+        Vec::new()
+    }
+}
+
 #[derive(Default, Clone)]
-pub struct SearchProviderWrapper(HashMap<SearchProvider, Box<dyn SearchProviderTrait>>);
+pub struct SearchProviderWrapper;
+
+/// a search provider shared between threads
+pub type SharedProvider = Arc<dyn SearchProviderTrait + Send + Sync>;
 
-struct UnsupportedError(pub &'static str);
+struct UnsupportedError(pub &'static str, pub String);
 
 impl Display for UnsupportedError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
             "Operation `{}` is not supported by {}",
-            self.0,
-            unsafe { MAIN_CONFIG.get().unwrap() }
-                .search_provider
-                .as_str()
+            self.0, self.1
         ))
     }
 }
@@ -114,90 +269,565 @@ impl Debug for UnsupportedError {
 
 impl Error for UnsupportedError {}
 
-static mut SEARCH_PROVIDER: OnceLock<SearchProviderWrapper> = OnceLock::new();
+/// providers of the chain, created when first used
+///
+/// calls clone the `Arc` out of the map, so providers replaced by `init()` stay alive until
+/// calls already made with them return
+static SEARCH_PROVIDERS: OnceLock<Mutex<HashMap<ProviderEntry, SharedProvider>>> = OnceLock::new();
+
+/// reads are answered from local data instead of the providers, see `LocalProvider`
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// set when a request is answered by a provider other than the first one in the chain
+static FALLBACK_NOTICE: Mutex<Option<String>> = Mutex::new(None);
+
+type Responses = Vec<(String, Box<dyn Any + Send>)>;
+
+/// responses recorded by `SearchProviderWrapper::prefetch()`, each one is a `Result<T, String>`
+/// keyed by the operation and its arguments
+#[derive(Default)]
+pub struct Prefetched(Responses);
+
+/// calls made inside `prefetch()`
+struct Recording {
+    responses: Responses,
+    /// skips the response cache, when reloading
+    refresh: bool,
+}
+
+thread_local! {
+    /// set while `prefetch()` runs on this thread
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+/// responses handed over with `use_prefetched()`, each one answers the first call made for it
+static PREFETCHED: Mutex<Responses> = Mutex::new(Vec::new());
+
+/// cache keys of stale responses being fetched again in the background, so a response shown
+/// many times while it is stale is only fetched once
+static REFRESHING: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
 
 impl SearchProviderWrapper {
     pub fn init() {
-        unsafe {
-            if SEARCH_PROVIDER.get().is_some() {
-                *SEARCH_PROVIDER.get_mut().unwrap() = Self::default()
-            } else {
-                let _ = SEARCH_PROVIDER.set(Self::default());
-            }
+        Self::providers().clear();
+    }
+
+    fn providers() -> MutexGuard<'static, HashMap<ProviderEntry, SharedProvider>> {
+        SEARCH_PROVIDERS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// `search_provider` followed by `fallback_providers`
+    pub fn chain() -> Vec<ProviderEntry> {
+        let config = unsafe { MAIN_CONFIG.get() }.unwrap();
+        std::iter::once(ProviderEntry::from(config.search_provider))
+            .chain(config.fallback_providers.iter().cloned())
+            .collect()
+    }
+
+    /// if reads are answered from local data, starts as `offline` in main config
+    pub fn offline() -> bool {
+        OFFLINE.load(Ordering::Relaxed)
+    }
+
+    pub fn set_offline(offline: bool) {
+        OFFLINE.store(offline, Ordering::Relaxed);
+    }
+
+    fn get(entry: &ProviderEntry) -> SharedProvider {
+        Self::providers()
+            .entry(entry.clone())
+            .or_insert_with(|| Arc::from(entry.create()))
+            .clone()
+    }
+
+    /// which provider answered the last request and why the ones before it did not, if that was
+    /// not the first provider in the chain
+    pub fn take_fallback_notice() -> Option<String> {
+        FALLBACK_NOTICE.lock().ok()?.take()
+    }
+
+    /// runs `f`, recording the responses of the calls it makes, so the same calls can be answered
+    /// without waiting once they are handed over to the main thread with `use_prefetched()`,
+    /// `refresh` fetches responses again even if they are cached
+    pub fn prefetch(refresh: bool, f: impl FnOnce()) -> Prefetched {
+        RECORDING.with(|recording| {
+            *recording.borrow_mut() = Some(Recording {
+                responses: Vec::new(),
+                refresh,
+            })
+        });
+        f();
+        Prefetched(
+            RECORDING
+                .with(|recording| recording.borrow_mut().take())
+                .map(|recording| recording.responses)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// recorded responses answer the next calls made for them, replacing ones that were not used
+    pub fn use_prefetched(prefetched: Prefetched) {
+        if let Ok(mut responses) = PREFETCHED.lock() {
+            *responses = prefetched.0;
         }
     }
 
-    #[allow(clippy::borrowed_box)]
-    fn get() -> &'static Box<dyn SearchProviderTrait> {
-        let provider = &unsafe { MAIN_CONFIG.get() }.unwrap().search_provider;
-        unsafe { SEARCH_PROVIDER.get_mut() }
-            .unwrap()
-            .0
-            .entry(*provider)
-            .or_insert(provider.create())
+    /// drops recorded responses that were not used
+    pub fn clear_prefetched() {
+        if let Ok(mut responses) = PREFETCHED.lock() {
+            responses.clear();
+        }
     }
 
-    pub fn channel(id: &str) -> Result<Channel, Box<dyn Error>> {
-        let provider = Self::get();
-        if !provider.supports_channel() {
-            return Err(UnsupportedError("channel").into());
+    /// the response cache, responses of the `Fixture` provider are not cached as they are read
+    /// from disk already
+    pub fn response_cache() -> Option<ResponseCache> {
+        let config = unsafe { MAIN_CONFIG.get() }.unwrap();
+        (config.search_provider != SearchProvider::Fixture)
+            .then(|| ResponseCache::open(config.cache.clone()))
+    }
+
+    /// answers with a response recorded by `prefetch()` or a cached response if there is one,
+    /// otherwise runs `f`, a stale cached response is shown while `f` runs in the background
+    ///
+    /// in offline mode stale responses are used as they are, and local data is not cached
+    fn cached<T>(
+        operation: &'static str,
+        args: String,
+        f: impl Fn() -> Result<T, Box<dyn Error>> + Send + 'static,
+    ) -> Result<T, Box<dyn Error>>
+    where
+        T: Clone + Send + Serialize + DeserializeOwned + 'static,
+    {
+        let key = format!("{operation}/{args}");
+        let refresh = RECORDING.with(|recording| {
+            recording
+                .borrow()
+                .as_ref()
+                .map(|recording| recording.refresh)
+        });
+
+        if refresh.is_none() {
+            let response = PREFETCHED.lock().ok().and_then(|mut responses| {
+                let index = responses
+                    .iter()
+                    .position(|(recorded, _)| recorded == &key)?;
+                Some(responses.swap_remove(index).1)
+            });
+            if let Some(res) = response.and_then(|res| res.downcast::<Result<T, String>>().ok()) {
+                return res.map_err(|e| Errors::StringError(e).into());
+            }
         }
-        provider.channel(id)
+
+        // continuations and ids only work with the providers they came from
+        let cache = Self::response_cache();
+        let cache_key = format!(
+            "{}/{key}",
+            Self::chain()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let offline = Self::offline();
+        let cached = match &cache {
+            Some(cache) if refresh != Some(true) || offline => cache.get(operation, &cache_key),
+            _ => Cached::Missing,
+        };
+
+        let res = match (cached, cache) {
+            (Cached::Fresh(value), _) => Ok(value),
+            (Cached::Stale(value), _) if offline => Ok(value),
+            (Cached::Stale(value), Some(cache)) => {
+                if REFRESHING
+                    .lock()
+                    .is_ok_and(|mut refreshing| refreshing.insert(cache_key.clone()))
+                {
+                    thread::spawn(move || {
+                        if let Ok(value) = f() {
+                            let _ = cache.put(operation, &cache_key, &value);
+                        }
+                        if let Ok(mut refreshing) = REFRESHING.lock() {
+                            refreshing.remove(&cache_key);
+                        }
+                    });
+                }
+                Ok(value)
+            }
+            (_, cache) => {
+                let res = f();
+                if let (Some(cache), Ok(value), false) = (cache, &res, offline) {
+                    let _ = cache.put(operation, &cache_key, value);
+                }
+                res
+            }
+        };
+
+        RECORDING.with(|recording| {
+            if let Some(recording) = recording.borrow_mut().as_mut() {
+                let response = res.as_ref().map(T::clone).map_err(|e| e.to_string());
+                recording.responses.push((key, Box::new(response)));
+            }
+        });
+        res
     }
 
-    pub fn channel_videos(id: &str) -> Result<Vec<CommonVideo>, Box<dyn Error>> {
-        let provider = Self::get();
-        if !provider.supports_channel_videos() {
-            return Err(UnsupportedError("channel_videos").into());
+    /// tries each provider in the chain until one of them supports the operation and succeeds,
+    /// or asks `LocalProvider` in offline mode
+    fn call<T>(
+        operation: &'static str,
+        supports: fn(&dyn SearchProviderTrait) -> bool,
+        f: impl Fn(&dyn SearchProviderTrait) -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        if Self::offline() {
+            let provider = LocalProvider::open(unsafe { MAIN_CONFIG.get() }.unwrap());
+            return if supports(&provider) {
+                f(&provider)
+            } else {
+                Err(Errors::StringError(format!(
+                    "`{operation}` is not avaliable in offline mode, turn it off with `offline off`"
+                ))
+                .into())
+            };
         }
-        provider.channel_videos(id)
+
+        Self::call_chain(operation, &Self::chain(), supports, f).map(|(value, _)| value)
     }
 
-    pub fn channel_playlists(id: &str) -> Result<Vec<CommonPlaylist>, Box<dyn Error>> {
-        let provider = Self::get();
-        if !provider.supports_channel_playlists() {
-            return Err(UnsupportedError("channel_playlists").into());
+    /// like `call`, but tokens in the response are tagged with the provider that answered, as
+    /// they only work with that provider, a tagged `token` is passed to the provider that issued
+    /// it without falling back to the others
+    fn call_tagged<T: Tagged>(
+        operation: &'static str,
+        token: Option<&str>,
+        supports: fn(&dyn SearchProviderTrait) -> bool,
+        f: impl Fn(&dyn SearchProviderTrait, Option<&str>) -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        if Self::offline() {
+            return Self::call(operation, supports, |provider| f(provider, token));
         }
-        provider.channel_playlists(id)
+
+        let (chain, token) = match token {
+            Some(token) => {
+                let (entry, token) = Self::untag(token)?;
+                (vec![entry], Some(token))
+            }
+            None => (Self::chain(), None),
+        };
+
+        let (mut value, entry) =
+            Self::call_chain(operation, &chain, supports, |provider| f(provider, token))?;
+        for token in value.tokens() {
+            *token = format!("{entry}|{token}");
+        }
+        Ok(value)
     }
 
-    pub fn trending() -> Result<Vec<CommonVideo>, Box<dyn Error>> {
-        let provider = Self::get();
-        if !provider.supports_trending() {
-            return Err(UnsupportedError("trending").into());
+    /// the provider that issued a token tagged by `call_tagged`, and the token
+    fn untag(token: &str) -> Result<(ProviderEntry, &str), Box<dyn Error>> {
+        Self::chain()
+            .into_iter()
+            .find_map(|entry| {
+                let token = token.strip_prefix(&format!("{entry}|"))?;
+                Some((entry, token))
+            })
+            .ok_or_else(|| {
+                Errors::StrError("The provider of this page is no longer in the provider chain")
+                    .into()
+            })
+    }
+
+    /// tries each provider of `chain` in order, returns the response with the provider that
+    /// answered
+    fn call_chain<T>(
+        operation: &'static str,
+        chain: &[ProviderEntry],
+        supports: fn(&dyn SearchProviderTrait) -> bool,
+        f: impl Fn(&dyn SearchProviderTrait) -> Result<T, Box<dyn Error>>,
+    ) -> Result<(T, ProviderEntry), Box<dyn Error>> {
+        let mut failed = Vec::new();
+        let mut last_error = None;
+
+        for entry in chain.iter() {
+            let provider = Self::get(entry);
+            let res = if supports(provider.as_ref()) {
+                f(provider.as_ref())
+            } else {
+                Err(UnsupportedError(operation, entry.to_string()).into())
+            };
+
+            match res {
+                Ok(value) => {
+                    if !failed.is_empty() {
+                        if let Ok(mut notice) = FALLBACK_NOTICE.lock() {
+                            *notice = Some(format!("Loaded from {entry} ({})", failed.join(", ")));
+                        }
+                    }
+                    return Ok((value, entry.clone()));
+                }
+                Err(e) => {
+                    failed.push(format!("{entry}: {e}"));
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        // keep the original error if there is nothing to fall back to
+        if chain.len() == 1 {
+            return Err(last_error.unwrap());
         }
-        provider.trending()
+
+        Err(Errors::StringError(format!("All providers failed - {}", failed.join(", "))).into())
+    }
+
+    pub fn channel(id: &str) -> Result<Channel, Box<dyn Error>> {
+        let id = id.to_string();
+        Self::cached("channel", id.clone(), move || {
+            Self::call(
+                "channel",
+                |provider| provider.supports_channel(),
+                |provider| provider.channel(&id),
+            )
+        })
+    }
+
+    pub fn channel_videos(
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        let id = id.to_string();
+        let continuation = continuation.map(str::to_string);
+        Self::cached(
+            "channel_videos",
+            format!("{id}/{}", continuation.as_deref().unwrap_or_default()),
+            move || {
+                Self::call_tagged(
+                    "channel_videos",
+                    continuation.as_deref(),
+                    |provider| provider.supports_channel_videos(),
+                    |provider, continuation| provider.channel_videos(&id, continuation),
+                )
+            },
+        )
+    }
+
+    pub fn channel_playlists(
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelPlaylists, Box<dyn Error>> {
+        let id = id.to_string();
+        let continuation = continuation.map(str::to_string);
+        Self::cached(
+            "channel_playlists",
+            format!("{id}/{}", continuation.as_deref().unwrap_or_default()),
+            move || {
+                Self::call_tagged(
+                    "channel_playlists",
+                    continuation.as_deref(),
+                    |provider| provider.supports_channel_playlists(),
+                    |provider, continuation| provider.channel_playlists(&id, continuation),
+                )
+            },
+        )
+    }
+
+    pub fn channel_shorts(
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        let id = id.to_string();
+        let continuation = continuation.map(str::to_string);
+        Self::cached(
+            "channel_shorts",
+            format!("{id}/{}", continuation.as_deref().unwrap_or_default()),
+            move || {
+                Self::call_tagged(
+                    "channel_shorts",
+                    continuation.as_deref(),
+                    |provider| provider.supports_channel_shorts(),
+                    |provider, continuation| provider.channel_shorts(&id, continuation),
+                )
+            },
+        )
+    }
+
+    pub fn channel_streams(
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelVideos, Box<dyn Error>> {
+        let id = id.to_string();
+        let continuation = continuation.map(str::to_string);
+        Self::cached(
+            "channel_streams",
+            format!("{id}/{}", continuation.as_deref().unwrap_or_default()),
+            move || {
+                Self::call_tagged(
+                    "channel_streams",
+                    continuation.as_deref(),
+                    |provider| provider.supports_channel_streams(),
+                    |provider, continuation| provider.channel_streams(&id, continuation),
+                )
+            },
+        )
+    }
+
+    pub fn channel_podcasts(
+        id: &str,
+        continuation: Option<&str>,
+    ) -> Result<ChannelPlaylists, Box<dyn Error>> {
+        let id = id.to_string();
+        let continuation = continuation.map(str::to_string);
+        Self::cached(
+            "channel_podcasts",
+            format!("{id}/{}", continuation.as_deref().unwrap_or_default()),
+            move || {
+                Self::call_tagged(
+                    "channel_podcasts",
+                    continuation.as_deref(),
+                    |provider| provider.supports_channel_podcasts(),
+                    |provider, continuation| provider.channel_podcasts(&id, continuation),
+                )
+            },
+        )
+    }
+
+    pub fn channel_search(id: &str, query: &str) -> Result<ChannelSearch, Box<dyn Error>> {
+        let id = id.to_string();
+        let query = query.to_string();
+        Self::cached("channel_search", format!("{id}/{query}"), move || {
+            Self::call(
+                "channel_search",
+                |provider| provider.supports_channel_search(),
+                |provider| provider.channel_search(&id, &query),
+            )
+        })
+    }
+
+    pub fn trending(continuation: Option<&str>) -> Result<Trending, Box<dyn Error>> {
+        let continuation = continuation.map(str::to_string);
+        Self::cached(
+            "trending",
+            continuation.clone().unwrap_or_default(),
+            move || {
+                Self::call_tagged(
+                    "trending",
+                    continuation.as_deref(),
+                    |provider| provider.supports_trending(),
+                    |provider, continuation| provider.trending(continuation),
+                )
+            },
+        )
     }
 
     pub fn popular() -> Result<Vec<PopularItem>, Box<dyn Error>> {
-        let provider = Self::get();
-        if !provider.supports_popular() {
-            return Err(UnsupportedError("popular").into());
-        }
-        provider.popular()
+        Self::cached("popular", String::new(), move || {
+            Self::call(
+                "popular",
+                |provider| provider.supports_popular(),
+                |provider| provider.popular(),
+            )
+        })
     }
 
     pub fn search(filters: &Search) -> Result<Vec<SearchItem>, Box<dyn Error>> {
-        let provider = Self::get();
-        if !provider.supports_search() {
-            return Err(UnsupportedError("search").into());
-        }
-        provider.search(filters)
+        let filters = filters.clone();
+        Self::cached("search", filters.to_string(), move || {
+            Self::call(
+                "search",
+                |provider| provider.supports_search(),
+                |provider| provider.search(&filters),
+            )
+        })
+    }
+
+    pub fn suggestions(query: &str) -> Result<Suggestions, Box<dyn Error>> {
+        Self::call(
+            "suggestions",
+            |provider| provider.supports_suggestions(),
+            |provider| provider.suggestions(query),
+        )
     }
 
     pub fn video(id: &str) -> Result<Video, Box<dyn Error>> {
-        let provider = Self::get();
-        if !provider.supports_video() {
-            return Err(UnsupportedError("video").into());
-        }
-        provider.video(id)
+        let id = id.to_string();
+        Self::cached("video", id.clone(), move || {
+            Self::call(
+                "video",
+                |provider| provider.supports_video(),
+                |provider| provider.video(&id),
+            )
+        })
     }
 
-    pub fn playlist(id: &str) -> Result<Playlist, Box<dyn Error>> {
-        let provider = Self::get();
-        if !provider.supports_playlist() {
-            return Err(UnsupportedError("playlist").into());
-        }
-        provider.playlist(id)
+    pub fn playlist(id: &str, continuation: Option<&str>) -> Result<Playlist, Box<dyn Error>> {
+        let id = id.to_string();
+        let continuation = continuation.map(str::to_string);
+        Self::cached(
+            "playlist",
+            format!("{id}/{}", continuation.as_deref().unwrap_or_default()),
+            move || {
+                Self::call_tagged(
+                    "playlist",
+                    continuation.as_deref(),
+                    |provider| provider.supports_playlist(),
+                    |provider, continuation| provider.playlist(&id, continuation),
+                )
+            },
+        )
+    }
+
+    pub fn mix(id: &str) -> Result<Mix, Box<dyn Error>> {
+        let id = id.to_string();
+        Self::cached("mix", id.clone(), move || {
+            Self::call(
+                "mix",
+                |provider| provider.supports_mix(),
+                |provider| provider.mix(&id),
+            )
+        })
+    }
+
+    pub fn comments(id: &str, continuation: Option<&str>) -> Result<Comments, Box<dyn Error>> {
+        let id = id.to_string();
+        let continuation = continuation.map(str::to_string);
+        Self::cached(
+            "comments",
+            format!("{id}/{}", continuation.as_deref().unwrap_or_default()),
+            move || {
+                Self::call_tagged(
+                    "comments",
+                    continuation.as_deref(),
+                    |provider| provider.supports_comments(),
+                    |provider, continuation| provider.comments(&id, continuation),
+                )
+            },
+        )
+    }
+
+    pub fn captions(id: &str) -> Result<Vec<Caption>, Box<dyn Error>> {
+        Self::call_tagged(
+            "captions",
+            None,
+            |provider| provider.supports_captions(),
+            |provider, _| provider.captions(id),
+        )
+    }
+
+    /// downloads a caption track from the provider that listed it in `captions()`
+    pub fn transcript(caption: &Caption) -> Result<Transcript, Box<dyn Error>> {
+        Self::call_tagged(
+            "transcript",
+            Some(&caption.url),
+            |provider| provider.supports_captions(),
+            |provider, url| {
+                provider.transcript(&Caption {
+                    url: url.unwrap_or_default().to_string(),
+                    ..caption.clone()
+                })
+            },
+        )
     }
 }
@@ -2,13 +2,13 @@ use crate::{
     config::*,
     global::{functions::*, structs::*, traits::*},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::Backend, Terminal};
 use std::{
     collections::HashSet,
     error::Error,
     fs::{self},
-    io::Stdout,
     sync::OnceLock,
+    time::Duration,
 };
 use tokio::runtime::{Builder, Runtime};
 use tui_additions::framework::{Framework, FrameworkClean};
@@ -22,15 +22,17 @@ pub static CACHED_BEFORE: OnceLock<HashSet<String>> = OnceLock::new();
 //  - create folders like `~/.config/youtube-tui/` and `~/.cache/youtube-tui/thumbnails/`
 //  - load all config files
 //  - insert data
-pub fn init(
+pub fn init<B: Backend>(
     framework: &mut Framework,
-    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    terminal: &mut Terminal<B>,
     command: Option<&str>,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), Box<dyn Error>>
+where
+    B::Error: 'static,
+{
     LocalStore::init();
-    RUNTIME
-        .set(Builder::new_current_thread().enable_all().build().unwrap())
-        .unwrap();
+    // init can be called more than once in the same process, such as in tests
+    RUNTIME.get_or_init(|| Builder::new_current_thread().enable_all().build().unwrap());
 
     // creating files
     let data = paths::data_dir();
@@ -52,6 +54,11 @@ pub fn init(
 
     let library = Library::load();
     let watchhistory = WatchHistory::load();
+    let playqueue = PlayQueue::load();
+    let localplaylists = LocalPlaylists::load();
+    let mut watchpositions = WatchPositions::load();
+    // positions saved by external players since the last time, they are read again when one exits
+    watchpositions.read_watch_later();
 
     let cached_set: HashSet<String> = HashSet::from_iter(
         library
@@ -65,9 +72,17 @@ pub fn init(
                     .iter()
                     .filter_map(|item| item.id())
                     .map(str::to_string),
-            ),
+            )
+            .chain(
+                playqueue
+                    .items()
+                    .iter()
+                    .filter_map(|item| item.id())
+                    .map(str::to_string),
+            )
+            .chain(localplaylists.ids()),
     );
-    CACHED_BEFORE.set(cached_set).unwrap();
+    let _ = CACHED_BEFORE.set(cached_set);
 
     framework
         .data
@@ -77,6 +92,18 @@ pub fn init(
         .data
         .global
         .insert::<SearchHistory>(SearchHistory::load());
+    framework
+        .data
+        .global
+        .insert::<SearchSuggestions>(SearchSuggestions::default());
+    framework
+        .data
+        .global
+        .insert::<PageLoader>(PageLoader::default());
+    framework
+        .data
+        .global
+        .insert::<BackgroundFetch>(BackgroundFetch::default());
     framework.data.global.insert::<WatchHistory>(watchhistory);
     /*
     framework
@@ -89,6 +116,32 @@ pub fn init(
         .global
         .insert::<Subscriptions>(Subscriptions::load());
     framework.data.global.insert::<Library>(library);
+    // built by the first `localsearch`
+    framework
+        .data
+        .global
+        .insert::<LocalIndex>(LocalIndex::default());
+    framework.data.global.insert::<PlayQueue>(playqueue);
+    framework
+        .data
+        .global
+        .insert::<LocalPlaylists>(localplaylists);
+    framework
+        .data
+        .global
+        .insert::<WatchPositions>(watchpositions);
+    framework
+        .data
+        .global
+        .insert::<WatchedVideos>(WatchedVideos {
+            hide: framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .hide_watched,
+            ..WatchedVideos::load()
+        });
     framework.data.global.insert::<Message>(Message::None);
 
     framework.data.global.insert::<Status>(Status {
@@ -101,6 +154,11 @@ pub fn init(
         .data
         .global
         .insert::<MpvWrapper>(MpvWrapper::spawn());
+    #[cfg(feature = "mpv")]
+    framework
+        .data
+        .global
+        .insert::<MpvState>(MpvState::default());
 
     framework.data.state.insert::<Tasks>(Tasks::default());
     framework.data.state.insert::<Page>(Page::default());
@@ -151,6 +209,13 @@ pub fn load_configs(framework: &mut FrameworkClean) -> Result<(), Box<dyn Error>
     }
 
     SearchProviderWrapper::init();
+    SearchProviderWrapper::set_offline(main_config.offline);
+    InstancePool::global().set_instances(
+        std::iter::once(main_config.invidious_instance.clone())
+            .chain(main_config.invidious_instances.iter().cloned())
+            .collect(),
+        Duration::from_secs(main_config.instance_timeout),
+    );
 
     framework
         .data
@@ -187,6 +252,15 @@ pub fn load_configs(framework: &mut FrameworkClean) -> Result<(), Box<dyn Error>
         .data
         .state
         .insert::<Search>(*Search::load(main_config.write_config)?);
+    let filters_config = *FiltersConfig::load(main_config.write_config)?;
+    framework
+        .data
+        .global
+        .insert::<Filters>(Filters::new(&filters_config, &main_config.block_list));
+    framework
+        .data
+        .global
+        .insert::<FiltersConfig>(filters_config);
     framework.data.global.insert::<MainConfig>(main_config);
 
     Ok(())
@@ -2,6 +2,10 @@ use super::ItemInfo;
 use crate::{
     config::*,
     global::{
+        common::{
+            channel::{ChannelPlaylists, ChannelVideos},
+            hidden::SearchItem,
+        },
         functions::*,
         structs::*,
         traits::{Collection, SearchProviderWrapper},
@@ -17,6 +21,42 @@ use tui_additions::{
     widgets::{Grid, TextList},
 };
 
+/// videos of a video tab, such as shorts
+pub fn fetch_videos(
+    id: &str,
+    r#type: &ChannelDisplayPageType,
+    continuation: Option<&str>,
+) -> Result<ChannelVideos, Box<dyn std::error::Error>> {
+    match r#type {
+        ChannelDisplayPageType::Shorts => SearchProviderWrapper::channel_shorts(id, continuation),
+        ChannelDisplayPageType::Streams => SearchProviderWrapper::channel_streams(id, continuation),
+        _ => SearchProviderWrapper::channel_videos(id, continuation),
+    }
+}
+
+/// playlists of a playlist tab, such as podcasts
+pub fn fetch_playlists(
+    id: &str,
+    r#type: &ChannelDisplayPageType,
+    continuation: Option<&str>,
+) -> Result<ChannelPlaylists, Box<dyn std::error::Error>> {
+    match r#type {
+        ChannelDisplayPageType::Podcasts => {
+            SearchProviderWrapper::channel_podcasts(id, continuation)
+        }
+        _ => SearchProviderWrapper::channel_playlists(id, continuation),
+    }
+}
+
+/// what a channel page loads from the search provider
+pub enum ChannelContent {
+    Channel(Box<Item>),
+    /// videos and the token for the next page
+    Videos(Vec<Item>, Option<String>),
+    /// playlists and the token for the next page
+    Playlists(Vec<Item>, Option<String>),
+}
+
 /// the 4 pages that a channel has (including the default "blank" page when loading)
 #[derive(Clone, Default)]
 pub enum ChannelDisplay {
@@ -37,6 +77,10 @@ pub enum ChannelDisplay {
         textlist: TextList,
         iteminfo: Box<ItemInfo>,
         grid: Grid,
+        /// token for the next page of videos
+        continuation: Option<String>,
+        /// id of the `BackgroundFetch` loading the next page
+        loading: Option<u64>,
     },
     /// created playlists
     Playlists {
@@ -44,10 +88,72 @@ pub enum ChannelDisplay {
         textlist: TextList,
         iteminfo: Box<ItemInfo>,
         grid: Grid,
+        /// token for the next page of playlists
+        continuation: Option<String>,
+        /// id of the `BackgroundFetch` loading the next page
+        loading: Option<u64>,
     },
 }
 
 impl ChannelDisplay {
+    /// content of the page from the search provider, `None` if the channel is already in the local
+    /// store
+    pub fn fetch(page: &Page, mainconfig: &MainConfig) -> Option<Fetch<ChannelContent>> {
+        let ChannelDisplayPage { id, r#type } = page.channeldisplay().clone();
+        let image_index = mainconfig.image_index;
+        Some(match r#type {
+            ChannelDisplayPageType::Main if LocalStore::get_info(&id).is_some() => return None,
+            ChannelDisplayPageType::Main => Box::new(move || {
+                Ok(ChannelContent::Channel(Box::new(Item::from_full_channel(
+                    SearchProviderWrapper::channel(&id)?,
+                    image_index,
+                ))))
+            }),
+            ChannelDisplayPageType::Videos
+            | ChannelDisplayPageType::Shorts
+            | ChannelDisplayPageType::Streams
+            | ChannelDisplayPageType::Search(_) => Box::new(move || {
+                let (videos, continuation) = match &r#type {
+                    // search results are a single page of videos
+                    ChannelDisplayPageType::Search(query) => (
+                        SearchProviderWrapper::channel_search(&id, query)?
+                            .items
+                            .into_iter()
+                            .filter_map(|item| match item {
+                                SearchItem::Video(video) => Some(video),
+                                _ => None,
+                            })
+                            .collect(),
+                        None,
+                    ),
+                    r#type => {
+                        let res = fetch_videos(&id, r#type, None)?;
+                        (res.videos, res.continuation)
+                    }
+                };
+                Ok(ChannelContent::Videos(
+                    videos
+                        .into_iter()
+                        .map(|video| Item::from_common_video(video, image_index))
+                        .collect(),
+                    continuation,
+                ))
+            }),
+            ChannelDisplayPageType::Playlists | ChannelDisplayPageType::Podcasts => {
+                Box::new(move || {
+                    let res = fetch_playlists(&id, &r#type, None)?;
+                    Ok(ChannelContent::Playlists(
+                        res.playlists
+                            .into_iter()
+                            .map(Item::from_common_playlist)
+                            .collect(),
+                        res.continuation,
+                    ))
+                })
+            }
+        })
+    }
+
     pub fn new_textlist_with_map(commands: Vec<(String, String)>) -> TextList {
         TextList::default()
             .items(
@@ -106,19 +212,25 @@ impl ChannelDisplay {
                 videos, textlist, ..
             } => {
                 if textlist.items.is_empty() {
-                    vec![(String::from("hover-url"), "no-videos".to_string())]
+                    vec![
+                        (String::from("hover-url"), "no-videos".to_string()),
+                        (String::from("hover-id"), "no-videos".to_string()),
+                    ]
                 } else {
-                    vec![(
-                        String::from("hover-url"),
-                        format!(
-                            "{}/watch?v={}",
-                            match status.provider {
-                                Provider::YouTube => "https://youtube.com",
-                                Provider::Invidious => &mainconfig.invidious_instance,
-                            },
-                            videos[textlist.selected].id().unwrap_or_default()
+                    let id = videos[textlist.selected].id().unwrap_or_default();
+                    vec![
+                        (
+                            String::from("hover-url"),
+                            format!(
+                                "{}/watch?v={id}",
+                                match status.provider {
+                                    Provider::YouTube => "https://youtube.com",
+                                    Provider::Invidious => &mainconfig.invidious_instance,
+                                },
+                            ),
                         ),
-                    )]
+                        (String::from("hover-id"), id.to_string()),
+                    ]
                 }
             }
             ChannelDisplay::Playlists {
@@ -258,17 +370,174 @@ impl ChannelDisplay {
                 textlist,
                 iteminfo,
                 ..
-            } => {
-                if !items.is_empty()
-                    && items[textlist.selected].id() != iteminfo.item.as_ref().unwrap().id()
-                {
-                    iteminfo.item = Some(items[textlist.selected].clone())
-                }
+            } if !items.is_empty()
+                && items[textlist.selected].id() != iteminfo.item.as_ref().unwrap().id() =>
+            {
+                iteminfo.item = Some(items[textlist.selected].clone())
             }
             _ => {}
         }
     }
 
+    /// loads the next page of videos or playlists in the background when the cursor reaches the
+    /// last one
+    fn load_more(&mut self, framework: &mut FrameworkClean) {
+        let at_end = match self {
+            Self::Videos {
+                videos, textlist, ..
+            } => textlist.selected + 1 >= videos.len(),
+            Self::Playlists {
+                playlists,
+                textlist,
+                ..
+            } => textlist.selected + 1 >= playlists.len(),
+            _ => false,
+        };
+
+        if at_end {
+            self.fetch_more(framework);
+        }
+    }
+
+    /// starts loading the next page if there is one and it is not already loading
+    fn fetch_more(&mut self, framework: &mut FrameworkClean) {
+        let page = framework.data.state.get::<Page>().unwrap().channeldisplay();
+        let (id, r#type) = (page.id.clone(), page.r#type.clone());
+        let image_index = framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .image_index;
+
+        let (fetch, loading): (Fetch<ChannelContent>, _) = match self {
+            Self::Videos {
+                continuation: Some(continuation),
+                loading: loading @ None,
+                ..
+            } => {
+                let continuation = continuation.clone();
+                (
+                    Box::new(move || {
+                        let res = fetch_videos(&id, &r#type, Some(&continuation))?;
+                        Ok(ChannelContent::Videos(
+                            res.videos
+                                .into_iter()
+                                .map(|video| Item::from_common_video(video, image_index))
+                                .collect(),
+                            res.continuation,
+                        ))
+                    }),
+                    loading,
+                )
+            }
+            Self::Playlists {
+                continuation: Some(continuation),
+                loading: loading @ None,
+                ..
+            } => {
+                let continuation = continuation.clone();
+                (
+                    Box::new(move || {
+                        let res = fetch_playlists(&id, &r#type, Some(&continuation))?;
+                        Ok(ChannelContent::Playlists(
+                            res.playlists
+                                .into_iter()
+                                .map(Item::from_common_playlist)
+                                .collect(),
+                            res.continuation,
+                        ))
+                    }),
+                    loading,
+                )
+            }
+            _ => return,
+        };
+
+        *loading = Some(
+            framework
+                .data
+                .global
+                .get_mut::<BackgroundFetch>()
+                .unwrap()
+                .start(fetch),
+        );
+    }
+
+    /// adds the next page once it arrives
+    fn more_loaded(&mut self, framework: &mut FrameworkClean, res: Result<ChannelContent, String>) {
+        let content = match res {
+            Ok(content) => content,
+            // the token is kept, so moving to the last item again retries
+            Err(e) => {
+                *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(e);
+                return;
+            }
+        };
+
+        let watched = framework.data.global.get::<WatchedVideos>().unwrap();
+        let filters = framework.data.global.get::<Filters>().unwrap();
+        let (new, loaded) = match (&mut *self, content) {
+            (
+                Self::Videos {
+                    videos,
+                    textlist,
+                    continuation,
+                    ..
+                },
+                ChannelContent::Videos(new, next),
+            ) => {
+                let loaded = new.len();
+                let new = new
+                    .into_iter()
+                    .filter(|video| {
+                        !video.id().is_some_and(|id| watched.hidden(id)) && filters.allowed(video)
+                    })
+                    .collect::<Vec<_>>();
+                videos.extend(new.iter().cloned());
+                textlist.set_items(videos).unwrap();
+                *continuation = next;
+                (new, loaded)
+            }
+            (
+                Self::Playlists {
+                    playlists,
+                    textlist,
+                    continuation,
+                    ..
+                },
+                ChannelContent::Playlists(new, next),
+            ) => {
+                let loaded = new.len();
+                let new = new
+                    .into_iter()
+                    .filter(|playlist| filters.allowed(playlist))
+                    .collect::<Vec<_>>();
+                playlists.extend(new.iter().cloned());
+                textlist.set_items(playlists).unwrap();
+                *continuation = next;
+                (new, loaded)
+            }
+            _ => return,
+        };
+
+        if framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .images
+            .display()
+        {
+            download_all_images(new.iter().map(|item| item.into()).collect());
+        }
+
+        // filters removed the whole page, the cursor cannot move to a new item to load the next one
+        if new.is_empty() && loaded != 0 {
+            self.fetch_more(framework);
+        }
+    }
+
     /// check if self should be able to be selected
     pub fn selectable(&self) -> bool {
         !matches!(self, Self::None)
@@ -341,6 +610,23 @@ impl FrameworkItem for ChannelDisplay {
                 iteminfo.render(frame, framework, inner[1], popup_render, info);
             }
         }
+
+        if let Self::Videos {
+            videos,
+            textlist,
+            grid,
+            ..
+        } = self
+        {
+            let watched = framework.data.global.get::<WatchedVideos>().unwrap();
+            dim_rows(
+                frame.buffer_mut(),
+                textlist,
+                grid.chunks(area).unwrap()[0][0],
+                watched_style(framework.data.global.get::<AppearanceConfig>().unwrap()),
+                |index| videos[index].id().is_some_and(|id| watched.contains(id)),
+            );
+        }
     }
 
     fn select(&mut self, _framework: &mut tui_additions::framework::FrameworkClean) -> bool {
@@ -356,6 +642,14 @@ impl FrameworkItem for ChannelDisplay {
             return false;
         }
 
+        if let Self::Videos { loading, .. } | Self::Playlists { loading, .. } = self {
+            if let Some(res) = Fetched::take(&data, *loading) {
+                *loading = None;
+                self.more_loaded(framework, res);
+                return true;
+            }
+        }
+
         let updated = match self {
             Self::None => false,
             Self::Main { textlist, .. } => data.get("type").is_some_and(|v| {
@@ -434,6 +728,10 @@ impl FrameworkItem for ChannelDisplay {
             }
         };
 
+        if updated {
+            self.load_more(framework);
+        }
+
         set_envs(
             self.infalte_item_update(
                 framework.data.global.get::<MainConfig>().unwrap(),
@@ -586,6 +884,13 @@ impl FrameworkItem for ChannelDisplay {
             _ => {}
         }
 
+        if matches!(
+            action,
+            KeyAction::MoveDown | KeyAction::MoveRight | KeyAction::End
+        ) {
+            self.load_more(framework);
+        }
+
         Ok(())
     }
 
@@ -600,15 +905,24 @@ impl FrameworkItem for ChannelDisplay {
         let page_id = page.id.clone();
         // let is_main_page = matches!(page.r#type, ChannelDisplayPageType::Main);
 
-        match page.r#type {
-            ChannelDisplayPageType::Main => {
-                let (channel, is_new) = if let Some(item) = LocalStore::get_info(&page.id) {
-                    (item, false)
-                } else {
-                    (load_channel(&page.id, mainconfig)?, true)
-                };
+        let fetched = Self::fetch(framework.data.state.get::<Page>().unwrap(), mainconfig)
+            .map(|fetch| fetch())
+            .transpose()?;
+        let is_new = fetched.is_some();
+        // the channel is loaded from the local store if it has been loaded before
+        let content = match fetched {
+            Some(content) => content,
+            None => ChannelContent::Channel(Box::new(
+                LocalStore::get_info(&page.id).ok_or(format!("Unknown channel: {}", page.id))?,
+            )),
+        };
 
-                LocalStore::set_info(page_id.clone(), channel.clone(), is_new);
+        match content {
+            ChannelContent::Channel(channel) => {
+                if is_new {
+                    download_item_images(&channel, mainconfig);
+                }
+                LocalStore::set_info(page_id.clone(), (*channel).clone(), is_new);
 
                 let commands = framework
                     .data
@@ -619,8 +933,8 @@ impl FrameworkItem for ChannelDisplay {
                     .clone();
 
                 *self = Self::Main {
-                    iteminfo: Box::new(ItemInfo::new(Some(channel.clone()))),
-                    channel: Box::new(channel.clone()), // TODO the clone seem rather wasteful here
+                    iteminfo: Box::new(ItemInfo::new(Some((*channel).clone()))),
+                    channel: channel.clone(), // TODO the clone seem rather wasteful here
                     grid: Grid::new(
                         vec![Constraint::Percentage(60), Constraint::Percentage(40)],
                         vec![Constraint::Percentage(100)],
@@ -630,13 +944,23 @@ impl FrameworkItem for ChannelDisplay {
                     commands,
                 };
 
+                framework
+                    .data
+                    .global
+                    .get_mut::<LocalIndex>()
+                    .unwrap()
+                    .push(&channel);
                 let watch_history = framework.data.global.get_mut::<WatchHistory>().unwrap();
-                watch_history.push(channel)?;
+                watch_history.push(*channel)?;
             }
-            ChannelDisplayPageType::Videos => {
-                let videos = SearchProviderWrapper::channel_videos(&page.id)?
+            ChannelContent::Videos(videos, continuation) => {
+                let watched = framework.data.global.get::<WatchedVideos>().unwrap();
+                let filters = framework.data.global.get::<Filters>().unwrap();
+                let videos = videos
                     .into_iter()
-                    .map(|video| Item::from_common_video(video, mainconfig.image_index))
+                    .filter(|video| {
+                        !video.id().is_some_and(|id| watched.hidden(id)) && filters.allowed(video)
+                    })
                     .collect::<Vec<_>>();
                 if mainconfig.images.display() {
                     download_all_images(videos.iter().map(|item| item.into()).collect());
@@ -654,12 +978,15 @@ impl FrameworkItem for ChannelDisplay {
                     )?
                     .border_type(appearance.borders),
                     videos,
+                    continuation,
+                    loading: None,
                 };
             }
-            ChannelDisplayPageType::Playlists => {
-                let playlists = SearchProviderWrapper::channel_playlists(&page.id)?
+            ChannelContent::Playlists(playlists, continuation) => {
+                let filters = framework.data.global.get::<Filters>().unwrap();
+                let playlists = playlists
                     .into_iter()
-                    .map(Item::from_common_playlist)
+                    .filter(|playlist| filters.allowed(playlist))
                     .collect::<Vec<_>>();
                 if mainconfig.images.display() {
                     download_all_images(playlists.iter().map(|item| item.into()).collect());
@@ -677,6 +1004,8 @@ impl FrameworkItem for ChannelDisplay {
                     )?
                     .border_type(appearance.borders),
                     playlists,
+                    continuation,
+                    loading: None,
                 };
             }
         }
@@ -763,6 +1092,7 @@ impl FrameworkItem for ChannelDisplay {
                 }
 
                 self.update();
+                self.load_more(framework);
 
                 // render the new image
                 framework
@@ -0,0 +1,194 @@
+use std::error::Error;
+
+use crate::{
+    config::*,
+    global::{
+        functions::{next_word, previous_word, remove_word},
+        structs::*,
+    },
+};
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Alignment,
+    style::Style,
+    widgets::{Block, Borders},
+};
+use tui_additions::{framework::FrameworkItem, widgets::TextField};
+
+/// text field on the channel page, searches the videos of the channel
+#[derive(Clone, Default)]
+pub struct ChannelSearchBar {
+    pub text_field: TextField,
+}
+
+impl FrameworkItem for ChannelSearchBar {
+    fn render(
+        &mut self,
+        frame: &mut ratatui::Frame,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        area: ratatui::layout::Rect,
+        popup_render: bool,
+        info: tui_additions::framework::ItemInfo,
+    ) {
+        if popup_render {
+            return;
+        }
+
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+
+        let block = Block::default()
+            .title("Search channel")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(appearance.borders)
+            .border_style(Style::default().fg(if info.hover {
+                appearance.colors.outline_hover
+            } else if info.selected {
+                appearance.colors.outline_selected
+            } else {
+                appearance.colors.outline
+            }));
+
+        let inner = block.inner(area);
+
+        frame.render_widget(block, area);
+
+        self.text_field.set_width(inner.width);
+
+        frame.render_widget(self.text_field.clone(), inner);
+    }
+
+    // shows the query when on a search results page
+    fn load_item(
+        &mut self,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn Error>> {
+        if let ChannelDisplayPageType::Search(query) =
+            &framework.data.state.get::<Page>().unwrap().channeldisplay().r#type
+        {
+            self.text_field.content = query.clone();
+            let _ = self.text_field.last();
+        }
+
+        Ok(())
+    }
+
+    fn key_event(
+        &mut self,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        key: crossterm::event::KeyEvent,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn Error>> {
+        let updated = match framework
+            .data
+            .global
+            .get::<KeyBindingsConfig>()
+            .unwrap()
+            .get(key)
+        {
+            _ if matches!(key.code, KeyCode::Char(_)) && key.modifiers.bits() < 2 => {
+                self.text_field.push(key.code.as_char().unwrap()).is_ok()
+            }
+            Some(KeyAction::RemoveWord) => {
+                remove_word(&mut self.text_field);
+                true
+            }
+            Some(KeyAction::PreviousWord) => {
+                previous_word(&mut self.text_field);
+                true
+            }
+            Some(KeyAction::NextWord) => {
+                next_word(&mut self.text_field);
+                true
+            }
+            Some(KeyAction::ClearLine) => {
+                self.text_field.content.clear();
+                self.text_field.scroll = 0;
+                self.text_field.cursor = 0;
+                true
+            }
+            _ => match key.code {
+                KeyCode::Backspace => self.text_field.remove(self.text_field.cursor).is_ok(),
+                KeyCode::Up => self.text_field.first().is_ok(),
+                KeyCode::Down => self.text_field.last().is_ok(),
+                KeyCode::Right => self.text_field.right().is_ok(),
+                KeyCode::Left => self.text_field.left().is_ok(),
+                KeyCode::Enter => {
+                    if self.text_field.content.trim().is_empty() {
+                        *framework.data.global.get_mut::<Message>().unwrap() =
+                            Message::Message(String::from("Search string must not be empty"));
+                        true
+                    } else {
+                        let id = framework
+                            .data
+                            .state
+                            .get::<Page>()
+                            .unwrap()
+                            .channeldisplay()
+                            .id
+                            .clone();
+                        framework
+                            .data
+                            .state
+                            .get_mut::<Tasks>()
+                            .unwrap()
+                            .priority
+                            .push(Task::LoadPage(Page::ChannelDisplay(ChannelDisplayPage {
+                                id,
+                                r#type: ChannelDisplayPageType::Search(
+                                    self.text_field.content.clone(),
+                                ),
+                            })));
+                        false
+                    }
+                }
+                _ => false,
+            },
+        };
+
+        // only re-render screen if updated
+        if updated {
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+        }
+
+        Ok(())
+    }
+
+    fn mouse_event(
+        &mut self,
+        _framework: &mut tui_additions::framework::FrameworkClean,
+        mut x: u16,
+        y: u16,
+        _absolute_x: u16,
+        _absolute_y: u16,
+    ) -> bool {
+        if y != 1 || x == 0 {
+            return false;
+        }
+
+        x -= 1; // there is 1 character to the left of the text field
+
+        if x == self.text_field.cursor as u16 {
+            return false;
+        }
+
+        if x > self.text_field.content.len() as u16 {
+            return self.text_field.last().is_ok();
+        }
+
+        self.text_field.cursor = x as usize;
+        self.text_field.update().is_ok()
+    }
+
+    fn selectable(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,654 @@
+use std::error::Error;
+
+use crate::{
+    config::*,
+    global::{
+        common::{hidden::Comment, video::Comments},
+        functions::*,
+        structs::*,
+        traits::SearchProviderWrapper,
+    },
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Style,
+    widgets::{Paragraph, Wrap},
+};
+use tui_additions::{
+    framework::{FrameworkClean, FrameworkItem},
+    widgets::{Grid, TextList},
+};
+
+/// Displays the comments of a video as a tree
+// It consists of a 1 x 2 grid, the left cell is a text list of the flattened comment tree, the
+// right cell displays the full content of the hovered comment
+#[derive(Clone)]
+pub struct CommentsView {
+    /// id of the video
+    pub id: String,
+    pub comment_count: Option<u32>,
+    pub comments: Vec<CommentNode>,
+    /// token for the next page of top level comments, `None` if all comments are loaded
+    pub continuation: Option<String>,
+    /// id of the `BackgroundFetch` loading more comments, with the path of the comment if they are
+    /// replies
+    pub loading: Option<(u64, Option<Vec<usize>>)>,
+    /// what each line in `self.textlist` represents
+    pub rows: Vec<CommentRow>,
+    pub textlist: TextList,
+    pub grid: Grid,
+}
+
+/// A comment and the replies that are loaded so far
+#[derive(Clone)]
+pub struct CommentNode {
+    pub comment: Comment,
+    pub replies: Vec<CommentNode>,
+    /// token for the next page of replies, `None` if all replies are loaded
+    pub continuation: Option<String>,
+    pub expanded: bool,
+}
+
+/// A line in the comments text list, the `Vec<usize>` is the path from the top level comment to
+/// the comment the line belongs to
+#[derive(Clone, PartialEq, Eq)]
+pub enum CommentRow {
+    Comment(Vec<usize>),
+    /// show/hide replies button
+    Replies(Vec<usize>),
+    /// load more replies button
+    MoreReplies(Vec<usize>),
+    /// load more comments button
+    MoreComments,
+}
+
+impl Default for CommentsView {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            comment_count: None,
+            comments: Vec::new(),
+            continuation: None,
+            loading: None,
+            rows: Vec::new(),
+            textlist: TextList::default(),
+            grid: Grid::new(
+                vec![Constraint::Percentage(60), Constraint::Percentage(40)],
+                vec![Constraint::Percentage(100)],
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl CommentNode {
+    pub fn new(comment: Comment) -> Self {
+        Self {
+            continuation: comment
+                .replies
+                .as_ref()
+                .map(|replies| replies.continuation.clone()),
+            comment,
+            replies: Vec::new(),
+            expanded: false,
+        }
+    }
+
+    /// if the comment has any replies, loaded or not
+    pub fn has_replies(&self) -> bool {
+        !self.replies.is_empty() || self.continuation.is_some()
+    }
+
+    fn flatten(&self, path: Vec<usize>, rows: &mut Vec<CommentRow>, lines: &mut Vec<String>) {
+        let indent = "    ".repeat(path.len() - 1);
+        lines.push(format!(
+            "{indent}{}: {}",
+            self.comment.author,
+            self.comment.content.replace('\n', " ")
+        ));
+        rows.push(CommentRow::Comment(path.clone()));
+
+        if !self.has_replies() {
+            return;
+        }
+
+        lines.push(if self.expanded {
+            format!("{indent}  [-] Hide replies")
+        } else {
+            match &self.comment.replies {
+                Some(replies) => format!(
+                    "{indent}  [+] {} repl{}",
+                    replies.replies,
+                    if replies.replies == 1 { "y" } else { "ies" }
+                ),
+                None => format!("{indent}  [+] Show replies"),
+            }
+        });
+        rows.push(CommentRow::Replies(path.clone()));
+
+        if !self.expanded {
+            return;
+        }
+
+        for (index, reply) in self.replies.iter().enumerate() {
+            let mut path = path.clone();
+            path.push(index);
+            reply.flatten(path, rows, lines);
+        }
+
+        if self.continuation.is_some() {
+            lines.push(format!("{indent}    Load more replies"));
+            rows.push(CommentRow::MoreReplies(path));
+        }
+    }
+}
+
+impl CommentsView {
+    /// the first page of comments of the video
+    pub fn fetch(page: &Page) -> Option<Fetch<Comments>> {
+        match page {
+            Page::Comments(id) => {
+                let id = id.clone();
+                Some(Box::new(move || SearchProviderWrapper::comments(&id, None)))
+            }
+            _ => None,
+        }
+    }
+
+    /// rebuild `self.rows` and `self.textlist` from the comment tree
+    fn update_rows(&mut self) {
+        let mut rows = Vec::new();
+        let mut lines = Vec::new();
+
+        for (index, node) in self.comments.iter().enumerate() {
+            node.flatten(vec![index], &mut rows, &mut lines);
+        }
+
+        if self.continuation.is_some() {
+            rows.push(CommentRow::MoreComments);
+            lines.push(String::from("Load more comments"));
+        }
+
+        self.rows = rows;
+        self.textlist.set_items(&lines).unwrap();
+    }
+
+    fn node(&self, path: &[usize]) -> Option<&CommentNode> {
+        let mut node = self.comments.get(path[0])?;
+        for index in &path[1..] {
+            node = node.replies.get(*index)?;
+        }
+        Some(node)
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut CommentNode> {
+        let mut node = self.comments.get_mut(path[0])?;
+        for index in &path[1..] {
+            node = node.replies.get_mut(*index)?;
+        }
+        Some(node)
+    }
+
+    /// the comment under the cursor, for "show replies" buttons this is the comment that is being replied to
+    fn hovered(&self) -> Option<&Comment> {
+        match self.rows.get(self.textlist.selected)? {
+            CommentRow::Comment(path)
+            | CommentRow::Replies(path)
+            | CommentRow::MoreReplies(path) => self.node(path).map(|node| &node.comment),
+            CommentRow::MoreComments => None,
+        }
+    }
+
+    /// index of the row drawn at line `y` of the text list, following its layout: rows are drawn
+    /// from `scroll`, each taking 1 line except the selected row which is boxed in 3 lines
+    fn row_at(&self, y: u16) -> Option<usize> {
+        let visible = self.textlist.height?.saturating_sub(2) as usize;
+        let mut top = 0;
+
+        for index in (self.textlist.scroll..self.textlist.items.len()).take(visible) {
+            let height = if index == self.textlist.selected {
+                3
+            } else {
+                1
+            };
+            if y < top + height {
+                return Some(index);
+            }
+            top += height;
+        }
+
+        None
+    }
+
+    /// load the next page of replies of a comment in the background
+    fn load_replies(&mut self, framework: &mut FrameworkClean, path: &[usize]) {
+        let continuation = match self.node(path) {
+            Some(node) => node.continuation.clone(),
+            None => return,
+        };
+        self.start_loading(framework, continuation, Some(path.to_vec()));
+    }
+
+    /// load the next page of top level comments in the background
+    fn load_more(&mut self, framework: &mut FrameworkClean) {
+        self.start_loading(framework, self.continuation.clone(), None);
+    }
+
+    fn start_loading(
+        &mut self,
+        framework: &mut FrameworkClean,
+        continuation: Option<String>,
+        path: Option<Vec<usize>>,
+    ) {
+        let continuation = match continuation {
+            Some(continuation) if self.loading.is_none() => continuation,
+            _ => return,
+        };
+
+        let id = self.id.clone();
+        let fetch_id = framework
+            .data
+            .global
+            .get_mut::<BackgroundFetch>()
+            .unwrap()
+            .start(Box::new(move || {
+                SearchProviderWrapper::comments(&id, Some(&continuation))
+            }));
+        self.loading = Some((fetch_id, path));
+    }
+
+    /// adds the comments or replies once they arrive
+    fn comments_loaded(
+        &mut self,
+        framework: &mut FrameworkClean,
+        path: Option<Vec<usize>>,
+        res: Result<Comments, String>,
+    ) {
+        let res = match res {
+            Ok(res) => res,
+            Err(e) => {
+                *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(e);
+                return;
+            }
+        };
+
+        match path {
+            Some(path) => {
+                if let Some(node) = self.node_mut(&path) {
+                    node.replies
+                        .extend(res.comments.into_iter().map(CommentNode::new));
+                    node.continuation = res.continuation;
+                }
+            }
+            None => self.extend(res),
+        }
+
+        self.update_rows();
+        self.set_env(framework);
+    }
+
+    fn extend(&mut self, comments: Comments) {
+        self.comment_count = comments.comment_count.or(self.comment_count);
+        self.comments
+            .extend(comments.comments.into_iter().map(CommentNode::new));
+        self.continuation = comments.continuation;
+    }
+
+    /// handles select (enter)
+    fn select_at_cursor(&mut self, framework: &mut FrameworkClean) {
+        match self.rows.get(self.textlist.selected).cloned() {
+            Some(CommentRow::Comment(path)) | Some(CommentRow::Replies(path)) => {
+                let node = self.node_mut(&path).unwrap();
+                if !node.has_replies() {
+                    return;
+                }
+
+                node.expanded = !node.expanded;
+                if node.expanded && node.replies.is_empty() {
+                    self.load_replies(framework, &path)
+                }
+            }
+            Some(CommentRow::MoreReplies(path)) => self.load_replies(framework, &path),
+            Some(CommentRow::MoreComments) => self.load_more(framework),
+            None => return,
+        }
+
+        self.update_rows();
+        self.set_env(framework);
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::RenderAll);
+    }
+
+    fn set_env(&self, framework: &mut FrameworkClean) {
+        let (channel_id, channel) = match self.hovered() {
+            Some(comment) => (comment.author_id.clone(), comment.author.clone()),
+            None => (String::from("not avaliable"), String::from("not avaliable")),
+        };
+
+        set_envs(
+            [
+                (String::from("hover-channel-id"), channel_id),
+                (String::from("hover-channel"), channel),
+            ]
+            .into_iter(),
+            &mut framework.data.state.get_mut::<StateEnvs>().unwrap().0,
+        );
+    }
+
+    fn update_appearance(
+        &mut self,
+        appearance: &AppearanceConfig,
+        mainconfig: &MainConfig,
+        iteminfo: &tui_additions::framework::ItemInfo,
+    ) {
+        self.textlist.set_ascii_only(!mainconfig.allow_unicode);
+        self.grid.set_border_type(appearance.borders);
+        self.textlist.set_border_type(appearance.borders);
+        self.textlist
+            .set_style(Style::default().fg(appearance.colors.text));
+
+        if iteminfo.selected {
+            self.grid
+                .set_border_style(Style::default().fg(appearance.colors.outline_selected));
+            self.textlist
+                .set_cursor_style(Style::default().fg(appearance.colors.outline_hover));
+            self.textlist
+                .set_selected_style(Style::default().fg(appearance.colors.text_special));
+        } else {
+            self.textlist
+                .set_cursor_style(Style::default().fg(appearance.colors.outline_secondary));
+            self.textlist
+                .set_selected_style(Style::default().fg(appearance.colors.text_secondary));
+            if iteminfo.hover {
+                self.grid
+                    .set_border_style(Style::default().fg(appearance.colors.outline_hover));
+            } else {
+                self.grid
+                    .set_border_style(Style::default().fg(appearance.colors.outline));
+            }
+        }
+    }
+
+    /// renders the hovered comment in the right cell
+    fn render_hovered(
+        &self,
+        frame: &mut ratatui::Frame,
+        appearance: &AppearanceConfig,
+        area: Rect,
+    ) {
+        let comment = match self.hovered() {
+            Some(comment) => comment,
+            None => {
+                let text = match self.comment_count {
+                    Some(count) => format!("Showing {} of {count} comments", self.comments.len()),
+                    None => format!("Showing {} comments", self.comments.len()),
+                };
+                frame.render_widget(
+                    Paragraph::new(text)
+                        .style(Style::default().fg(appearance.colors.item_info.page_turner)),
+                    Rect { height: 1, ..area },
+                );
+                return;
+            }
+        };
+
+        let mut spans = vec![
+            (
+                comment.author.clone(),
+                Style::default().fg(appearance.colors.item_info.author),
+            ),
+            (
+                comment.published_text.clone(),
+                Style::default().fg(appearance.colors.item_info.published),
+            ),
+            (
+                format!("{} likes", viewcount_text(comment.likes as u64)),
+                Style::default().fg(appearance.colors.item_info.likes),
+            ),
+        ];
+
+        if comment.channel_owner {
+            spans.push((
+                String::from("[Channel owner]"),
+                Style::default().fg(appearance.colors.item_info.tag),
+            ));
+        }
+        if comment.verified {
+            spans.push((
+                String::from("[Verified]"),
+                Style::default().fg(appearance.colors.item_info.tag),
+            ));
+        }
+        if let Some(heart) = &comment.heart {
+            spans.push((
+                format!("[Hearted by {}]", heart.name),
+                Style::default().fg(appearance.colors.item_info.tag),
+            ));
+        }
+        if comment.edited {
+            spans.push((
+                String::from("[Edited]"),
+                Style::default().fg(appearance.colors.item_info.tag),
+            ));
+        }
+
+        let mut y = area.y;
+        let bottom = area.bottom();
+
+        for (text, style) in spans.into_iter().take((bottom - y) as usize) {
+            frame.render_widget(
+                Paragraph::new(text).style(style),
+                Rect {
+                    y,
+                    height: 1,
+                    ..area
+                },
+            );
+            y += 1;
+        }
+
+        if y + 1 >= bottom {
+            return;
+        }
+
+        frame.render_widget(
+            Paragraph::new(comment.content.as_str())
+                .style(Style::default().fg(appearance.colors.item_info.description))
+                .wrap(Wrap { trim: true }),
+            Rect {
+                y: y + 1,
+                height: bottom - y - 1,
+                ..area
+            },
+        );
+    }
+}
+
+impl FrameworkItem for CommentsView {
+    fn message(
+        &mut self,
+        framework: &mut FrameworkClean,
+        data: std::collections::HashMap<String, Box<dyn std::any::Any>>,
+    ) -> bool {
+        if !data.contains_key("type") {
+            return false;
+        }
+
+        if let Some(res) = Fetched::take(&data, self.loading.as_ref().map(|(id, _)| *id)) {
+            let (_, path) = self.loading.take().unwrap();
+            self.comments_loaded(framework, path, res);
+            return true;
+        }
+
+        let updated = data.get("type").is_some_and(|v| {
+            v.downcast_ref::<String>()
+                .is_some_and(|v| match v.as_str() {
+                    "scrollup" => self.textlist.up().is_ok(),
+                    "scrolldown" => self.textlist.down().is_ok(),
+                    _ => false,
+                })
+        });
+
+        if updated {
+            self.set_env(framework);
+        }
+
+        updated
+    }
+
+    fn render(
+        &mut self,
+        frame: &mut ratatui::Frame,
+        framework: &mut FrameworkClean,
+        area: Rect,
+        popup_render: bool,
+        info: tui_additions::framework::ItemInfo,
+    ) {
+        if popup_render {
+            return;
+        }
+
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+        let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+
+        self.update_appearance(appearance, mainconfig, &info);
+
+        let chunks = self.grid.chunks(area).unwrap()[0].clone();
+
+        self.textlist.set_height(chunks[0].height);
+
+        frame.render_widget(self.grid.clone(), area);
+        frame.render_widget(self.textlist.clone(), chunks[0]);
+        self.render_hovered(frame, appearance, chunks[1]);
+    }
+
+    fn selectable(&self) -> bool {
+        true
+    }
+
+    fn load_item(
+        &mut self,
+        framework: &mut FrameworkClean,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn Error>> {
+        *self = Self::default();
+
+        let page = framework.data.state.get::<Page>().unwrap();
+
+        let id = if let Page::Comments(id) = page {
+            id.clone()
+        } else {
+            unreachable!("item `CommentsView` cannot be used in {page:?}")
+        };
+
+        self.extend(Self::fetch(page).unwrap()()?);
+        self.id = id;
+        self.update_rows();
+
+        set_envs(
+            [(String::from("id"), self.id.clone())].into_iter(),
+            &mut framework.data.state.get_mut::<StateEnvs>().unwrap().0,
+        );
+        self.set_env(framework);
+
+        Ok(())
+    }
+
+    fn key_event(
+        &mut self,
+        framework: &mut FrameworkClean,
+        key: crossterm::event::KeyEvent,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn Error>> {
+        let action = if let Some(keyactions) = framework
+            .data
+            .global
+            .get::<KeyBindingsConfig>()
+            .unwrap()
+            .get(key)
+        {
+            keyactions
+        } else {
+            return Ok(());
+        };
+
+        let updated = match action {
+            KeyAction::MoveUp => self.textlist.up().is_ok(),
+            KeyAction::MoveDown => self.textlist.down().is_ok(),
+            KeyAction::MoveLeft | KeyAction::First => self.textlist.first().is_ok(),
+            KeyAction::MoveRight | KeyAction::End => self.textlist.last().is_ok(),
+            KeyAction::Select => {
+                self.select_at_cursor(framework);
+                return Ok(());
+            }
+            _ => false,
+        };
+
+        if updated {
+            self.set_env(framework);
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+        }
+
+        Ok(())
+    }
+
+    fn mouse_event(
+        &mut self,
+        framework: &mut FrameworkClean,
+        x: u16,
+        y: u16,
+        _absolute_x: u16,
+        _absolute_y: u16,
+    ) -> bool {
+        let chunk = self
+            .grid
+            .chunks(
+                if let Some(prev_frame) = framework.data.global.get::<Status>().unwrap().prev_frame
+                {
+                    prev_frame
+                } else {
+                    return false;
+                },
+            )
+            .unwrap()[0][0];
+
+        if !chunk.intersects(Rect::new(x, y, 1, 1)) {
+            return false;
+        }
+
+        let previously_selected = self.textlist.selected;
+
+        match self.row_at(y - chunk.y) {
+            // clicking on already selected item
+            Some(row) if row == previously_selected => {
+                self.select_at_cursor(framework);
+                return true;
+            }
+            Some(row) => {
+                let _ = self.textlist.set_selected(row);
+            }
+            // clicking on lines after the last item
+            None => {
+                let _ = self.textlist.last();
+            }
+        }
+
+        if self.textlist.selected == previously_selected {
+            return false;
+        }
+
+        self.set_env(framework);
+        true
+    }
+}
@@ -0,0 +1,279 @@
+use ratatui::{
+    layout::Constraint,
+    style::Style,
+    widgets::{Paragraph, Wrap},
+};
+use tui_additions::{
+    framework::{FrameworkClean, FrameworkItem},
+    widgets::{Grid, TextList},
+};
+
+use crate::{
+    config::{AppearanceConfig, KeyBindingsConfig},
+    global::structs::*,
+};
+
+/// lists instances in the Invidious instance pool, selecting one pins or unpins it
+#[derive(Clone)]
+pub struct InstanceList {
+    pub selector: TextList,
+    pub grid: Grid,
+    pub instances: Vec<Instance>,
+    pub pinned: Option<String>,
+    pub last_used: Option<String>,
+}
+
+impl Default for InstanceList {
+    fn default() -> Self {
+        Self {
+            selector: TextList::default(),
+            grid: Grid::new(
+                vec![Constraint::Percentage(50), Constraint::Percentage(50)],
+                vec![Constraint::Percentage(100)],
+            )
+            .unwrap(),
+            instances: Vec::new(),
+            pinned: None,
+            last_used: None,
+        }
+    }
+}
+
+impl InstanceList {
+    fn update_appearance(
+        &mut self,
+        info: &tui_additions::framework::ItemInfo,
+        appearance: &AppearanceConfig,
+    ) {
+        if info.selected {
+            self.grid
+                .set_border_style(Style::default().fg(appearance.colors.outline_selected));
+            self.selector
+                .set_cursor_style(Style::default().fg(appearance.colors.outline_hover));
+        } else if info.hover {
+            self.grid
+                .set_border_style(Style::default().fg(appearance.colors.outline_hover));
+            self.selector
+                .set_cursor_style(Style::default().fg(appearance.colors.outline_secondary));
+        } else {
+            self.grid
+                .set_border_style(Style::default().fg(appearance.colors.outline));
+            self.selector
+                .set_cursor_style(Style::default().fg(appearance.colors.outline));
+        }
+    }
+
+    /// copies the instances from the pool
+    fn refresh(&mut self) {
+        let pool = InstancePool::global();
+        self.instances = pool.instances.clone();
+        self.pinned = pool.pinned.clone();
+        self.last_used = pool.last_used.clone();
+        drop(pool);
+
+        let _ = self.selector.set_items(
+            &self
+                .instances
+                .iter()
+                .map(|instance| {
+                    format!(
+                        "{instance}{}",
+                        if self.pinned.as_ref() == Some(&instance.url) {
+                            " [pinned]"
+                        } else {
+                            ""
+                        }
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        if self.selector.selected >= self.selector.items.len() {
+            let _ = self.selector.last();
+        }
+    }
+
+    fn toggle_pin(&mut self, framework: &mut FrameworkClean) {
+        let Some(instance) = self.instances.get(self.selector.selected) else {
+            return;
+        };
+
+        let mut pool = InstancePool::global();
+        let message = if pool.pinned.as_ref() == Some(&instance.url) {
+            pool.pinned = None;
+            Message::Success(String::from(
+                "Unpinned, requests go to the fastest instance",
+            ))
+        } else {
+            match pool.pin(&instance.url) {
+                Ok(url) => Message::Success(format!("Pinned {url}")),
+                Err(e) => Message::Error(e),
+            }
+        };
+        drop(pool);
+
+        *framework.data.global.get_mut::<Message>().unwrap() = message;
+        self.refresh();
+    }
+
+    fn details(&self) -> String {
+        let Some(instance) = self.instances.get(self.selector.selected) else {
+            return String::from(
+                "No Invidious instances, add some to `invidious_instances` in main.yml",
+            );
+        };
+
+        let mut lines = vec![
+            instance.url.clone(),
+            String::new(),
+            format!(
+                "Status: {}",
+                match instance.healthy {
+                    Some(true) => "up",
+                    Some(false) => "down",
+                    None => "unknown",
+                }
+            ),
+            format!(
+                "Latency: {}",
+                instance
+                    .latency
+                    .map(|latency| format!("{}ms", latency.as_millis()))
+                    .unwrap_or(String::from("-"))
+            ),
+            format!("Failures in a row: {}", instance.failures),
+        ];
+
+        if let Some(e) = &instance.last_error {
+            lines.push(format!("Last error: {e}"));
+        }
+        if self.last_used.as_ref() == Some(&instance.url) {
+            lines.push(String::from("Answered the last request"));
+        }
+
+        lines.push(String::new());
+        lines.push(String::from(
+            if self.pinned.as_ref() == Some(&instance.url) {
+                "Pinned, select again to unpin"
+            } else {
+                "Select to pin this instance"
+            },
+        ));
+
+        lines.join("\n")
+    }
+}
+
+impl FrameworkItem for InstanceList {
+    fn load_item(
+        &mut self,
+        framework: &mut FrameworkClean,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+        self.selector.set_border_type(appearance.borders);
+        self.grid.set_border_type(appearance.borders);
+
+        if InstancePool::global().unprobed() {
+            InstancePool::probe_all();
+        }
+
+        self.refresh();
+        Ok(())
+    }
+
+    fn render(
+        &mut self,
+        frame: &mut ratatui::Frame,
+        framework: &mut FrameworkClean,
+        area: ratatui::layout::Rect,
+        popup_render: bool,
+        info: tui_additions::framework::ItemInfo,
+    ) {
+        if popup_render {
+            return;
+        }
+
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+
+        self.update_appearance(&info, appearance);
+        let chunks = self.grid.chunks(area).unwrap()[0].clone();
+        frame.render_widget(self.grid.clone(), area);
+        self.selector.set_height(chunks[0].height);
+        frame.render_widget(self.selector.clone(), chunks[0]);
+        frame.render_widget(
+            Paragraph::new(self.details()).wrap(Wrap { trim: true }),
+            chunks[1],
+        );
+    }
+
+    fn message(
+        &mut self,
+        framework: &mut FrameworkClean,
+        data: std::collections::HashMap<String, Box<dyn std::any::Any>>,
+    ) -> bool {
+        let updated = data.get("type").is_some_and(|v| {
+            v.downcast_ref::<String>()
+                .is_some_and(|v| match v.as_str() {
+                    "scrollup" => self.selector.up().is_ok(),
+                    "scrolldown" => self.selector.down().is_ok(),
+                    _ => false,
+                })
+        });
+
+        if updated {
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+        }
+
+        updated
+    }
+
+    fn key_event(
+        &mut self,
+        framework: &mut FrameworkClean,
+        key: crossterm::event::KeyEvent,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let action = if let Some(action) = framework
+            .data
+            .global
+            .get::<KeyBindingsConfig>()
+            .unwrap()
+            .get(key)
+        {
+            action
+        } else {
+            return Ok(());
+        };
+
+        let updated = match action {
+            KeyAction::MoveDown => self.selector.down().is_ok(),
+            KeyAction::MoveUp => self.selector.up().is_ok(),
+            KeyAction::MoveLeft | KeyAction::First => self.selector.first().is_ok(),
+            KeyAction::MoveRight | KeyAction::End => self.selector.last().is_ok(),
+            KeyAction::Select => {
+                self.toggle_pin(framework);
+                true
+            }
+            _ => false,
+        };
+
+        if updated {
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+        }
+
+        Ok(())
+    }
+}
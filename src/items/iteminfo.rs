@@ -99,6 +99,14 @@ impl FrameworkItem for ItemInfo {
         self.lazy_scroll = scroll;
 
         let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+        let watched = item.id().and_then(|id| {
+            framework
+                .data
+                .global
+                .get::<WatchPositions>()
+                .unwrap()
+                .percentage(id)
+        });
 
         // Each "span" contains a string and a Style, and they are one line max each
         // A "text" is used for descriptions in video/playlist and channels, and starts a new line if the old one runs out
@@ -132,6 +140,12 @@ impl FrameworkItem for ItemInfo {
                     format!("Length: {}", minivideo.length),
                     Style::default().fg(appearance.colors.item_info.length),
                 ));
+                if let Some(watched) = watched {
+                    out.0.push((
+                        format!("Watched {watched}%"),
+                        Style::default().fg(appearance.colors.item_info.length),
+                    ));
+                }
                 out.0.push((
                     format!("Uploaded by {}", minivideo.channel),
                     Style::default().fg(appearance.colors.item_info.author),
@@ -210,45 +224,59 @@ impl FrameworkItem for ItemInfo {
                     Style::default().fg(appearance.colors.item_info.description),
                 )),
             ),
-            Item::FullVideo(fullvideo) => (
-                vec![
-                    (
-                        String::from("[Video]"),
-                        Style::default().fg(appearance.colors.item_info.tag),
-                    ),
-                    (
-                        fullvideo.title.clone(),
-                        Style::default().fg(appearance.colors.item_info.title),
-                    ),
-                    (
-                        format!("{} views", fullvideo.views),
-                        Style::default().fg(appearance.colors.item_info.viewcount),
-                    ),
-                    (
-                        format!("{} likes", fullvideo.likes),
-                        Style::default().fg(appearance.colors.item_info.likes),
-                    ),
-                    (
-                        format!("Length: {}", fullvideo.length),
-                        Style::default().fg(appearance.colors.item_info.length),
-                    ),
-                    (
-                        format!(
-                            "Uploaded by {} ({} subscribers)",
-                            fullvideo.channel, fullvideo.sub_count
+            Item::FullVideo(fullvideo) => {
+                let mut out = (
+                    vec![
+                        (
+                            String::from("[Video]"),
+                            Style::default().fg(appearance.colors.item_info.tag),
                         ),
-                        Style::default().fg(appearance.colors.item_info.author),
-                    ),
-                    (
-                        format!("Published {}", fullvideo.published),
-                        Style::default().fg(appearance.colors.item_info.published),
-                    ),
-                ],
-                Some((
-                    fullvideo.description.clone(),
-                    Style::default().fg(appearance.colors.item_info.description),
-                )),
-            ),
+                        (
+                            fullvideo.title.clone(),
+                            Style::default().fg(appearance.colors.item_info.title),
+                        ),
+                        (
+                            format!("{} views", fullvideo.views),
+                            Style::default().fg(appearance.colors.item_info.viewcount),
+                        ),
+                        (
+                            format!("{} likes", fullvideo.likes),
+                            Style::default().fg(appearance.colors.item_info.likes),
+                        ),
+                        (
+                            format!("Length: {}", fullvideo.length),
+                            Style::default().fg(appearance.colors.item_info.length),
+                        ),
+                        (
+                            format!(
+                                "Uploaded by {} ({} subscribers)",
+                                fullvideo.channel, fullvideo.sub_count
+                            ),
+                            Style::default().fg(appearance.colors.item_info.author),
+                        ),
+                        (
+                            format!("Published {}", fullvideo.published),
+                            Style::default().fg(appearance.colors.item_info.published),
+                        ),
+                    ],
+                    Some((
+                        fullvideo.description.clone(),
+                        Style::default().fg(appearance.colors.item_info.description),
+                    )),
+                );
+                if let Some(watched) = watched {
+                    // below the length
+                    out.0.insert(
+                        5,
+                        (
+                            format!("Watched {watched}%"),
+                            Style::default().fg(appearance.colors.item_info.length),
+                        ),
+                    );
+                }
+
+                out
+            }
             Item::FullPlaylist(fullplaylist) => (
                 vec![
                     (
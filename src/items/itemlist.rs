@@ -22,6 +22,12 @@ pub struct ItemList {
     pub items: Vec<Item>,
     pub textlist: TextList,
     pub grid: Grid,
+    /// token for the next page of items, loaded when the cursor reaches the last item
+    pub continuation: Option<String>,
+    /// id of the `BackgroundFetch` loading the next page
+    pub loading: Option<u64>,
+    /// id of the `BackgroundFetch` getting info of the hovered imported video
+    pub resolving: Option<u64>,
 }
 
 impl ItemList {
@@ -267,6 +273,9 @@ impl Default for ItemList {
                 vec![Constraint::Percentage(100)],
             )
             .unwrap(),
+            continuation: None,
+            loading: None,
+            resolving: None,
         }
     }
 }
@@ -281,6 +290,18 @@ impl FrameworkItem for ItemList {
             return false;
         }
 
+        if let Some(res) = Fetched::take(&data, self.loading) {
+            self.loading = None;
+            self.more_loaded(framework, res);
+            return true;
+        }
+
+        if let Some(res) = Fetched::take(&data, self.resolving) {
+            self.resolving = None;
+            self.resolved(framework, res);
+            return true;
+        }
+
         let updated = data.get("type").is_some_and(|v| {
             v.downcast_ref::<String>()
                 .is_some_and(|v| match v.as_str() {
@@ -291,6 +312,7 @@ impl FrameworkItem for ItemList {
         });
 
         if updated && !self.items.is_empty() {
+            self.load_more(framework);
             self.update(framework);
             set_envs(
                 self.infalte_item_update(
@@ -363,6 +385,19 @@ impl FrameworkItem for ItemList {
         frame.render_widget(grid, area);
         frame.render_widget(textlist, chunks[0]);
 
+        let watched = framework.data.global.get::<WatchedVideos>().unwrap();
+        dim_rows(
+            frame.buffer_mut(),
+            &self.textlist,
+            chunks[0],
+            watched_style(appearance),
+            |index| {
+                self.items[index]
+                    .id()
+                    .is_some_and(|id| watched.contains(id))
+            },
+        );
+
         // used the `.render()` function in self.info because it is an ItemInfo and impls FrameworkItem instead of Widget
         self.info
             .render(frame, framework, chunks[1], popup_render, info);
@@ -380,71 +415,60 @@ impl FrameworkItem for ItemList {
         *self = Self::default();
 
         let page = framework.data.state.get::<Page>().unwrap();
-        let image_index = framework
-            .data
-            .global
-            .get::<MainConfig>()
-            .unwrap()
-            .image_index;
 
-        let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
-
-        // fetch the items using the invidious api
         match page {
-            Page::MainMenu(MainMenuPage::Trending) => {
-                self.items = SearchProviderWrapper::trending()?
-                    .into_iter()
-                    .map(|item| Item::from_common_video(item, image_index))
-                    .collect();
-            }
-            Page::MainMenu(MainMenuPage::Popular) => {
-                self.items = SearchProviderWrapper::popular()?
-                    .into_iter()
-                    .map(|item| Item::from_popular_item(item, image_index))
-                    .collect();
+            // fetch the items using the search provider
+            Page::MainMenu(MainMenuPage::Trending | MainMenuPage::Popular)
+            | Page::Search(_)
+            | Page::Related(_) => {
+                let fetch = Self::fetch(page, framework.data.global.get::<MainConfig>().unwrap());
+                (self.items, self.continuation) = fetch.unwrap()()?;
             }
             Page::MainMenu(MainMenuPage::Library) => {
                 let history = framework.data.global.get::<Library>().unwrap();
                 self.items = history.0.clone().into_iter().rev().collect();
             }
+            Page::MainMenu(MainMenuPage::Queue) => {
+                // unlike history, the item that plays next is on top
+                let queue = framework.data.global.get::<PlayQueue>().unwrap();
+                self.items = queue.items.clone();
+            }
             Page::MainMenu(MainMenuPage::History) => {
                 // the vector needs to be reversed because the latest watch history is pushed to
                 // the back, meaning it needs to be reversed so that the latests one are on top
                 let history = framework.data.global.get::<WatchHistory>().unwrap();
                 self.items = history.0.clone().into_iter().rev().collect();
             }
-            Page::Search(search) => {
-                self.items = SearchProviderWrapper::search(search)?
-                    .into_iter()
-                    .map(|item| Item::from_search_item(item, image_index))
-                    .filter(|item| {
-                        let channel_id = match item.clone() {
-                            Item::MiniVideo(video) => Some(video.channel_id),
-                            Item::MiniPlaylist(playlist) => Some(playlist.channel_id),
-                            Item::MiniChannel(channel) => Some(channel.id),
-                            Item::FullVideo(video) => Some(video.channel_id),
-                            Item::FullPlaylist(playlist) => Some(playlist.channel_id),
-                            Item::FullChannel(channel) => Some(channel.id),
-                            _ => None,
-                        };
-
-                        !channel_id.is_some_and(|channel_id| {
-                            mainconfig.block_list.channels.contains(&channel_id)
-                        })
-                    })
-                    .collect();
-                if !self.items.is_empty() {
-                    self.items.push(Item::Page(true));
-                }
-                if search.page != 1 {
-                    self.items.insert(0, Item::Page(false));
+            Page::LocalSearch(query) => {
+                let global = &mut framework.data.global;
+                if !global.get::<LocalIndex>().unwrap().is_built() {
+                    let index = LocalIndex::build(
+                        global.get::<Library>().unwrap(),
+                        global.get::<WatchHistory>().unwrap(),
+                        global.get::<Subscriptions>().unwrap(),
+                        &global.get::<MainConfig>().unwrap().captions_language,
+                    );
+                    global.insert::<LocalIndex>(index);
                 }
+                self.items = global.get::<LocalIndex>().unwrap().search(query);
             }
             _ => unreachable!("item `ItemList` cannot be used in `{page:?}`"),
         }
 
-        // update the items in text list
-        self.textlist.set_items(&self.items).unwrap();
+        // pages that the user put videos in themselves are never filtered
+        if !matches!(
+            page,
+            Page::MainMenu(MainMenuPage::Library | MainMenuPage::Queue | MainMenuPage::History)
+                | Page::LocalSearch(_)
+        ) {
+            let watched = framework.data.global.get::<WatchedVideos>().unwrap();
+            let filters = framework.data.global.get::<Filters>().unwrap();
+            self.items.retain(|item| {
+                !item.id().is_some_and(|id| watched.hidden(id)) && filters.allowed(item)
+            });
+        }
+
+        self.set_textlist(framework);
         self.update(framework);
 
         let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
@@ -496,6 +520,7 @@ impl FrameworkItem for ItemList {
 
         // only create a render task if the key event actually changed something
         if updated && !self.items.is_empty() {
+            self.load_more(framework);
             self.update(framework);
             set_envs(
                 self.infalte_item_update(
@@ -574,6 +599,7 @@ impl FrameworkItem for ItemList {
             return false;
         }
 
+        self.load_more(framework);
         self.update(framework);
         set_envs(
             self.infalte_item_update(
@@ -597,6 +623,164 @@ impl FrameworkItem for ItemList {
 }
 
 impl ItemList {
+    /// items of the page from the search provider and the token of the next page, `None` if the
+    /// page shows local data
+    pub fn fetch(
+        page: &Page,
+        mainconfig: &MainConfig,
+    ) -> Option<Fetch<(Vec<Item>, Option<String>)>> {
+        let image_index = mainconfig.image_index;
+        Some(match page.clone() {
+            Page::MainMenu(MainMenuPage::Trending) => Box::new(move || {
+                let trending = SearchProviderWrapper::trending(None)?;
+                Ok((
+                    trending
+                        .videos
+                        .into_iter()
+                        .map(|item| Item::from_common_video(item, image_index))
+                        .collect(),
+                    trending.continuation,
+                ))
+            }),
+            Page::MainMenu(MainMenuPage::Popular) => Box::new(move || {
+                Ok((
+                    SearchProviderWrapper::popular()?
+                        .into_iter()
+                        .map(|item| Item::from_popular_item(item, image_index))
+                        .collect(),
+                    None,
+                ))
+            }),
+            Page::Search(search) => Box::new(move || {
+                let mut items = SearchProviderWrapper::search(&search)?
+                    .into_iter()
+                    .map(|item| Item::from_search_item(item, image_index))
+                    .collect::<Vec<_>>();
+                if !items.is_empty() {
+                    items.push(Item::Page(true));
+                }
+                if search.page != 1 {
+                    items.insert(0, Item::Page(false));
+                }
+                Ok((items, None))
+            }),
+            Page::Related(id) => Box::new(move || {
+                Ok((
+                    SearchProviderWrapper::video(&id)?
+                        .recommended_videos
+                        .into_iter()
+                        .map(|item| Item::from_video_short(item, image_index))
+                        .collect(),
+                    None,
+                ))
+            }),
+            _ => return None,
+        })
+    }
+
+    /// loads the next page in the background when the cursor reaches the last item, only trending
+    /// has more pages
+    fn load_more(&mut self, framework: &mut FrameworkClean) {
+        if self.textlist.selected + 1 >= self.items.len() {
+            self.fetch_more(framework);
+        }
+    }
+
+    /// starts loading the next page if there is one and it is not already loading
+    fn fetch_more(&mut self, framework: &mut FrameworkClean) {
+        if self.loading.is_some() {
+            return;
+        }
+        let continuation = if let Some(continuation) = self.continuation.clone() {
+            continuation
+        } else {
+            return;
+        };
+
+        let image_index = framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .image_index;
+        self.loading = Some(
+            framework
+                .data
+                .global
+                .get_mut::<BackgroundFetch>()
+                .unwrap()
+                .start(Box::new(move || {
+                    let trending = SearchProviderWrapper::trending(Some(&continuation))?;
+                    Ok((
+                        trending
+                            .videos
+                            .into_iter()
+                            .map(|video| Item::from_common_video(video, image_index))
+                            .collect::<Vec<_>>(),
+                        trending.continuation,
+                    ))
+                })),
+        );
+    }
+
+    /// adds the next page once it arrives
+    fn more_loaded(
+        &mut self,
+        framework: &mut FrameworkClean,
+        res: Result<(Vec<Item>, Option<String>), String>,
+    ) {
+        let (items, continuation) = match res {
+            Ok(res) => res,
+            // the token is kept, so moving to the last item again retries
+            Err(e) => {
+                *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(e);
+                return;
+            }
+        };
+
+        let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+        let watched = framework.data.global.get::<WatchedVideos>().unwrap();
+        let filters = framework.data.global.get::<Filters>().unwrap();
+        let loaded = items.len();
+        let items = items
+            .into_iter()
+            .filter(|item| !item.id().is_some_and(|id| watched.hidden(id)) && filters.allowed(item))
+            .collect::<Vec<_>>();
+        if mainconfig.images.display() {
+            download_all_images(items.iter().map(|item| item.into()).collect());
+        }
+
+        // every item of the page is filtered out, so the cursor has nothing new to reach
+        let refetch = items.is_empty() && loaded != 0;
+        self.items.extend(items);
+        self.continuation = continuation;
+        self.textlist.set_items(&self.items).unwrap();
+
+        if refetch {
+            self.fetch_more(framework);
+        }
+    }
+
+    /// update the items in text list, watch history also shows how much of each video is watched
+    fn set_textlist(&mut self, framework: &FrameworkClean) {
+        if framework.data.state.get::<Page>().unwrap() == &Page::MainMenu(MainMenuPage::History) {
+            let positions = framework.data.global.get::<WatchPositions>().unwrap();
+            let labels = self
+                .items
+                .iter()
+                .map(
+                    |item| match item.id().and_then(|id| positions.percentage(id)) {
+                        Some(watched) => format!("{item} [watched {watched}%]"),
+                        None => item.to_string(),
+                    },
+                )
+                .collect::<Vec<_>>();
+            self.textlist.set_items(&labels).unwrap();
+        } else {
+            self.textlist.set_items(&self.items).unwrap();
+        }
+    }
+
     // change `self.item` to the currently selected item
     pub fn update(&mut self, framework: &mut FrameworkClean) {
         let selected = self.items.get(self.textlist.selected);
@@ -624,5 +808,72 @@ impl ItemList {
         }
 
         self.info.item = Some(self.items[self.textlist.selected].clone());
+        self.resolve_hovered(framework);
+    }
+
+    /// fetches info of the hovered video in the background if it is imported and has not been
+    /// fetched
+    fn resolve_hovered(&mut self, framework: &mut FrameworkClean) {
+        let id = match &self.items[self.textlist.selected] {
+            item if item.is_unresolved() && self.resolving.is_none() => {
+                item.id().unwrap().to_string()
+            }
+            _ => return,
+        };
+
+        let image_index = framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .image_index;
+        self.resolving = Some(
+            framework
+                .data
+                .global
+                .get_mut::<BackgroundFetch>()
+                .unwrap()
+                .start(Box::new(move || {
+                    Ok(Item::from_full_video(
+                        SearchProviderWrapper::video(&id)?,
+                        image_index,
+                    ))
+                })),
+        );
+    }
+
+    /// replaces the imported video with its info everywhere it is saved
+    fn resolved(&mut self, framework: &mut FrameworkClean, res: Result<Item, String>) {
+        let item = match res {
+            Ok(item) => item,
+            // the video is kept by its id, such as when it has been removed
+            Err(e) => {
+                *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(e);
+                return;
+            }
+        };
+
+        let id = item.id().unwrap().to_string();
+        LocalStore::set_info(id.clone(), item.clone(), true);
+        download_item_images(&item, framework.data.global.get::<MainConfig>().unwrap());
+
+        let replace = |items: &mut Vec<Item>| {
+            items
+                .iter_mut()
+                .filter(|saved| saved.id() == Some(&id))
+                .for_each(|saved| *saved = item.clone())
+        };
+        replace(&mut framework.data.global.get_mut::<WatchHistory>().unwrap().0);
+        replace(&mut framework.data.global.get_mut::<Library>().unwrap().0);
+        framework
+            .data
+            .global
+            .get_mut::<LocalIndex>()
+            .unwrap()
+            .invalidate();
+        replace(&mut self.items);
+
+        self.set_textlist(framework);
+        self.update(framework);
     }
 }
@@ -9,24 +9,16 @@ use ratatui::{
 use tui_additions::framework::*;
 
 /// a message bar item, contains no fields because the message is taken from `data.global.Message`
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default)]
 pub enum MessageBar {
+    #[default]
     Stateless,
     /// start and end position of the seeker for mouse scrubbing
-    Music {
-        seeker_front: u16,
-        seeker_rear: u16,
-    },
+    Music { seeker_front: u16, seeker_rear: u16 },
     /// bar too short for seeker to display
     MusicSeekerLess,
 }
 
-impl Default for MessageBar {
-    fn default() -> Self {
-        Self::Stateless
-    }
-}
-
 impl FrameworkItem for MessageBar {
     fn render(
         &mut self,
@@ -42,34 +34,14 @@ impl FrameworkItem for MessageBar {
 
         #[cfg(feature = "mpv")]
         {
-            let mpv = framework.data.global.get::<MpvWrapper>().unwrap();
+            let mpv = framework.data.global.get::<MpvState>().unwrap();
             if Self::is_mpv_render(framework) {
-                let mut label = mpv.property("media-title".to_string()).unwrap();
-
-                if let Some((name, ext)) = label.rsplit_once('.') {
-                    if ext.len() < 5
-                        && !name.len() > 13
-                        && &name[name.len() - 1..name.len()] == "]"
-                        && name[name.len() - 12..name.len() - 1]
-                            .chars()
-                            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
-                        && &name[name.len() - 13..name.len() - 12] == "["
-                    {
-                        label = name[0..name.len() - 13].to_string();
-                    }
-                }
+                let label = mpv.display_title().to_string();
 
-                let duration = mpv
-                    .property("duration".to_string())
-                    .unwrap_or_default()
-                    .parse::<f64>()
-                    .unwrap() as u64;
+                // duration is unknown until the file is loaded
+                let duration = (mpv.duration as u64).max(1);
                 let duration_s = secs_display_string(duration as u32);
-                let playerhead = mpv
-                    .property("time-pos".to_string())
-                    .unwrap_or_default()
-                    .parse::<f64>()
-                    .unwrap() as u64;
+                let playerhead = (mpv.time_pos as u64).min(duration);
                 let mut playerhead_s = secs_display_string(playerhead as u32);
                 if playerhead_s.len() != duration_s.len() {
                     playerhead_s = format!(
@@ -230,7 +202,7 @@ impl FrameworkItem for MessageBar {
 #[cfg(feature = "mpv")]
 impl MessageBar {
     pub fn is_mpv_render(framework: &FrameworkClean) -> bool {
-        framework.data.global.get::<MpvWrapper>().unwrap().playing()
+        framework.data.global.get::<MpvState>().unwrap().playing()
             && matches!(
                 framework.data.global.get::<Message>().unwrap(),
                 Message::Mpv(_) | Message::None
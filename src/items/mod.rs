@@ -15,7 +15,15 @@ mod singleitem;
 pub use singleitem::*;
 mod channeldisplay;
 pub use channeldisplay::*;
+mod channelsearch;
+pub use channelsearch::*;
 mod channellist;
 pub use channellist::*;
 mod videolist;
 pub use videolist::*;
+mod comments;
+pub use comments::*;
+mod nowplaying;
+pub use nowplaying::*;
+mod instancelist;
+pub use instancelist::*;
@@ -0,0 +1,160 @@
+use crate::config::*;
+#[cfg(feature = "mpv")]
+use crate::global::{functions::secs_display_string, structs::*};
+#[cfg(feature = "mpv")]
+use ratatui::{
+    layout::{Constraint, Layout},
+    widgets::Gauge,
+};
+use ratatui::{
+    style::Style,
+    widgets::{Block, Borders, Paragraph},
+};
+use tui_additions::framework::*;
+
+/// displays the title, progress, play state and volume of the embedded mpv player
+#[derive(Clone, Copy, Default)]
+pub struct NowPlaying {
+    /// x offset and width of the progress gauge for mouse seeking, `None` if nothing is playing
+    #[cfg(feature = "mpv")]
+    seeker: Option<(u16, u16)>,
+}
+
+impl FrameworkItem for NowPlaying {
+    fn render(
+        &mut self,
+        frame: &mut ratatui::Frame,
+        framework: &mut FrameworkClean,
+        area: ratatui::layout::Rect,
+        popup_render: bool,
+        _info: ItemInfo,
+    ) {
+        if popup_render {
+            return;
+        }
+
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(appearance.borders)
+            .border_style(Style::default().fg(appearance.colors.outline));
+
+        #[cfg(not(feature = "mpv"))]
+        frame.render_widget(
+            Paragraph::new("Feature `mpv` is disabled and not compiled")
+                .style(Style::default().fg(appearance.colors.text_error))
+                .block(block),
+            area,
+        );
+
+        #[cfg(feature = "mpv")]
+        {
+            let mpv = framework.data.global.get::<MpvState>().unwrap();
+
+            if !mpv.loaded {
+                self.seeker = None;
+                frame.render_widget(
+                    Paragraph::new("Nothing playing")
+                        .style(Style::default().fg(appearance.colors.text_secondary))
+                        .block(block),
+                    area,
+                );
+                return;
+            }
+
+            let block = block.title(format!(" {} ", mpv.display_title()));
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+
+            let state = if mpv.paused { "[Paused]" } else { "[Playing]" };
+            let volume = format!("Vol {}%", mpv.volume.round() as u32);
+            let chunks = Layout::horizontal([
+                Constraint::Length(state.len() as u16 + 1),
+                Constraint::Min(0),
+                Constraint::Length(volume.len() as u16 + 1),
+            ])
+            .split(inner);
+
+            // live streams have no duration
+            let (ratio, label) = if mpv.duration > 0. {
+                (
+                    (mpv.time_pos / mpv.duration).clamp(0., 1.),
+                    format!(
+                        "{}/{}",
+                        secs_display_string(mpv.time_pos as u32),
+                        secs_display_string(mpv.duration as u32)
+                    ),
+                )
+            } else {
+                (0., secs_display_string(mpv.time_pos as u32))
+            };
+
+            frame.render_widget(
+                Paragraph::new(state).style(Style::default().fg(appearance.colors.text)),
+                chunks[0],
+            );
+            frame.render_widget(
+                Gauge::default()
+                    .ratio(ratio)
+                    .label(label)
+                    .use_unicode(
+                        framework
+                            .data
+                            .global
+                            .get::<MainConfig>()
+                            .unwrap()
+                            .allow_unicode,
+                    )
+                    .gauge_style(Style::default().fg(appearance.colors.text_special)),
+                chunks[1],
+            );
+            frame.render_widget(
+                Paragraph::new(volume)
+                    .style(Style::default().fg(appearance.colors.text))
+                    .alignment(ratatui::layout::Alignment::Right),
+                chunks[2],
+            );
+
+            self.seeker = Some((chunks[1].x - area.x, chunks[1].width));
+        }
+    }
+
+    fn selectable(&self) -> bool {
+        false
+    }
+
+    // clicking on the gauge seeks to that position
+    #[cfg(feature = "mpv")]
+    fn mouse_passthrough(
+        &mut self,
+        framework: &mut FrameworkClean,
+        _selected: bool,
+        x: u16,
+        _y: u16,
+        _absolute_x: u16,
+        _absolute_y: u16,
+    ) -> bool {
+        let (start, width) = match self.seeker {
+            Some((start, width)) if width != 0 && x >= start && x < start + width => (start, width),
+            _ => return false,
+        };
+
+        let percentage = (x - start) as f64 * 100. / width as f64;
+        if let MpvResponse::Error(e) = framework.data.global.get::<MpvWrapper>().unwrap().command(
+            String::from("seek"),
+            vec![percentage.to_string(), String::from("absolute-percent")],
+        ) {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Error(format!("MPV error: {e}"));
+        }
+
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::RenderAll);
+        true
+    }
+}
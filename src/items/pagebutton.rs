@@ -15,10 +15,14 @@ pub enum PageButton {
     Popular,
     ChannelMain,
     ChannelVideos,
+    ChannelShorts,
+    ChannelStreams,
     ChannelPlaylists,
+    ChannelPodcasts,
     History,
     Feed,
     Library,
+    Queue,
 }
 
 impl PageButton {
@@ -34,13 +38,26 @@ impl PageButton {
                 id: current_page.channeldisplay().id.clone(),
                 r#type: ChannelDisplayPageType::Videos,
             }),
+            Self::ChannelShorts => Page::ChannelDisplay(ChannelDisplayPage {
+                id: current_page.channeldisplay().id.clone(),
+                r#type: ChannelDisplayPageType::Shorts,
+            }),
+            Self::ChannelStreams => Page::ChannelDisplay(ChannelDisplayPage {
+                id: current_page.channeldisplay().id.clone(),
+                r#type: ChannelDisplayPageType::Streams,
+            }),
             Self::ChannelPlaylists => Page::ChannelDisplay(ChannelDisplayPage {
                 id: current_page.channeldisplay().id.clone(),
                 r#type: ChannelDisplayPageType::Playlists,
             }),
+            Self::ChannelPodcasts => Page::ChannelDisplay(ChannelDisplayPage {
+                id: current_page.channeldisplay().id.clone(),
+                r#type: ChannelDisplayPageType::Podcasts,
+            }),
             Self::History => Page::MainMenu(MainMenuPage::History),
             Self::Feed => Page::Feed,
             Self::Library => Page::MainMenu(MainMenuPage::Library),
+            Self::Queue => Page::MainMenu(MainMenuPage::Queue),
         }
     }
 
@@ -62,10 +79,14 @@ impl Display for PageButton {
             Self::Trending => "Trending",
             Self::ChannelMain => "Main",
             Self::ChannelVideos => "Videos",
+            Self::ChannelShorts => "Shorts",
+            Self::ChannelStreams => "Streams",
             Self::ChannelPlaylists => "Playlists",
+            Self::ChannelPodcasts => "Podcasts",
             Self::History => "History",
             Self::Feed => "Feed",
             Self::Library => "Library",
+            Self::Queue => "Queue",
         })
     }
 }
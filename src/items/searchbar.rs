@@ -11,11 +11,14 @@ use crate::global::functions::{next_word, previous_word, remove_word};
 
 use crossterm::event::KeyCode;
 use ratatui::{
-    layout::Alignment,
+    layout::{Alignment, Rect},
     style::Style,
-    widgets::{Block, Borders},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tui_additions::{
+    framework::{FrameworkClean, FrameworkItem},
+    widgets::TextField,
 };
-use tui_additions::{framework::FrameworkItem, widgets::TextField};
 
 /// the search bar item
 #[derive(Clone, Default)]
@@ -23,6 +26,113 @@ pub struct SearchBar {
     pub text_field: TextField,
     pub history_index: Option<usize>,
     pub custom_value_cache: String,
+    /// the suggestions dropdown is only shown after typing something
+    pub show_suggestions: bool,
+    /// highlighted item in the suggestions dropdown
+    pub suggestion_index: Option<usize>,
+}
+
+impl SearchBar {
+    /// searches in the history starting with the current content (latest first), followed by
+    /// suggestions from the search provider
+    pub fn suggestions(&self, framework: &FrameworkClean) -> Vec<String> {
+        let limit = framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .search_suggestions;
+        let query = &self.text_field.content;
+        if !self.show_suggestions || limit == 0 || query.trim().is_empty() {
+            return Vec::new();
+        }
+
+        let prefix = query.to_lowercase();
+        let history = framework.data.global.get::<SearchHistory>().unwrap();
+        let provider = framework.data.global.get::<SearchSuggestions>().unwrap();
+
+        let mut suggestions: Vec<String> = Vec::new();
+        for suggestion in history
+            .0
+            .iter()
+            .rev()
+            .filter(|item| item.to_lowercase().starts_with(&prefix))
+            .chain(provider.suggestions.suggestions.iter())
+        {
+            if suggestions.len() == limit {
+                break;
+            }
+            if suggestion != query && !suggestions.contains(suggestion) {
+                suggestions.push(suggestion.clone());
+            }
+        }
+
+        suggestions
+    }
+
+    /// called when the content is changed by typing, fetches new suggestions
+    fn edited(&mut self, framework: &mut FrameworkClean) {
+        self.show_suggestions = true;
+        self.suggestion_index = None;
+        if framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .search_suggestions
+            != 0
+        {
+            framework
+                .data
+                .global
+                .get_mut::<SearchSuggestions>()
+                .unwrap()
+                .request(&self.text_field.content);
+        }
+    }
+
+    /// the dropdown below the search bar
+    fn render_suggestions(
+        &self,
+        frame: &mut ratatui::Frame,
+        framework: &FrameworkClean,
+        area: Rect,
+    ) {
+        let suggestions = self.suggestions(framework);
+        if suggestions.is_empty() {
+            return;
+        }
+
+        let y = area.bottom();
+        let area = Rect {
+            y,
+            height: (suggestions.len() as u16 + 2).min(frame.area().bottom().saturating_sub(y)),
+            ..area
+        };
+        // no space for even one suggestion
+        if area.height < 3 {
+            return;
+        }
+
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(appearance.borders)
+            .border_style(Style::default().fg(appearance.colors.outline_selected));
+        let inner = block.inner(area);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        for (index, (suggestion, row)) in suggestions.iter().zip(inner.rows()).enumerate() {
+            let style = Style::default().fg(if self.suggestion_index == Some(index) {
+                appearance.colors.outline_hover
+            } else {
+                appearance.colors.text
+            });
+            frame.render_widget(Paragraph::new(suggestion.as_str()).style(style), row);
+        }
+    }
 }
 
 impl FrameworkItem for SearchBar {
@@ -36,6 +146,9 @@ impl FrameworkItem for SearchBar {
         info: tui_additions::framework::ItemInfo,
     ) {
         if popup_render {
+            if info.selected {
+                self.render_suggestions(frame, framework, area);
+            }
             return;
         }
 
@@ -73,6 +186,8 @@ impl FrameworkItem for SearchBar {
         let search = framework.data.state.get::<Search>().unwrap().query.clone();
         self.text_field.content = search;
         let _ = self.text_field.last();
+        self.show_suggestions = false;
+        self.suggestion_index = None;
 
         Ok(())
     }
@@ -83,7 +198,9 @@ impl FrameworkItem for SearchBar {
         key: crossterm::event::KeyEvent,
         _info: tui_additions::framework::ItemInfo,
     ) -> Result<(), Box<dyn Error>> {
+        let suggestions = self.suggestions(framework);
         let mut render = true;
+        let mut edited = true;
         match framework
             .data
             .global
@@ -91,7 +208,10 @@ impl FrameworkItem for SearchBar {
             .unwrap()
             .get(key)
         {
-            _ if matches!(key.code, KeyCode::Char(_)) && key.modifiers.bits() < 2 => render = false,
+            _ if matches!(key.code, KeyCode::Char(_)) && key.modifiers.bits() < 2 => {
+                render = false;
+                edited = false;
+            }
             #[cfg(feature = "clipboard")]
             Some(KeyAction::Paste) => {
                 let content = get_clipboard();
@@ -113,11 +233,29 @@ impl FrameworkItem for SearchBar {
                 self.text_field.scroll = 0;
                 self.text_field.cursor = 0;
             }
-            Some(KeyAction::First | KeyAction::MoveUp) => self.text_field.cursor = 0,
+            // move through the suggestions dropdown when it is shown
+            Some(KeyAction::MoveDown) if !suggestions.is_empty() => {
+                edited = false;
+                self.suggestion_index = Some(
+                    self.suggestion_index
+                        .map_or(0, |index| (index + 1).min(suggestions.len() - 1)),
+                );
+            }
+            Some(KeyAction::MoveUp) if self.suggestion_index.is_some() => {
+                edited = false;
+                self.suggestion_index = self.suggestion_index.unwrap().checked_sub(1);
+            }
+            Some(KeyAction::First | KeyAction::MoveUp) => {
+                edited = false;
+                self.text_field.cursor = 0
+            }
             Some(KeyAction::End | KeyAction::MoveDown) => {
+                edited = false;
                 self.text_field.cursor = self.text_field.content.len()
             }
             Some(KeyAction::PreviousEntry) => {
+                edited = false;
+                self.show_suggestions = false;
                 let history = framework.data.global.get::<SearchHistory>().unwrap();
                 if self.history_index != Some(0) && !history.0.is_empty() {
                     if self.history_index.is_none() {
@@ -132,6 +270,8 @@ impl FrameworkItem for SearchBar {
                 }
             }
             Some(KeyAction::NextEntry) => {
+                edited = false;
+                self.show_suggestions = false;
                 let history = framework.data.global.get::<SearchHistory>().unwrap();
                 if self.history_index.is_none() {
                     render = false
@@ -145,10 +285,16 @@ impl FrameworkItem for SearchBar {
                     let _ = self.text_field.last();
                 }
             }
-            _ => render = false,
+            _ => {
+                render = false;
+                edited = false;
+            }
         }
 
         if render {
+            if edited {
+                self.edited(framework);
+            }
             framework
                 .data
                 .state
@@ -161,13 +307,32 @@ impl FrameworkItem for SearchBar {
         // pasting clipboard content
 
         let updated = match key.code {
-            KeyCode::Backspace => self.text_field.remove(self.text_field.cursor).is_ok(),
-            KeyCode::Char(c) => self.text_field.push(c).is_ok(),
+            KeyCode::Backspace | KeyCode::Char(_) => {
+                let updated = match key.code {
+                    KeyCode::Char(c) => self.text_field.push(c).is_ok(),
+                    _ => self.text_field.remove(self.text_field.cursor).is_ok(),
+                };
+                if updated {
+                    self.edited(framework);
+                }
+                updated
+            }
             KeyCode::Up => self.text_field.first().is_ok(),
             KeyCode::Down => self.text_field.last().is_ok(),
             KeyCode::Right => self.text_field.right().is_ok(),
             KeyCode::Left => self.text_field.left().is_ok(),
             KeyCode::Enter => {
+                // search for the highlighted suggestion
+                if let Some(suggestion) = self
+                    .suggestion_index
+                    .and_then(|index| suggestions.get(index))
+                {
+                    self.text_field.content = suggestion.clone();
+                    let _ = self.text_field.last();
+                }
+                self.show_suggestions = false;
+                self.suggestion_index = None;
+
                 if self.text_field.content.is_empty() {
                     *framework.data.global.get_mut::<Message>().unwrap() =
                         Message::Message(String::from("Search string must not be empty"));
@@ -3,7 +3,11 @@ use std::fs;
 use super::ItemInfo;
 use crate::{
     config::*,
-    global::{functions::*, structs::*, traits::Collection},
+    global::{
+        functions::*,
+        structs::*,
+        traits::{Collection, SearchProviderWrapper},
+    },
 };
 use ratatui::{
     layout::{Constraint, Rect},
@@ -43,7 +47,7 @@ pub struct SingleItem {
 #[derive(Clone)]
 pub enum SingleItemType {
     None,
-    Video(SingleVideoItem),
+    Video(Box<SingleVideoItem>),
     Playlist(Box<SinglePlaylistItem>),
 }
 
@@ -51,6 +55,13 @@ pub enum SingleItemType {
 pub struct SingleVideoItem {
     pub textlist: TextList,
     pub commands: Vec<(String, String)>,
+    pub is_commands_view: bool,
+    pub transcript_view: TextList,
+    pub transcript: Option<Transcript>,
+    /// indices of cues displayed in `transcript_view`, all cues if there is no search query
+    pub transcript_cues: Vec<usize>,
+    /// id of the `BackgroundFetch` downloading the transcript
+    pub loading: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -60,6 +71,8 @@ pub struct SinglePlaylistItem {
     pub commands: Vec<(String, String)>,
     pub is_commands_view: bool,
     pub hovered_video: ItemInfo,
+    /// id of the `BackgroundFetch` loading the next page of videos
+    pub loading: Option<u64>,
 }
 
 impl SingleVideoItem {
@@ -83,9 +96,55 @@ impl SingleVideoItem {
                 )
                 .unwrap(),
             commands,
+            is_commands_view: true,
+            transcript_view: TextList::default(),
+            transcript: None,
+            transcript_cues: Vec::new(),
+            loading: None,
         }
     }
 
+    /// the textlist that is currently displayed
+    pub fn active_list(&mut self) -> &mut TextList {
+        if self.is_commands_view {
+            &mut self.textlist
+        } else {
+            &mut self.transcript_view
+        }
+    }
+
+    pub fn set_transcript(&mut self, transcript: Transcript) {
+        self.transcript = Some(transcript);
+        self.search_transcript("");
+    }
+
+    /// only display cues containing `query`, an empty query displays all cues
+    pub fn search_transcript(&mut self, query: &str) {
+        let transcript = if let Some(transcript) = &self.transcript {
+            transcript
+        } else {
+            return;
+        };
+
+        self.transcript_cues = transcript.search(query);
+
+        let mut items = vec![if query.is_empty() {
+            String::from("Switch view")
+        } else {
+            format!(
+                "Switch view ({} matches for `{query}`)",
+                self.transcript_cues.len()
+            )
+        }];
+        items.extend(self.transcript_cues.iter().map(|index| {
+            let cue = &transcript.cues[*index];
+            format!("[{}] {}", secs_display_string(cue.start as u32), cue.text)
+        }));
+
+        self.transcript_view.set_items(&items).unwrap();
+        let _ = self.transcript_view.first();
+    }
+
     /// find all occurances of ${provider}
     pub fn update_provider(&mut self) -> Vec<usize> {
         self.commands
@@ -101,20 +160,16 @@ impl SingleVideoItem {
         appearance: &AppearanceConfig,
         iteminfo: &tui_additions::framework::ItemInfo,
     ) {
-        self.textlist.set_border_type(appearance.borders);
-        self.textlist
-            .set_style(Style::default().fg(appearance.colors.text));
+        let textlist = self.active_list();
+        textlist.set_border_type(appearance.borders);
+        textlist.set_style(Style::default().fg(appearance.colors.text));
 
         if iteminfo.selected {
-            self.textlist
-                .set_cursor_style(Style::default().fg(appearance.colors.outline_hover));
-            self.textlist
-                .set_selected_style(Style::default().fg(appearance.colors.text_special));
+            textlist.set_cursor_style(Style::default().fg(appearance.colors.outline_hover));
+            textlist.set_selected_style(Style::default().fg(appearance.colors.text_special));
         } else {
-            self.textlist
-                .set_cursor_style(Style::default().fg(appearance.colors.outline_secondary));
-            self.textlist
-                .set_selected_style(Style::default().fg(appearance.colors.text_secondary));
+            textlist.set_cursor_style(Style::default().fg(appearance.colors.outline_secondary));
+            textlist.set_selected_style(Style::default().fg(appearance.colors.text_secondary));
         }
     }
 
@@ -148,6 +203,16 @@ impl SingleVideoItem {
                     Provider::YouTube => format!("'https://youtube.com/embed/{}'", video_item.id),
                 },
             ),
+            (
+                String::from("play-url"),
+                // the saved file is played in offline mode
+                offline_file(&video_item.id, mainconfig).unwrap_or_else(|| match status.provider {
+                    Provider::Invidious => {
+                        format!("{}/embed/{}", mainconfig.invidious_instance, video_item.id)
+                    }
+                    Provider::YouTube => format!("https://youtube.com/embed/{}", video_item.id),
+                }),
+            ),
             (String::from("channel-id"), video_item.channel_id.clone()),
             (String::from("title"), video_item.title.clone()),
             (
@@ -170,6 +235,13 @@ impl SingleVideoItem {
                     .unwrap()
                     .to_string(),
             ),
+            (
+                String::from("caption-path"),
+                Transcript::path(&video_item.id, &mainconfig.captions_language)
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            ),
         ]
     }
 }
@@ -225,6 +297,7 @@ impl SinglePlaylistItem {
             commands,
             hovered_video,
             is_commands_view: true,
+            loading: None,
         }
     }
 
@@ -384,6 +457,31 @@ impl SingleItemType {
 }
 
 impl SingleItem {
+    /// the video or playlist from the search provider, `None` if it is already in the local store
+    pub fn fetch(page: &Page, mainconfig: &MainConfig) -> Option<Fetch<Item>> {
+        let image_index = mainconfig.image_index;
+        match page {
+            Page::SingleItem(SingleItemPage::Video(id)) if LocalStore::get_info(id).is_none() => {
+                let id = id.clone();
+                Some(Box::new(move || {
+                    Ok(Item::from_full_video(
+                        SearchProviderWrapper::video(&id)?,
+                        image_index,
+                    ))
+                }))
+            }
+            Page::SingleItem(SingleItemPage::Playlist(id))
+                if LocalStore::get_info(id).is_none() =>
+            {
+                let id = id.clone();
+                Some(Box::new(move || {
+                    Ok(Item::from_full_playlist(fetch_playlist(&id)?, image_index))
+                }))
+            }
+            _ => None,
+        }
+    }
+
     fn infalte_item_update(
         &self,
         mainconfig: &MainConfig,
@@ -466,6 +564,114 @@ impl SingleItem {
         }
     }
 
+    /// loads the next page of the playlist in the background when the cursor reaches the last
+    /// video
+    fn load_more(&mut self, framework: &mut FrameworkClean) {
+        let singleplaylistitem = match &mut self.r#type {
+            SingleItemType::Playlist(singleplaylistitem)
+                if !singleplaylistitem.is_commands_view && singleplaylistitem.loading.is_none() =>
+            {
+                singleplaylistitem
+            }
+            _ => return,
+        };
+        let videos_view = &singleplaylistitem.videos_view;
+        if videos_view.selected + 1 < videos_view.items.len() {
+            return;
+        }
+        let (id, continuation) = match self.item.as_ref().map(Item::fullplaylist) {
+            Some(Ok(FullPlaylistItem {
+                id,
+                continuation: Some(continuation),
+                ..
+            })) => (id.clone(), continuation.clone()),
+            _ => return,
+        };
+
+        let image_index = framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .image_index;
+        singleplaylistitem.loading = Some(
+            framework
+                .data
+                .global
+                .get_mut::<BackgroundFetch>()
+                .unwrap()
+                .start(Box::new(move || {
+                    let playlist = SearchProviderWrapper::playlist(&id, Some(&continuation))?;
+                    Ok((
+                        playlist
+                            .videos
+                            .into_iter()
+                            .map(|video| Item::from_playlist_item(video, image_index))
+                            .collect::<Vec<_>>(),
+                        // a repeated token would load the same page forever
+                        playlist.continuation.filter(|next| next != &continuation),
+                    ))
+                })),
+        );
+    }
+
+    /// adds the next page of videos once it arrives
+    fn more_loaded(
+        &mut self,
+        framework: &mut FrameworkClean,
+        res: Result<(Vec<Item>, Option<String>), String>,
+    ) {
+        let (videos, continuation) = match res {
+            Ok(res) => res,
+            // the token is kept, so moving to the last video again retries
+            Err(e) => {
+                *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(e);
+                return;
+            }
+        };
+        let (Some(Item::FullPlaylist(playlist)), SingleItemType::Playlist(singleplaylistitem)) =
+            (&mut self.item, &mut self.r#type)
+        else {
+            return;
+        };
+
+        let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+        if mainconfig.images.display() {
+            download_all_images(videos.iter().map(|item| item.into()).collect());
+        }
+
+        playlist.continuation = continuation.filter(|_| !videos.is_empty());
+        playlist.videos.extend(videos);
+        singleplaylistitem
+            .videos_view
+            .set_items(&{
+                let mut items = vec!["Switch view"];
+                items.extend(
+                    playlist
+                        .videos
+                        .iter()
+                        .map(|item| item.minivideo().unwrap().title.as_str()),
+                );
+                items
+            })
+            .unwrap();
+
+        // the cached playlist is updated, so opening it again keeps the loaded videos
+        let item = self.item.clone().unwrap();
+        LocalStore::set_info(item.id().unwrap().to_string(), item, true);
+        self.iteminfo.item = self.item.clone();
+        set_envs(
+            self.r#type
+                .inflate_load(
+                    mainconfig,
+                    framework.data.global.get::<Status>().unwrap(),
+                    &self.item,
+                )
+                .into_iter(),
+            &mut framework.data.state.get_mut::<StateEnvs>().unwrap().0,
+        );
+    }
+
     /// handle enter presses
     fn select_at_cursor(
         &mut self,
@@ -473,19 +679,75 @@ impl SingleItem {
         // info: tui_additions::framework::ItemInfo,
     ) {
         match &mut self.r#type {
+            SingleItemType::Video(singlevideoitem) if !singlevideoitem.is_commands_view => {
+                match singlevideoitem.transcript_view.selected {
+                    0 => {
+                        singlevideoitem.is_commands_view = true;
+                        *framework.data.global.get_mut::<Message>().unwrap() =
+                            Message::Success(String::from("Switched view"));
+                    }
+                    selected => {
+                        // jump to the cue in the embedded player
+                        let cue = &singlevideoitem.transcript.as_ref().unwrap().cues
+                            [singlevideoitem.transcript_cues[selected - 1]];
+                        framework
+                            .data
+                            .state
+                            .get_mut::<Tasks>()
+                            .unwrap()
+                            .priority
+                            .push(Task::Command(format!("mpv seek {} absolute", cue.start)));
+                    }
+                }
+            }
             SingleItemType::Video(singlevideoitem) => {
                 let command_string = singlevideoitem.commands[singlevideoitem.textlist.selected]
                     .1
                     .clone();
 
-                // check if the command starts with an ':' which case should be captured
-                framework
-                    .data
-                    .state
-                    .get_mut::<Tasks>()
-                    .unwrap()
-                    .priority
-                    .push(Task::Command(apply_envs(command_string)));
+                // checks for special cases
+                match command_string.as_str() {
+                    "%switch-view%" if singlevideoitem.transcript.is_some() => {
+                        singlevideoitem.is_commands_view = false;
+                        *framework.data.global.get_mut::<Message>().unwrap() =
+                            Message::Success(String::from("Switched view"));
+                    }
+                    // the view is switched once the transcript is downloaded
+                    "%switch-view%" => {
+                        if singlevideoitem.loading.is_some() {
+                            return;
+                        }
+
+                        let id = self.item.as_ref().unwrap().id().unwrap().to_string();
+                        let language = framework
+                            .data
+                            .global
+                            .get::<MainConfig>()
+                            .unwrap()
+                            .captions_language
+                            .clone();
+                        singlevideoitem.loading = Some(
+                            framework
+                                .data
+                                .global
+                                .get_mut::<BackgroundFetch>()
+                                .unwrap()
+                                .start(Box::new(move || load_transcript(&id, &language))),
+                        );
+                        *framework.data.global.get_mut::<Message>().unwrap() =
+                            Message::Message(String::from("Loading transcript"));
+                    }
+                    _ => {
+                        // check if the command starts with an ':' which case should be captured
+                        framework
+                            .data
+                            .state
+                            .get_mut::<Tasks>()
+                            .unwrap()
+                            .priority
+                            .push(Task::Command(apply_envs(command_string)));
+                    }
+                }
             }
             SingleItemType::Playlist(singleplaylistitem) => {
                 let command_string = singleplaylistitem.commands
@@ -541,7 +803,30 @@ impl FrameworkItem for SingleItem {
 
         match &mut self.r#type {
             SingleItemType::None => false,
-            SingleItemType::Video(SingleVideoItem { textlist, .. }) => {
+            SingleItemType::Video(singlevideoitem) => {
+                if let Some(res) = Fetched::take(&data, singlevideoitem.loading) {
+                    singlevideoitem.loading = None;
+                    *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                        Ok(transcript) => {
+                            framework
+                                .data
+                                .global
+                                .get_mut::<LocalIndex>()
+                                .unwrap()
+                                .add_transcript(
+                                    self.item.as_ref().unwrap().id().unwrap(),
+                                    &transcript,
+                                );
+                            singlevideoitem.set_transcript(transcript);
+                            singlevideoitem.is_commands_view = false;
+                            Message::Success(String::from("Switched view"))
+                        }
+                        Err(e) => Message::Error(e),
+                    };
+                    return true;
+                }
+
+                let textlist = singlevideoitem.active_list();
                 data.get("type").is_some_and(|v| {
                     v.downcast_ref::<String>()
                         .is_some_and(|v| match v.as_str() {
@@ -552,6 +837,12 @@ impl FrameworkItem for SingleItem {
                 })
             }
             SingleItemType::Playlist(item) => {
+                if let Some(res) = Fetched::take(&data, item.loading) {
+                    item.loading = None;
+                    self.more_loaded(framework, res);
+                    return true;
+                }
+
                 if item.is_commands_view {
                     data.get("type").is_some_and(|v| {
                         v.downcast_ref::<String>()
@@ -594,6 +885,8 @@ impl FrameworkItem for SingleItem {
                             );
                         }
 
+                        self.load_more(framework);
+
                         framework
                             .data
                             .global
@@ -674,10 +967,30 @@ impl FrameworkItem for SingleItem {
                         )
                     });
                 }
+                // set by the `transcript search` command, which also makes sure the transcript is
+                // cached
+                if let Some(TranscriptSearch(query)) = status.storage.get::<TranscriptSearch>() {
+                    if typeinfo.transcript.is_none() {
+                        typeinfo.transcript = Transcript::load(
+                            self.item.as_ref().unwrap().id().unwrap(),
+                            &framework
+                                .data
+                                .global
+                                .get::<MainConfig>()
+                                .unwrap()
+                                .captions_language,
+                        );
+                    }
+                    if typeinfo.transcript.is_some() {
+                        typeinfo.search_transcript(query);
+                        typeinfo.is_commands_view = false;
+                    }
+                }
                 self.iteminfo
                     .render(frame, framework, chunks[0], popup_render, info);
-                typeinfo.textlist.set_height(chunks[1].height);
-                frame.render_widget(typeinfo.textlist.clone(), chunks[1]);
+                let textlist = typeinfo.active_list();
+                textlist.set_height(chunks[1].height);
+                frame.render_widget(textlist.clone(), chunks[1]);
             }
             SingleItemType::Playlist(typeinfo) => {
                 // 3 by 1 grid if hovering a video inside the playlist
@@ -740,32 +1053,39 @@ impl FrameworkItem for SingleItem {
         };
 
         let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
-        // load items using the invidious api
-        // gets the item that it needs to load from `data.state.Page`
-        let mut is_new = true;
+        // load items using the search provider, or from the local store if it has been loaded
+        // before
+        let fetched = Self::fetch(page, mainconfig)
+            .map(|fetch| fetch())
+            .transpose()?;
+        if let Some(item) = &fetched {
+            download_item_images(item, mainconfig);
+        }
+        let is_new = fetched.is_some();
+        // local playlists are not cached or added to watch history
+        let is_local = matches!(r#type, SingleItemPage::LocalPlaylist(_));
         let (item, r#type) = match r#type {
             SingleItemPage::Video(id) => {
-                let video = if let Some(item) = LocalStore::get_info(id) {
-                    is_new = false;
-                    item
-                } else {
-                    load_video(id, mainconfig)?
+                let video = match fetched {
+                    Some(item) => item,
+                    None => LocalStore::get_info(id).ok_or(format!("Unknown video: {id}"))?,
                 };
                 (
                     video,
-                    SingleItemType::Video(SingleVideoItem::new(
-                        framework.data.global.get::<CommandsConfig>().unwrap(),
-                        mainconfig,
-                        id,
-                    )),
+                    SingleItemType::Video(
+                        SingleVideoItem::new(
+                            framework.data.global.get::<CommandsConfig>().unwrap(),
+                            mainconfig,
+                            id,
+                        )
+                        .into(),
+                    ),
                 )
             }
             SingleItemPage::Playlist(id) => {
-                let playlist = if let Some(item) = LocalStore::get_info(id) {
-                    is_new = false;
-                    item
-                } else {
-                    load_playlist(id, mainconfig)?
+                let playlist = match fetched {
+                    Some(item) => item,
+                    None => LocalStore::get_info(id).ok_or(format!("Unknown playlist: {id}"))?,
                 };
 
                 let r#type = SingleItemType::Playlist(
@@ -778,22 +1098,56 @@ impl FrameworkItem for SingleItem {
                     .into(),
                 );
 
+                (playlist, r#type)
+            }
+            SingleItemPage::LocalPlaylist(name) => {
+                let playlist = framework
+                    .data
+                    .global
+                    .get::<LocalPlaylists>()
+                    .unwrap()
+                    .get(name)
+                    .ok_or(format!("Unknown playlist: {name}"))?
+                    .to_item();
+
+                let r#type = SingleItemType::Playlist(
+                    SinglePlaylistItem::new_with_map(
+                        framework
+                            .data
+                            .global
+                            .get::<CommandsConfig>()
+                            .unwrap()
+                            .local_playlist
+                            .clone(),
+                        &playlist.fullplaylist().unwrap().videos,
+                    )
+                    .into(),
+                );
+
                 (playlist, r#type)
             }
         };
 
-        LocalStore::set_info(item.id().unwrap().to_string(), item.clone(), is_new);
+        if !is_local {
+            LocalStore::set_info(item.id().unwrap().to_string(), item.clone(), is_new);
+        }
 
         self.item = Some(item);
         self.r#type = r#type;
         self.iteminfo.item = self.item.clone();
 
-        if let Some(item) = &self.item {
+        if let Some(item) = self.item.as_ref().filter(|_| !is_local) {
             // if item.is_unknown() {
             //     return Ok(());
             // }
 
             let item = item.clone();
+            framework
+                .data
+                .global
+                .get_mut::<LocalIndex>()
+                .unwrap()
+                .push(&item);
             // push to watch history
             let watch_history = framework.data.global.get_mut::<WatchHistory>().unwrap();
             watch_history.push(item)?;
@@ -850,10 +1204,14 @@ impl FrameworkItem for SingleItem {
         let updated = match &mut self.r#type {
             SingleItemType::Video(singlevideoitem) => match action {
                 // move the cursor in the textlist, only update the screen if it is changed
-                KeyAction::MoveUp => singlevideoitem.textlist.up().is_ok(),
-                KeyAction::MoveDown => singlevideoitem.textlist.down().is_ok(),
-                KeyAction::MoveLeft | KeyAction::First => singlevideoitem.textlist.first().is_ok(),
-                KeyAction::MoveRight | KeyAction::End => singlevideoitem.textlist.last().is_ok(),
+                KeyAction::MoveUp => singlevideoitem.active_list().up().is_ok(),
+                KeyAction::MoveDown => singlevideoitem.active_list().down().is_ok(),
+                KeyAction::MoveLeft | KeyAction::First => {
+                    singlevideoitem.active_list().first().is_ok()
+                }
+                KeyAction::MoveRight | KeyAction::End => {
+                    singlevideoitem.active_list().last().is_ok()
+                }
                 KeyAction::Select => {
                     self.select_at_cursor(framework);
                     return Ok(());
@@ -991,6 +1349,7 @@ impl FrameworkItem for SingleItem {
                     };
 
                     if updated {
+                        self.load_more(framework);
                         framework
                             .data
                             .global
@@ -1051,7 +1410,7 @@ impl FrameworkItem for SingleItem {
         }
 
         let textlist = match &mut self.r#type {
-            SingleItemType::Video(SingleVideoItem { textlist, .. }) => textlist,
+            SingleItemType::Video(singlevideoitem) => singlevideoitem.active_list(),
             SingleItemType::Playlist(singleplaylistitem) => {
                 if singleplaylistitem.is_commands_view {
                     &mut singleplaylistitem.commands_view
@@ -1090,6 +1449,7 @@ impl FrameworkItem for SingleItem {
         }
 
         self.update();
+        self.load_more(framework);
 
         set_envs(
             self.infalte_item_update(
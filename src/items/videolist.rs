@@ -11,10 +11,10 @@ use typemap::Key;
 use crate::{
     config::{AppearanceConfig, KeyBindingsConfig, MainConfig, Provider},
     global::{
-        functions::set_envs,
+        functions::{dim_rows, set_envs, watched_style},
         structs::{
-            ChannelDisplayPage, ChannelDisplayPageType, Item, KeyAction, MiniVideoItem, Page,
-            StateEnvs, Status, Subscriptions, Task, Tasks,
+            ChannelDisplayPage, ChannelDisplayPageType, Filters, Item, KeyAction, MiniVideoItem,
+            Page, StateEnvs, Status, Subscriptions, Task, Tasks, WatchedVideos,
         },
     },
 };
@@ -76,7 +76,13 @@ impl VideoList {
         }
     }
 
-    fn update_items(&mut self, subscriptions: &Subscriptions, subselect: usize) {
+    fn update_items(
+        &mut self,
+        subscriptions: &Subscriptions,
+        watched: &WatchedVideos,
+        filters: &Filters,
+        subselect: usize,
+    ) {
         self.previous = subselect;
         if subselect == 0 {
             // if channellist is at index 0 (first item), then fetch all feeds
@@ -91,6 +97,8 @@ impl VideoList {
             self.channel_id = None;
             self.items.clear();
         }
+        self.items
+            .retain(|item| !watched.hidden(&item.id) && filters.allowed_video(item));
     }
 
     fn select_at_cursor(&self, framework: &mut FrameworkClean) {
@@ -264,6 +272,8 @@ impl FrameworkItem for VideoList {
         // put the items into self.items
         self.update_items(
             framework.data.global.get::<Subscriptions>().unwrap(),
+            framework.data.global.get::<WatchedVideos>().unwrap(),
+            framework.data.global.get::<Filters>().unwrap(),
             framework
                 .data
                 .global
@@ -315,6 +325,8 @@ impl FrameworkItem for VideoList {
         {
             self.update_items(
                 framework.data.global.get::<Subscriptions>().unwrap(),
+                framework.data.global.get::<WatchedVideos>().unwrap(),
+                framework.data.global.get::<Filters>().unwrap(),
                 subselect.0,
             );
             framework
@@ -356,6 +368,17 @@ impl FrameworkItem for VideoList {
         self.selector.set_height(chunks[1].height);
         frame.render_widget(self.selector.clone(), chunks[0]);
 
+        // the options on top are never dimmed
+        let offset = if self.channel_id.is_some() { 3 } else { 1 };
+        let watched = framework.data.global.get::<WatchedVideos>().unwrap();
+        dim_rows(
+            frame.buffer_mut(),
+            &self.selector,
+            chunks[0],
+            watched_style(framework.data.global.get::<AppearanceConfig>().unwrap()),
+            |index| index >= offset && watched.contains(&self.items[index - offset].id),
+        );
+
         if self.channel_id.is_some() {
             match self.selector.selected {
                 0 => {}
@@ -1,12 +1,13 @@
 use crossterm::event::{self, Event, KeyEventKind, MouseButton, MouseEventKind};
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::Backend, Terminal};
 #[cfg(feature = "mpv")]
-use std::time::{Duration, Instant};
+use std::time::Instant;
 use std::{
     any::{Any, TypeId},
     collections::HashMap,
     error::Error,
-    io::Stdout,
+    rc::Rc,
+    time::Duration,
 };
 use tui_additions::framework::Framework;
 
@@ -16,21 +17,25 @@ use crate::{
     items::*,
 };
 
+/// how often to check for search suggestions while waiting for them
+const SUGGESTIONS_POLL: Duration = Duration::from_millis(50);
+/// how often the spinner moves while a page is loading
+pub const LOAD_POLL: Duration = Duration::from_millis(100);
+
 /// the main event loop of the program
-pub fn run(
-    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+pub fn run<B: Backend>(
+    terminal: &mut Terminal<B>,
     framework: &mut Framework,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), Box<dyn Error>>
+where
+    B::Error: 'static,
+{
     #[cfg(feature = "mpv")]
     let tick_rate = Duration::from_secs(1);
     #[cfg(feature = "mpv")]
     let mut last_tick = Instant::now();
     loop {
-        // repeat forever until all tasks are ran (and Tasks is cleared)
-        if let Some(tasks) = framework.data.state.get_mut::<Tasks>().unwrap().pop() {
-            tasks.run(framework, terminal)?;
-            continue;
-        }
+        run_tasks(terminal, framework)?;
 
         framework
             .data
@@ -45,223 +50,320 @@ pub fn run(
             break;
         }
 
+        // wake up regularly to pick up what the background threads are waiting on, without
+        // waiting for the next key press
+        let global = &framework.data.global;
+        let wait = if global.get::<PageLoader>().unwrap().pending()
+            || global.get::<BackgroundFetch>().unwrap().pending()
+        {
+            Some(LOAD_POLL)
+        } else if global.get::<SearchSuggestions>().unwrap().pending() {
+            Some(SUGGESTIONS_POLL)
+        } else {
+            None
+        };
         #[cfg(feature = "mpv")]
-        if !event::poll(
+        let wait = Some(
             tick_rate
-                .checked_sub(last_tick.elapsed())
-                .unwrap_or_else(|| Duration::from_secs(0)),
-        )? {
-            // do tick changes
-            last_tick = Instant::now();
-            // TaskQueue::render_filter(framework, terminal, |item| (*item).type_id() == TypeId::of::<MessageBar>())?;
-            if MessageBar::is_mpv_render(&framework.split_clean().0) {
-                TaskQueue::render(framework, terminal)?;
+                .saturating_sub(last_tick.elapsed())
+                .min(wait.unwrap_or(Duration::MAX)),
+        );
+
+        if wait.map(event::poll).transpose()? == Some(false) {
+            // keep the spinner moving, and finish loading the page once its responses arrive
+            if framework.data.global.get::<PageLoader>().unwrap().pending() {
+                poll_page_load(framework);
+            }
+            poll_background_fetch(framework);
+            poll_suggestions(framework);
+
+            #[cfg(feature = "mpv")]
+            if last_tick.elapsed() >= tick_rate {
+                // do tick changes
+                last_tick = Instant::now();
+                // pass on events from the mpv event thread
+                let tasks = &mut framework.data.state.get_mut::<Tasks>().unwrap().priority;
+                for event in framework
+                    .data
+                    .global
+                    .get::<MpvWrapper>()
+                    .unwrap()
+                    .events
+                    .try_iter()
+                {
+                    tasks.push(Task::MpvEvent(event));
+                }
+                // TaskQueue::render_filter(framework, terminal, |item| (*item).type_id() == TypeId::of::<MessageBar>())?;
+                if MessageBar::is_mpv_render(&framework.split_clean().0) {
+                    framework
+                        .data
+                        .state
+                        .get_mut::<Tasks>()
+                        .unwrap()
+                        .priority
+                        .push(Task::RenderAll);
+                }
             }
             continue;
         }
 
         *framework.data.global.get_mut::<Message>().unwrap() = Message::None;
 
-        let mut updated = false;
+        handle_event(event::read()?, terminal, framework);
+    }
+    Ok(())
+}
 
-        match event::read()? {
-            Event::Mouse(mouse)
-                if framework
-                    .data
-                    .global
-                    .get::<MainConfig>()
-                    .unwrap()
-                    .mouse_support =>
-            {
-                let scroll_behaviour = framework
-                    .data
-                    .global
-                    .get::<MainConfig>()
-                    .unwrap()
-                    .textbar_scroll_behaviour;
-                let command_capture = &mut framework
-                    .data
-                    .global
-                    .get_mut::<Status>()
-                    .unwrap()
-                    .command_capture;
-
-                match mouse.kind {
-                    // copied from messagebar.rs::key_event
-                    // if modify this, also modify it there
-                    MouseEventKind::ScrollUp if command_capture.is_some() => {
-                        updated = updated
-                            || match scroll_behaviour {
-                                TextbarScrollBehaviour::Character => {
-                                    command_capture.as_mut().unwrap().left().is_ok()
+/// repeat until all tasks are ran (and Tasks is cleared)
+pub fn run_tasks<B: Backend>(
+    terminal: &mut Terminal<B>,
+    framework: &mut Framework,
+) -> Result<(), Box<dyn Error>>
+where
+    B::Error: 'static,
+{
+    while let Some(tasks) = framework.data.state.get_mut::<Tasks>().unwrap().pop() {
+        tasks.run(framework, terminal)?;
+    }
+
+    Ok(())
+}
+
+/// finishes loading the page if its responses have arrived, otherwise moves the spinner, tasks it
+/// creates are not ran until `run_tasks`
+pub fn poll_page_load(framework: &mut Framework) {
+    let loader = framework.data.global.get_mut::<PageLoader>().unwrap();
+    let tasks = framework.data.state.get_mut::<Tasks>().unwrap();
+
+    match loader.receive() {
+        Some(load) => {
+            tasks.priority.push(Task::FinishLoad);
+            // the page command runs after the page is loaded
+            if let Some(command) = load.command {
+                tasks.last.push(Task::Command(command));
+            }
+        }
+        None => {
+            let spinner = loader.spinner();
+            *framework.data.global.get_mut::<Message>().unwrap() = Message::Message(spinner);
+            tasks.priority.push(Task::RenderAll);
+        }
+    }
+}
+
+/// sends the responses of background fetches to the items that started them, and queues the tasks
+/// handling the ones started by commands, tasks it creates
+/// are not ran until `run_tasks`
+pub fn poll_background_fetch(framework: &mut Framework) {
+    let (responses, tasks) = framework
+        .data
+        .global
+        .get_mut::<BackgroundFetch>()
+        .unwrap()
+        .receive();
+    if responses.is_empty() && tasks.is_empty() {
+        return;
+    }
+
+    let queue = &mut framework.data.state.get_mut::<Tasks>().unwrap().priority;
+    for task in tasks {
+        queue.push(Task::Custom(task));
+    }
+
+    let (mut frameworkclean, state) = framework.split_clean();
+    for fetched in responses.into_iter().map(Rc::new) {
+        for row in state.0.iter_mut() {
+            for item in row.items.iter_mut() {
+                if !fetched.is_taken() {
+                    item.item.message(&mut frameworkclean, fetched.message());
+                }
+            }
+        }
+    }
+
+    framework
+        .data
+        .global
+        .get_mut::<Status>()
+        .unwrap()
+        .render_image = true;
+    framework
+        .data
+        .state
+        .get_mut::<Tasks>()
+        .unwrap()
+        .priority
+        .push(Task::RenderAll);
+}
+
+/// picks up search suggestions that arrived, tasks it creates are not ran until `run_tasks`
+pub fn poll_suggestions(framework: &mut Framework) {
+    if framework
+        .data
+        .global
+        .get_mut::<SearchSuggestions>()
+        .unwrap()
+        .receive()
+    {
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::RenderAll);
+    }
+}
+
+/// handles a single terminal event, tasks it creates are not ran until `run_tasks`
+pub fn handle_event<B: Backend>(
+    event: Event,
+    terminal: &mut Terminal<B>,
+    framework: &mut Framework,
+)
+where
+    B::Error: 'static,
+{
+    let mut updated = false;
+
+    match event {
+        Event::Mouse(mouse)
+            if framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .mouse_support =>
+        {
+            let scroll_behaviour = framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .textbar_scroll_behaviour;
+            let command_capture = &mut framework
+                .data
+                .global
+                .get_mut::<Status>()
+                .unwrap()
+                .command_capture;
+
+            match mouse.kind {
+                // copied from messagebar.rs::key_event
+                // if modify this, also modify it there
+                MouseEventKind::ScrollUp if command_capture.is_some() => {
+                    updated = updated
+                        || match scroll_behaviour {
+                            TextbarScrollBehaviour::Character => {
+                                command_capture.as_mut().unwrap().left().is_ok()
+                            }
+                            TextbarScrollBehaviour::History => {
+                                let status = framework.data.global.get::<Status>().unwrap();
+                                let history =
+                                    framework.data.global.get::<CommandHistory>().unwrap();
+                                if status.command_history_index != Some(0) && !history.0.is_empty()
+                                {
+                                    let new_index =
+                                        status.command_history_index.unwrap_or(history.0.len()) - 1;
+                                    let new_content = history.0[new_index].clone();
+
+                                    let status = framework.data.global.get_mut::<Status>().unwrap();
+                                    let text_field = status.command_capture.as_mut().unwrap();
+                                    if status.command_history_index.is_none() {
+                                        status.command_editing_cache = text_field.content.clone();
+                                    }
+                                    text_field.content = new_content;
+                                    status.command_history_index = Some(new_index);
+                                    let _ = text_field.last();
+                                    true
+                                } else {
+                                    false
                                 }
-                                TextbarScrollBehaviour::History => {
-                                    let status = framework.data.global.get::<Status>().unwrap();
+                            }
+                            TextbarScrollBehaviour::Word => {
+                                previous_word(
+                                    framework
+                                        .data
+                                        .global
+                                        .get_mut::<Status>()
+                                        .unwrap()
+                                        .command_capture
+                                        .as_mut()
+                                        .unwrap(),
+                                );
+                                true
+                            }
+                        }
+                }
+                MouseEventKind::ScrollUp => {
+                    let data: Box<dyn Any> = Box::new("scrollup".to_string());
+                    updated =
+                        updated || framework.message(HashMap::from([("type".to_string(), data)]))
+                }
+                MouseEventKind::ScrollDown if command_capture.is_some() => {
+                    updated = updated
+                        || match scroll_behaviour {
+                            TextbarScrollBehaviour::Word => {
+                                next_word(
+                                    framework
+                                        .data
+                                        .global
+                                        .get_mut::<Status>()
+                                        .unwrap()
+                                        .command_capture
+                                        .as_mut()
+                                        .unwrap(),
+                                );
+                                true
+                            }
+                            TextbarScrollBehaviour::History => {
+                                let status = framework.data.global.get::<Status>().unwrap();
+                                if status.command_history_index.is_none() {
+                                    false
+                                } else {
                                     let history =
                                         framework.data.global.get::<CommandHistory>().unwrap();
-                                    if status.command_history_index != Some(0)
-                                        && !history.0.is_empty()
-                                    {
+                                    if status.command_history_index == Some(history.0.len() - 1) {
+                                        let status =
+                                            framework.data.global.get_mut::<Status>().unwrap();
+                                        let text_field = status.command_capture.as_mut().unwrap();
+                                        status.command_history_index = None;
+                                        text_field.content = status.command_editing_cache.clone();
+                                        text_field.cursor =
+                                            text_field.cursor.min(text_field.content.len());
+                                        if !text_field.content.is_empty() {
+                                            let _ = text_field.last();
+                                        }
+                                    } else {
                                         let new_index =
                                             status.command_history_index.unwrap_or(history.0.len())
-                                                - 1;
+                                                + 1;
                                         let new_content = history.0[new_index].clone();
 
                                         let status =
                                             framework.data.global.get_mut::<Status>().unwrap();
                                         let text_field = status.command_capture.as_mut().unwrap();
-                                        if status.command_history_index.is_none() {
-                                            status.command_editing_cache =
-                                                text_field.content.clone();
-                                        }
                                         text_field.content = new_content;
                                         status.command_history_index = Some(new_index);
                                         let _ = text_field.last();
-                                        true
-                                    } else {
-                                        false
                                     }
-                                }
-                                TextbarScrollBehaviour::Word => {
-                                    previous_word(
-                                        framework
-                                            .data
-                                            .global
-                                            .get_mut::<Status>()
-                                            .unwrap()
-                                            .command_capture
-                                            .as_mut()
-                                            .unwrap(),
-                                    );
                                     true
                                 }
                             }
-                    }
-                    MouseEventKind::ScrollUp => {
-                        let data: Box<dyn Any> = Box::new("scrollup".to_string());
-                        updated = updated
-                            || framework.message(HashMap::from([("type".to_string(), data)]))
-                    }
-                    MouseEventKind::ScrollDown if command_capture.is_some() => {
-                        updated = updated
-                            || match scroll_behaviour {
-                                TextbarScrollBehaviour::Word => {
-                                    next_word(
-                                        framework
-                                            .data
-                                            .global
-                                            .get_mut::<Status>()
-                                            .unwrap()
-                                            .command_capture
-                                            .as_mut()
-                                            .unwrap(),
-                                    );
-                                    true
-                                }
-                                TextbarScrollBehaviour::History => {
-                                    let status = framework.data.global.get::<Status>().unwrap();
-                                    if status.command_history_index.is_none() {
-                                        false
-                                    } else {
-                                        let history =
-                                            framework.data.global.get::<CommandHistory>().unwrap();
-                                        if status.command_history_index == Some(history.0.len() - 1)
-                                        {
-                                            let status =
-                                                framework.data.global.get_mut::<Status>().unwrap();
-                                            let text_field =
-                                                status.command_capture.as_mut().unwrap();
-                                            status.command_history_index = None;
-                                            text_field.content =
-                                                status.command_editing_cache.clone();
-                                            text_field.cursor =
-                                                text_field.cursor.min(text_field.content.len());
-                                            if !text_field.content.is_empty() {
-                                                let _ = text_field.last();
-                                            }
-                                        } else {
-                                            let new_index = status
-                                                .command_history_index
-                                                .unwrap_or(history.0.len())
-                                                + 1;
-                                            let new_content = history.0[new_index].clone();
-
-                                            let status =
-                                                framework.data.global.get_mut::<Status>().unwrap();
-                                            let text_field =
-                                                status.command_capture.as_mut().unwrap();
-                                            text_field.content = new_content;
-                                            status.command_history_index = Some(new_index);
-                                            let _ = text_field.last();
-                                        }
-                                        true
-                                    }
-                                }
-                                TextbarScrollBehaviour::Character => {
-                                    command_capture.as_mut().unwrap().right().is_ok()
-                                }
+                            TextbarScrollBehaviour::Character => {
+                                command_capture.as_mut().unwrap().right().is_ok()
                             }
-                    }
-                    MouseEventKind::ScrollDown => {
-                        let data: Box<dyn Any> = Box::new("scrolldown".to_string());
-                        updated = updated
-                            || framework.message(HashMap::from([("type".to_string(), data)]))
-                    }
-                    _ => {}
-                }
-
-                if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
-                    if updated {
-                        framework
-                            .data
-                            .state
-                            .get_mut::<Tasks>()
-                            .unwrap()
-                            .priority
-                            .push(Task::RenderAll);
-                    }
-
-                    continue;
-                }
-
-                *framework.data.global.get_mut::<Message>().unwrap() = Message::None;
-
-                let status = framework.data.global.get_mut::<Status>().unwrap();
-                status.command_capture = None;
-
-                // check if the search filter popup is clicked
-                let mut searchfilter_clicked = false;
-                if status.search_filter_opened {
-                    let (mut frameworkclean, state) = framework.split_clean();
-                    for row in state.0.iter_mut() {
-                        for item in row.items.iter_mut() {
-                            if (*item.item).type_id() == TypeId::of::<SearchFilter>()
-                                && item.item.mouse_event(
-                                    &mut frameworkclean,
-                                    0,
-                                    0,
-                                    mouse.column,
-                                    mouse.row,
-                                )
-                            {
-                                searchfilter_clicked = true;
-                                break;
-                            }
-                        }
-
-                        if searchfilter_clicked {
-                            break;
                         }
-                    }
                 }
+                MouseEventKind::ScrollDown => {
+                    let data: Box<dyn Any> = Box::new("scrolldown".to_string());
+                    updated =
+                        updated || framework.message(HashMap::from([("type".to_string(), data)]))
+                }
+                _ => {}
+            }
 
-                updated = updated
-                    || if searchfilter_clicked {
-                        true
-                    } else {
-                        framework.mouse_event(mouse.column, mouse.row)
-                    };
-
+            if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
                 if updated {
                     framework
                         .data
@@ -271,14 +373,49 @@ pub fn run(
                         .priority
                         .push(Task::RenderAll);
                 }
+
+                return;
             }
-            // Only handle Press events; ignore Release/Repeat to avoid
-            // duplicate key actions on Windows terminals that send enhanced key events.
-            Event::Key(key) if key.kind == KeyEventKind::Press => {
-                key_input(key, framework, terminal)
+
+            *framework.data.global.get_mut::<Message>().unwrap() = Message::None;
+
+            let status = framework.data.global.get_mut::<Status>().unwrap();
+            status.command_capture = None;
+
+            // check if the search filter popup is clicked
+            let mut searchfilter_clicked = false;
+            if status.search_filter_opened {
+                let (mut frameworkclean, state) = framework.split_clean();
+                for row in state.0.iter_mut() {
+                    for item in row.items.iter_mut() {
+                        if (*item.item).type_id() == TypeId::of::<SearchFilter>()
+                            && item.item.mouse_event(
+                                &mut frameworkclean,
+                                0,
+                                0,
+                                mouse.column,
+                                mouse.row,
+                            )
+                        {
+                            searchfilter_clicked = true;
+                            break;
+                        }
+                    }
+
+                    if searchfilter_clicked {
+                        break;
+                    }
+                }
             }
-            // always render if there is a screen resize event
-            Event::Resize(_, _) => {
+
+            updated = updated
+                || if searchfilter_clicked {
+                    true
+                } else {
+                    framework.mouse_event(mouse.column, mouse.row)
+                };
+
+            if updated {
                 framework
                     .data
                     .state
@@ -286,15 +423,27 @@ pub fn run(
                     .unwrap()
                     .priority
                     .push(Task::RenderAll);
-                framework
-                    .data
-                    .global
-                    .get_mut::<Status>()
-                    .unwrap()
-                    .render_image = true;
             }
-            _ => {}
         }
+        // Only handle Press events; ignore Release/Repeat to avoid
+        // duplicate key actions on Windows terminals that send enhanced key events.
+        Event::Key(key) if key.kind == KeyEventKind::Press => key_input(key, framework, terminal),
+        // always render if there is a screen resize event
+        Event::Resize(_, _) => {
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+            framework
+                .data
+                .global
+                .get_mut::<Status>()
+                .unwrap()
+                .render_image = true;
+        }
+        _ => {}
     }
-    Ok(())
 }
@@ -0,0 +1,143 @@
+#![allow(static_mut_refs)]
+
+use std::path::PathBuf;
+
+use youtube_tui::{
+    config::{MainConfig, ProviderEntry, SearchProvider},
+    global::traits::SearchProviderWrapper,
+    MAIN_CONFIG,
+};
+
+fn fixture(dir: &str) -> ProviderEntry {
+    ProviderEntry {
+        provider: SearchProvider::Fixture,
+        instance: Some(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join(dir)
+                .to_string_lossy()
+                .to_string(),
+        ),
+    }
+}
+
+fn set_chain(primary: &str, fallbacks: &[&str]) {
+    let config = MainConfig {
+        search_provider: SearchProvider::Fixture,
+        fixture_dir: PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join(primary)
+            .to_string_lossy()
+            .to_string(),
+        fallback_providers: fallbacks.iter().map(|dir| fixture(dir)).collect(),
+        ..Default::default()
+    };
+
+    unsafe {
+        if MAIN_CONFIG.get().is_some() {
+            *MAIN_CONFIG.get_mut().unwrap() = config;
+        } else {
+            let _ = MAIN_CONFIG.set(config);
+        }
+    }
+    SearchProviderWrapper::init();
+}
+
+// the provider chain is global, so the cases run one after another in a single test
+#[test]
+fn provider_chain() {
+    // the first provider answers, nothing to report
+    set_chain("tests/fixtures", &["tests/missing"]);
+    assert!(SearchProviderWrapper::trending(None).is_ok());
+    assert!(SearchProviderWrapper::take_fallback_notice().is_none());
+
+    // the first provider fails, the next one answers and is named in the notice
+    set_chain("tests/missing", &["tests/fixtures"]);
+    assert_eq!(
+        SearchProviderWrapper::video("dQw4w9WgXcQ").unwrap().id,
+        "dQw4w9WgXcQ"
+    );
+    let notice = SearchProviderWrapper::take_fallback_notice().unwrap();
+    assert!(notice.starts_with("Loaded from Fixture ("));
+    assert!(notice.contains("tests/fixtures"));
+    assert!(SearchProviderWrapper::take_fallback_notice().is_none());
+
+    // every provider fails, all errors are reported
+    set_chain("tests/missing", &["tests/missing2"]);
+    let err = SearchProviderWrapper::trending(None)
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(err.starts_with("All providers failed"));
+    assert!(err.contains("missing2"));
+
+    // a single provider keeps its own error
+    set_chain("tests/missing", &[]);
+    let err = SearchProviderWrapper::trending(None)
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(err.starts_with("Cannot read fixture"));
+
+    // continuations only go to the provider that issued them
+    set_chain("tests/missing", &["tests/fixtures"]);
+    let page = SearchProviderWrapper::channel_videos("UCuAXFkgsw1L7xaCfnd5JJOw", None).unwrap();
+    let continuation = page.continuation.unwrap();
+    assert!(continuation.contains("tests/fixtures)|"));
+    let _ = SearchProviderWrapper::take_fallback_notice();
+    assert!(
+        SearchProviderWrapper::channel_videos("UCuAXFkgsw1L7xaCfnd5JJOw", Some(&continuation))
+            .is_ok()
+    );
+    assert!(SearchProviderWrapper::take_fallback_notice().is_none());
+
+    // and fail without falling back once that provider has left the chain
+    set_chain("tests/missing", &[]);
+    let err =
+        SearchProviderWrapper::channel_videos("UCuAXFkgsw1L7xaCfnd5JJOw", Some(&continuation))
+            .err()
+            .unwrap()
+            .to_string();
+    assert!(err.contains("no longer in the provider chain"));
+
+    // caption tracks are downloaded from the provider that listed them, the same way
+    set_chain("tests/missing", &["tests/fixtures"]);
+    let captions = SearchProviderWrapper::captions("dQw4w9WgXcQ").unwrap();
+    assert!(captions[0].url.contains("tests/fixtures)|"));
+    let _ = SearchProviderWrapper::take_fallback_notice();
+    assert!(SearchProviderWrapper::transcript(&captions[0]).is_ok());
+    assert!(SearchProviderWrapper::take_fallback_notice().is_none());
+
+    set_chain("tests/missing", &[]);
+    let err = SearchProviderWrapper::transcript(&captions[0])
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(err.contains("no longer in the provider chain"));
+
+    // offline mode does not reach the providers
+    set_chain("tests/fixtures", &[]);
+    SearchProviderWrapper::set_offline(true);
+    let err = SearchProviderWrapper::trending(None)
+        .err()
+        .unwrap()
+        .to_string();
+    SearchProviderWrapper::set_offline(false);
+    assert!(err.contains("not avaliable in offline mode"));
+    assert!(SearchProviderWrapper::trending(None).is_ok());
+
+    // providers can be used from other threads while they are replaced
+    let workers = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for _ in 0..50 {
+                    assert!(SearchProviderWrapper::video("dQw4w9WgXcQ").is_ok());
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for _ in 0..50 {
+        SearchProviderWrapper::init();
+    }
+    for worker in workers {
+        worker.join().unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+use youtube_tui::{
+    config::{BlockList, FilterAction, FilterRule, FiltersConfig},
+    global::{
+        common::{video::Video, CommonPlaylist, CommonVideo},
+        structs::{Filters, Item},
+    },
+};
+
+const CHANNEL: &str = "UCuAXFkgsw1L7xaCfnd5JJOw";
+
+fn video(title: &str, length: u32) -> Item {
+    Item::from_common_video(
+        CommonVideo {
+            title: title.to_string(),
+            id: String::from("dQw4w9WgXcQ"),
+            author_id: CHANNEL.to_string(),
+            description: String::from("The official video for Never Gonna Give You Up"),
+            length,
+            ..Default::default()
+        },
+        0,
+    )
+}
+
+fn filters(rules: Vec<FilterRule>) -> Filters {
+    Filters::new(
+        &FiltersConfig { rules },
+        &BlockList {
+            channels: Vec::new(),
+        },
+    )
+}
+
+#[test]
+fn no_rules() {
+    assert!(filters(Vec::new()).allowed(&video("Never Gonna Give You Up", 213)));
+}
+
+#[test]
+fn conditions() {
+    let filters = filters(vec![
+        FilterRule {
+            title: Some(String::from(r"(?i)\(remix\)$")),
+            ..Default::default()
+        },
+        FilterRule {
+            keywords: vec![String::from("LIVE")],
+            ..Default::default()
+        },
+        FilterRule {
+            min_length: Some(3600),
+            ..Default::default()
+        },
+    ]);
+
+    assert!(filters.allowed(&video("Never Gonna Give You Up", 213)));
+    assert!(!filters.allowed(&video("Never Gonna Give You Up (Remix)", 213)));
+    assert!(!filters.allowed(&video("Never Gonna Give You Up live", 213)));
+    // keywords are whole words
+    assert!(filters.allowed(&video("Never Gonna Give You Up, delivered", 213)));
+    assert!(!filters.allowed(&video("Full concert", 5400)));
+}
+
+#[test]
+fn shorts() {
+    let filters = filters(vec![FilterRule {
+        shorts: Some(true),
+        ..Default::default()
+    }]);
+
+    assert!(!filters.allowed(&video("Never Gonna Give You Up, but it's a short", 42)));
+    assert!(filters.allowed(&video("Never Gonna Give You Up", 213)));
+    // the length of live streams is unknown
+    assert!(filters.allowed(&video("Live stream", 0)));
+
+    // conditions on videos never match playlists
+    let playlist = Item::from_common_playlist(CommonPlaylist {
+        title: String::from("Greatest Hits"),
+        ..Default::default()
+    });
+    assert!(filters.allowed(&playlist));
+}
+
+#[test]
+fn full_video_length() {
+    // the length of full videos is read from the length they display
+    let video = Item::from_full_video(
+        serde_json::from_str::<Video>(include_str!("fixtures/video/dQw4w9WgXcQ.json")).unwrap(),
+        0,
+    );
+    let filters = filters(vec![FilterRule {
+        min_length: Some(200),
+        ..Default::default()
+    }]);
+    assert!(!filters.allowed(&video));
+}
+
+#[test]
+fn priorities() {
+    let deny_channel = FilterRule {
+        channel: Some(CHANNEL.to_string()),
+        ..Default::default()
+    };
+    let allow_title = |priority| FilterRule {
+        action: FilterAction::Allow,
+        priority,
+        title: Some(String::from("Never Gonna")),
+        ..Default::default()
+    };
+    let item = video("Never Gonna Give You Up", 213);
+
+    // deny wins between rules of the same priority
+    assert!(!filters(vec![deny_channel.clone(), allow_title(0)]).allowed(&item));
+    assert!(filters(vec![deny_channel.clone(), allow_title(1)]).allowed(&item));
+
+    // an allow list, with a rule matching everything
+    let filters = filters(vec![FilterRule::default(), allow_title(1)]);
+    assert!(filters.allowed(&item));
+    assert!(!filters.allowed(&video("Together Forever", 205)));
+    // page buttons are always shown
+    assert!(filters.allowed(&Item::Page(true)));
+}
+
+#[test]
+fn block_list() {
+    let filters = Filters::new(
+        &FiltersConfig::default(),
+        &BlockList {
+            channels: vec![CHANNEL.to_string()],
+        },
+    );
+    assert!(!filters.allowed(&video("Never Gonna Give You Up", 213)));
+}
+
+#[test]
+fn invalid_regex() {
+    // the invalid rule is skipped and reported once, the others still apply
+    let mut filters = filters(vec![
+        FilterRule {
+            title: Some(String::from("(unclosed")),
+            ..Default::default()
+        },
+        FilterRule {
+            title: Some(String::from("Never")),
+            ..Default::default()
+        },
+    ]);
+    assert!(!filters.allowed(&video("Never Gonna Give You Up", 213)));
+    assert!(filters.allowed(&video("(unclosed", 213)));
+
+    let skipped = filters.take_skipped().unwrap();
+    assert!(skipped.contains("(unclosed"));
+    assert!(!skipped.contains('\n'));
+    assert!(filters.take_skipped().is_none());
+}
@@ -0,0 +1,207 @@
+use std::path::PathBuf;
+
+use youtube_tui::{
+    config::Search,
+    global::{
+        common::{hidden::SearchItem, universal::Playlist},
+        structs::{FixtureProvider, Item},
+        traits::SearchProviderTrait,
+    },
+};
+
+const VIDEO: &str = "dQw4w9WgXcQ";
+const CHANNEL: &str = "UCuAXFkgsw1L7xaCfnd5JJOw";
+const PLAYLIST: &str = "PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI";
+
+fn provider() -> FixtureProvider {
+    FixtureProvider::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+}
+
+fn search(query: &str, page: u16) -> Search {
+    Search {
+        query: query.to_string(),
+        page,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn video() {
+    let video = provider().video(VIDEO).unwrap();
+    assert_eq!(video.id, VIDEO);
+    assert_eq!(video.author_id, CHANNEL);
+
+    // fixtures go through the same conversions as responses from the network
+    let item = Item::from_full_video(video, 4);
+    assert_eq!(item.id(), Some(VIDEO));
+    assert!(item.fullvideo().is_ok());
+}
+
+#[test]
+fn missing_fixture() {
+    let err = provider().video("missing").err().unwrap();
+    assert!(err.to_string().contains("video/missing.json"));
+}
+
+#[test]
+fn channel() {
+    let provider = provider();
+
+    let channel = provider.channel(CHANNEL).unwrap();
+    assert_eq!(channel.name, "Rick Astley");
+
+    let videos = provider.channel_videos(CHANNEL, None).unwrap();
+    assert_eq!(videos.videos.len(), 2);
+
+    // the continuation names the fixture of the next page
+    let next = provider
+        .channel_videos(CHANNEL, videos.continuation.as_deref())
+        .unwrap();
+    assert_eq!(next.videos.len(), 1);
+    assert!(next.continuation.is_none());
+
+    let playlists = provider.channel_playlists(CHANNEL, None).unwrap();
+    assert_eq!(playlists.playlists[0].id, PLAYLIST);
+    assert!(playlists.continuation.is_none());
+}
+
+#[test]
+fn channel_tabs() {
+    let provider = provider();
+
+    let shorts = provider.channel_shorts(CHANNEL, None).unwrap();
+    assert_eq!(shorts.videos[0].length, 42);
+
+    let streams = provider.channel_streams(CHANNEL, None).unwrap();
+    assert_eq!(streams.videos[0].author_id, CHANNEL);
+
+    // channels without podcasts have an empty tab
+    let podcasts = provider.channel_podcasts(CHANNEL, None).unwrap();
+    assert!(podcasts.playlists.is_empty());
+}
+
+#[test]
+fn channel_search() {
+    let results = provider().channel_search(CHANNEL, "together").unwrap();
+    let [SearchItem::Video(video)] = results.items.as_slice() else {
+        panic!("unexpected channel search results");
+    };
+    assert_eq!(video.author_id, CHANNEL);
+}
+
+#[test]
+fn playlist() {
+    let playlist = provider().playlist(PLAYLIST, None).unwrap();
+    assert_eq!(playlist.videos.len(), 2);
+
+    let item = Item::from_full_playlist(playlist, 4);
+    assert_eq!(item.id(), Some(PLAYLIST));
+}
+
+#[test]
+fn mix() {
+    let mix = provider().mix("RDdQw4w9WgXcQ").unwrap();
+    assert_eq!(mix.videos[0].id, VIDEO);
+
+    // mixes are shown as playlists
+    let playlist = Playlist::from(mix);
+    assert_eq!(playlist.id, "RDdQw4w9WgXcQ");
+    assert_eq!(playlist.video_count, 3);
+    assert_eq!(playlist.videos[1].author_id, CHANNEL);
+    assert!(playlist.continuation.is_none());
+}
+
+#[test]
+fn related() {
+    let video = provider().video(VIDEO).unwrap();
+    let Some(Item::MiniVideo(related)) = video
+        .recommended_videos
+        .into_iter()
+        .map(|video| Item::from_video_short(video, 4))
+        .next()
+    else {
+        panic!("no related videos");
+    };
+    assert_eq!(related.channel_id, CHANNEL);
+    assert_eq!(related.views.as_deref(), Some("180M"));
+}
+
+#[test]
+fn search_by_query() {
+    let items = provider().search(&search("rick astley", 1)).unwrap();
+    assert!(matches!(
+        items.as_slice(),
+        [
+            SearchItem::Channel(_),
+            SearchItem::Video(_),
+            SearchItem::Playlist(_)
+        ]
+    ));
+}
+
+#[test]
+fn search_fallback() {
+    let provider = provider();
+
+    // queries without their own file use `search.json`
+    let items = provider.search(&search("anything else", 1)).unwrap();
+    assert_eq!(items.len(), 1);
+
+    // pages that are not captured are empty
+    assert!(provider
+        .search(&search("rick astley", 2))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn suggestions() {
+    let provider = provider();
+    assert_eq!(provider.suggestions("rick").unwrap().suggestions.len(), 3);
+
+    // queries that are not captured have no suggestions
+    assert!(provider
+        .suggestions("anything else")
+        .unwrap()
+        .suggestions
+        .is_empty());
+}
+
+#[test]
+fn trending_and_popular() {
+    let provider = provider();
+    assert_eq!(provider.trending(None).unwrap().videos.len(), 2);
+
+    let popular = provider.popular().unwrap();
+    assert_eq!(popular[0].id, VIDEO);
+}
+
+#[test]
+fn comments() {
+    let provider = provider();
+
+    let comments = provider.comments(VIDEO, None).unwrap();
+    assert_eq!(comments.comments.len(), 2);
+
+    let continuation = comments.comments[0]
+        .replies
+        .as_ref()
+        .unwrap()
+        .continuation
+        .clone();
+    let replies = provider.comments(VIDEO, Some(&continuation)).unwrap();
+    assert_eq!(replies.comments[0].content, "A reply");
+    assert!(replies.continuation.is_none());
+}
+
+#[test]
+fn captions() {
+    let provider = provider();
+
+    let captions = provider.captions(VIDEO).unwrap();
+    assert_eq!(captions[0].language, "en");
+
+    let transcript = provider.transcript(&captions[0]).unwrap();
+    assert_eq!(transcript.cues.len(), 2);
+    assert_eq!(transcript.cues[0].text, "We're no strangers to love");
+}
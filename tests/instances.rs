@@ -0,0 +1,219 @@
+//! runs the Invidious instance pool against stand-in instances on localhost
+
+#![cfg(feature = "invidious")]
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread,
+    time::Duration,
+};
+
+use youtube_tui::global::{
+    structs::{InstancePool, InvidiousClient},
+    traits::SearchProviderTrait,
+};
+
+const STATS: &str = r#"{"version":"2.0","software":{"name":"invidious","version":"2024.01.01"}}"#;
+
+/// the pool is global, so only one test can use it at a time
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// a stand-in instance answering requests with `respond(path)`, counts API requests other than
+/// `/api/v1/stats`
+fn serve(respond: fn(&str) -> (u16, &'static str)) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buffer = [0; 4096];
+            let len = stream.read(&mut buffer).unwrap_or_default();
+            let request = String::from_utf8_lossy(&buffer[..len]);
+            let path = request.split_whitespace().nth(1).unwrap_or("/");
+            if !path.starts_with("/api/v1/stats") {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+
+            let (status, body) = respond(path);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+
+    (url, hits)
+}
+
+fn healthy(path: &str) -> (u16, &'static str) {
+    if path.starts_with("/api/v1/stats") {
+        (200, STATS)
+    } else {
+        (200, "[]")
+    }
+}
+
+/// a blocked instance, which answers with a html page
+fn blocked(_: &str) -> (u16, &'static str) {
+    (502, "<html>Bad Gateway</html>")
+}
+
+fn unavaliable(path: &str) -> (u16, &'static str) {
+    if path.starts_with("/api/v1/stats") {
+        (200, STATS)
+    } else {
+        (500, r#"{"error":"This video is unavailable"}"#)
+    }
+}
+
+/// an instance which refuses connections, low ports are never bound by other tests
+fn dead(port: u16) -> String {
+    format!("http://127.0.0.1:{port}")
+}
+
+fn pool(urls: &[&String]) -> MutexGuard<'static, ()> {
+    let lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut pool = InstancePool::global();
+    // forget the health of instances from other tests
+    pool.set_instances(Vec::new(), Duration::from_secs(2));
+    pool.set_instances(
+        urls.iter().map(|url| url.to_string()).collect(),
+        Duration::from_secs(2),
+    );
+    pool.pinned = None;
+    pool.last_used = None;
+    lock
+}
+
+#[test]
+fn probe() {
+    let timeout = Duration::from_secs(2);
+    let (up, _) = serve(healthy);
+    let (html, _) = serve(|_| (200, "<html></html>"));
+    let (down, _) = serve(blocked);
+
+    assert!(InstancePool::probe(&up, timeout).is_ok());
+    assert_eq!(
+        InstancePool::probe(&html, timeout),
+        Err(String::from("Not an Invidious API response"))
+    );
+    assert_eq!(
+        InstancePool::probe(&down, timeout),
+        Err(String::from("HTTP 502"))
+    );
+    assert!(InstancePool::probe(&dead(1), timeout).is_err());
+}
+
+#[test]
+fn probe_all() {
+    let (up, _) = serve(healthy);
+    let (down, _) = serve(blocked);
+    let dead = dead(1);
+    let _lock = pool(&[&dead, &down, &up]);
+
+    assert_eq!(InstancePool::probe_all(), (1, 3));
+
+    let pool = InstancePool::global();
+    assert_eq!(pool.order()[0], up);
+    assert_eq!(pool.instances[1].healthy, Some(false));
+    assert_eq!(pool.instances[1].last_error.as_deref(), Some("HTTP 502"));
+}
+
+#[test]
+fn failover() {
+    let (down, down_hits) = serve(blocked);
+    let (up, up_hits) = serve(healthy);
+    let dead = dead(1);
+    let _lock = pool(&[&dead, &down, &up]);
+
+    let client = InvidiousClient::pool();
+    assert!(client.trending(None).unwrap().videos.is_empty());
+    assert_eq!(down_hits.load(Ordering::SeqCst), 1);
+    assert_eq!(up_hits.load(Ordering::SeqCst), 1);
+
+    {
+        let pool = InstancePool::global();
+        assert_eq!(pool.last_used.as_ref(), Some(&up));
+        assert_eq!(pool.instances[0].healthy, Some(false));
+        assert_eq!(pool.instances[0].failures, 1);
+        assert_eq!(pool.order()[0], up);
+    }
+
+    // instances that failed are tried last from now on
+    assert!(client.trending(None).is_ok());
+    assert_eq!(down_hits.load(Ordering::SeqCst), 1);
+    assert_eq!(up_hits.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn timeout() {
+    // accepts connections but never responds
+    let hanging = TcpListener::bind("127.0.0.1:0").unwrap();
+    let hanging_url = format!("http://{}", hanging.local_addr().unwrap());
+    let (up, _) = serve(healthy);
+    let _lock = pool(&[&hanging_url, &up]);
+    InstancePool::global().timeout = Duration::from_millis(300);
+
+    assert!(InvidiousClient::pool().trending(None).is_ok());
+    assert_eq!(InstancePool::global().last_used.as_ref(), Some(&up));
+}
+
+#[test]
+fn api_errors_are_not_retried() {
+    let (unavaliable, _) = serve(unavaliable);
+    let (up, up_hits) = serve(healthy);
+    let _lock = pool(&[&unavaliable, &up]);
+
+    let err = InvidiousClient::pool().video("removed").err().unwrap();
+    assert!(err.to_string().contains("This video is unavailable"));
+    assert_eq!(up_hits.load(Ordering::SeqCst), 0);
+    assert_eq!(InstancePool::global().instances[0].healthy, Some(true));
+}
+
+#[test]
+fn pinned() {
+    let (first, first_hits) = serve(healthy);
+    let (second, second_hits) = serve(healthy);
+    let lock = pool(&[&first, &second]);
+
+    assert_eq!(InstancePool::global().pin("2"), Ok(second.clone()));
+    assert_eq!(InstancePool::global().order(), vec![second.clone()]);
+    assert!(InstancePool::global()
+        .pin("https://unknown.example")
+        .is_err());
+
+    assert!(InvidiousClient::pool().trending(None).is_ok());
+    assert_eq!(first_hits.load(Ordering::SeqCst), 0);
+    assert_eq!(second_hits.load(Ordering::SeqCst), 1);
+
+    // a pinned instance is used even when it is down
+    let dead = dead(1);
+    drop(lock);
+    let _lock = pool(&[&first, &dead]);
+    InstancePool::global().pin(&dead).unwrap();
+    assert!(InvidiousClient::pool().trending(None).is_err());
+    assert_eq!(first_hits.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn all_failed() {
+    let first = dead(1);
+    let second = dead(2);
+    let _lock = pool(&[&first, &second]);
+
+    let err = InvidiousClient::pool()
+        .trending(None)
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(err.starts_with("All Invidious instances failed"));
+    assert!(err.contains(&first) && err.contains(&second));
+}
@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+
+use youtube_tui::global::{
+    structs::{
+        Cue, FixtureProvider, Item, Library, LocalIndex, SubItem, Subscriptions, Transcript,
+        WatchHistory,
+    },
+    traits::SearchProviderTrait,
+};
+
+const VIDEO: &str = "dQw4w9WgXcQ";
+const CHANNEL: &str = "UCuAXFkgsw1L7xaCfnd5JJOw";
+const PLAYLIST: &str = "PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI";
+
+fn fixtures() -> FixtureProvider {
+    FixtureProvider::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+}
+
+fn ids(items: &[Item]) -> Vec<&str> {
+    items.iter().map(|item| item.id().unwrap()).collect()
+}
+
+/// a video with only a title and description
+fn video(id: &str, title: &str, description: &str) -> Item {
+    let mut video = fixtures().video(VIDEO).unwrap();
+    video.id = id.to_string();
+    video.title = title.to_string();
+    video.author = "Someone".to_string();
+    video.description = description.to_string();
+    Item::from_full_video(video, 0)
+}
+
+#[test]
+fn matches_every_field() {
+    let mut index = LocalIndex::default();
+    index.add(video("title", "Cooking pasta", ""), None);
+    index.add(video("description", "Episode 1", "how to cook pasta"), None);
+    index.add(
+        video("transcript", "Episode 2", ""),
+        Some(&Transcript {
+            language: "en".to_string(),
+            cues: vec![Cue {
+                start: 0.,
+                end: 1.,
+                text: "today we make pasta".to_string(),
+            }],
+        }),
+    );
+
+    // titles rank above descriptions, which rank above transcripts
+    assert_eq!(
+        ids(&index.search("pasta")),
+        ["title", "description", "transcript"]
+    );
+    assert_eq!(ids(&index.search("someone")).len(), 3);
+    assert!(index.search("pizza").is_empty());
+    assert!(index.search("  ").is_empty());
+}
+
+#[test]
+fn all_terms_and_prefixes() {
+    let mut index = LocalIndex::default();
+    index.add(video("both", "Cooking pasta", ""), None);
+    index.add(video("one", "Cooking rice", ""), None);
+
+    assert_eq!(ids(&index.search("cooking PASTA")), ["both"]);
+    assert_eq!(ids(&index.search("cook")), ["both", "one"]);
+
+    // whole words rank above words that only start with the term
+    index.add(video("whole", "How to cook", ""), None);
+    assert_eq!(ids(&index.search("cook")), ["whole", "both", "one"]);
+}
+
+#[test]
+fn build_from_saved_items() {
+    let fixtures = fixtures();
+    let full_video = Item::from_full_video(fixtures.video(VIDEO).unwrap(), 0);
+    let playlist = Item::from_full_playlist(fixtures.playlist(PLAYLIST, None).unwrap(), 0);
+    let channel = Item::from_full_channel(fixtures.channel(CHANNEL).unwrap(), 0);
+    let videos = fixtures.channel_videos(CHANNEL, None).unwrap().videos;
+
+    let subscriptions = Subscriptions(vec![SubItem {
+        channel: channel.fullchannel().unwrap().clone(),
+        videos: videos
+            .into_iter()
+            .map(|video| Item::from_common_video(video, 0).into_minivideo().unwrap())
+            .collect(),
+        last_sync: 0,
+        last_sync_channel: 0,
+        has_new: false,
+    }]);
+
+    // the video is both in the library and the watch history, but only shows up once
+    let index = LocalIndex::build(
+        &Library(vec![full_video.clone()]),
+        &WatchHistory(vec![full_video.clone(), playlist.clone()]),
+        &subscriptions,
+        "en",
+    );
+    let results = index.search("never gonna give");
+    assert_eq!(
+        ids(&results).iter().filter(|id| **id == VIDEO).count(),
+        1,
+        "{:?}",
+        ids(&results)
+    );
+
+    assert!(
+        ids(&index.search(playlist.fullplaylist().unwrap().title.as_str())).contains(&PLAYLIST)
+    );
+    assert!(ids(&index.search(channel.fullchannel().unwrap().name.as_str())).contains(&CHANNEL));
+    assert!(index.len() > 3);
+}
+
+#[test]
+fn updates_after_build() {
+    let mut index = LocalIndex::default();
+    // items are not pushed until the first search builds the index
+    index.push(&video("early", "Cooking pasta", ""));
+    assert!(!index.is_built() && index.is_empty());
+
+    let mut index = LocalIndex::build(
+        &Library::default(),
+        &WatchHistory::default(),
+        &Subscriptions::default(),
+        "en",
+    );
+    index.push(&video("pushed", "Cooking pasta", ""));
+    index.push(&video("pushed", "Cooking pasta", ""));
+    assert_eq!(ids(&index.search("pasta")), ["pushed"]);
+
+    let transcript = Transcript {
+        language: "en".to_string(),
+        cues: vec![Cue {
+            start: 0.,
+            end: 1.,
+            text: "boil the water".to_string(),
+        }],
+    };
+    index.add_transcript("pushed", &transcript);
+    index.add_transcript("missing", &transcript);
+    assert_eq!(ids(&index.search("boil")), ["pushed"]);
+
+    index.invalidate();
+    assert!(!index.is_built());
+}
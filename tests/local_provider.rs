@@ -0,0 +1,178 @@
+use std::{env, fs, path::PathBuf};
+
+use youtube_tui::{
+    global::{
+        structs::{FixtureProvider, Item, LocalProvider, SubItem, Subscriptions},
+        traits::SearchProviderTrait,
+    },
+};
+
+const VIDEO: &str = "dQw4w9WgXcQ";
+const CHANNEL: &str = "UCuAXFkgsw1L7xaCfnd5JJOw";
+const PLAYLIST: &str = "PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI";
+
+fn fixtures() -> FixtureProvider {
+    FixtureProvider::new(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+}
+
+/// a data directory with the fixture video, playlist and channel viewed, and the channel
+/// subscribed to
+fn data_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!(
+        "youtube-tui-local-provider-{}-{name}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("info")).unwrap();
+
+    let fixtures = fixtures();
+    let items = [
+        Item::from_full_video(fixtures.video(VIDEO).unwrap(), 0),
+        Item::from_full_playlist(fixtures.playlist(PLAYLIST, None).unwrap(), 0),
+        Item::from_full_channel(fixtures.channel(CHANNEL).unwrap(), 0),
+    ];
+    for item in items.iter() {
+        fs::write(
+            dir.join("info")
+                .join(format!("{}.json", item.id().unwrap())),
+            serde_json::to_string(item).unwrap(),
+        )
+        .unwrap();
+    }
+
+    let subscriptions = Subscriptions(vec![SubItem {
+        channel: items[2].fullchannel().unwrap().clone(),
+        videos: fixtures
+            .channel_videos(CHANNEL, None)
+            .unwrap()
+            .videos
+            .into_iter()
+            .map(|video| Item::from_common_video(video, 0).into_minivideo().unwrap())
+            .collect(),
+        last_sync: 0,
+        last_sync_channel: 0,
+        has_new: false,
+    }]);
+    fs::write(
+        dir.join("subscriptions.json"),
+        serde_json::to_string(&subscriptions).unwrap(),
+    )
+    .unwrap();
+
+    dir
+}
+
+#[test]
+fn saved_items_load_as_they_were() {
+    let dir = data_dir("items");
+    let provider = LocalProvider::new(dir.clone(), None);
+    let fixtures = fixtures();
+
+    // converting back and forth shows the same text as when the item was viewed
+    let original = Item::from_full_video(fixtures.video(VIDEO).unwrap(), 0);
+    let video = Item::from_full_video(provider.video(VIDEO).unwrap(), 0);
+    assert_eq!(
+        serde_json::to_string(&video).unwrap(),
+        serde_json::to_string(&original).unwrap()
+    );
+
+    let original = Item::from_full_channel(fixtures.channel(CHANNEL).unwrap(), 0);
+    let channel = Item::from_full_channel(provider.channel(CHANNEL).unwrap(), 0);
+    assert_eq!(
+        serde_json::to_string(&channel).unwrap(),
+        serde_json::to_string(&original).unwrap()
+    );
+
+    let playlist = provider.playlist(PLAYLIST, None).unwrap();
+    assert_eq!(playlist.id, PLAYLIST);
+    assert_eq!(
+        playlist.videos.len(),
+        fixtures.playlist(PLAYLIST, None).unwrap().videos.len()
+    );
+    assert!(provider
+        .playlist(PLAYLIST, Some("next"))
+        .unwrap()
+        .videos
+        .is_empty());
+
+    assert!(provider
+        .video("unknown")
+        .unwrap_err()
+        .to_string()
+        .contains("not saved for offline use"));
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn channel_videos_come_from_subscriptions() {
+    let dir = data_dir("subscriptions");
+    let provider = LocalProvider::new(dir.clone(), None);
+    let original = fixtures().channel_videos(CHANNEL, None).unwrap().videos;
+
+    let videos = provider.channel_videos(CHANNEL, None).unwrap();
+    assert_eq!(videos.videos.len(), original.len());
+    assert!(videos.continuation.is_none());
+    for (video, original) in videos.videos.iter().zip(original.iter()) {
+        assert_eq!(video.id, original.id);
+        assert_eq!(video.length, original.length);
+        assert_eq!(video.published, original.published);
+    }
+
+    // videos of channels that are not subscribed to are not saved
+    assert!(provider.channel_videos("UCother", None).is_err());
+
+    // videos that were only seen in the feed can still be opened
+    let video = provider.video(&original[0].id).unwrap();
+    assert_eq!(video.title, original[0].title);
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn library_files_without_info() {
+    let dir = data_dir("library");
+    let library = dir.join("saved");
+    fs::create_dir_all(&library).unwrap();
+    fs::write(library.join("A saved video[abcdefghijk].webm"), "").unwrap();
+
+    let provider = LocalProvider::new(dir.clone(), Some(library));
+    let video = provider.video("abcdefghijk").unwrap();
+    assert_eq!(video.title, "A saved video");
+    assert_eq!(video.id, "abcdefghijk");
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn downloaded_captions() {
+    let dir = data_dir("captions");
+    fs::create_dir_all(dir.join("captions")).unwrap();
+    fs::write(
+        dir.join("captions").join(format!("{VIDEO}.en.vtt")),
+        "WEBVTT\n\n00:00:01.000 --> 00:00:02.500\nhello\n",
+    )
+    .unwrap();
+
+    let provider = LocalProvider::new(dir.clone(), None);
+    let captions = provider.captions(VIDEO).unwrap();
+    assert_eq!(captions.len(), 1);
+    assert_eq!(captions[0].language, "en");
+
+    let transcript = provider.transcript(&captions[0]).unwrap();
+    assert_eq!(transcript.cues[0].text, "hello");
+
+    assert!(provider.captions("unknown").is_err());
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn network_only_operations_are_unsupported() {
+    let provider = LocalProvider::new(env::temp_dir(), None);
+    assert!(!provider.supports_search());
+    assert!(!provider.supports_trending());
+    assert!(!provider.supports_popular());
+    assert!(!provider.supports_comments());
+    assert!(!provider.supports_mix());
+}
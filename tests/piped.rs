@@ -0,0 +1,359 @@
+//! runs the Piped client against a stand-in Piped API on localhost
+
+#![cfg(feature = "piped")]
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use youtube_tui::{
+    config::{Search, SearchFilterType},
+    global::{common::hidden::SearchItem, structs::PipedClient, traits::SearchProviderTrait},
+};
+
+const VIDEO: &str = "dQw4w9WgXcQ";
+const CHANNEL: &str = "UCuAXFkgsw1L7xaCfnd5JJOw";
+const PLAYLIST: &str = "PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI";
+
+const STREAM: &str = r#"{
+    "url": "/watch?v=dQw4w9WgXcQ",
+    "type": "stream",
+    "title": "Never Gonna Give You Up",
+    "thumbnail": "https://piped.example/vi/dQw4w9WgXcQ/hqdefault.jpg",
+    "uploaderName": "Rick Astley",
+    "uploaderUrl": "/channel/UCuAXFkgsw1L7xaCfnd5JJOw",
+    "uploadedDate": "14 years ago",
+    "shortDescription": "The official video",
+    "duration": 212,
+    "views": 1500000000,
+    "uploaded": 1256428800000,
+    "uploaderVerified": true,
+    "isShort": false
+}"#;
+
+const LIVE: &str = r#"{
+    "url": "/watch?v=live0000000",
+    "type": "stream",
+    "title": "Live radio",
+    "duration": -1,
+    "views": 10,
+    "uploaded": -1
+}"#;
+
+const CHANNEL_ITEM: &str = r#"{
+    "url": "/channel/UCuAXFkgsw1L7xaCfnd5JJOw",
+    "type": "channel",
+    "name": "Rick Astley",
+    "thumbnail": "https://piped.example/avatar.jpg",
+    "subscribers": 4000000,
+    "videos": 300,
+    "verified": true
+}"#;
+
+const PLAYLIST_ITEM: &str = r#"{
+    "url": "/playlist?list=PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI",
+    "type": "playlist",
+    "name": "Hits",
+    "thumbnail": "https://piped.example/playlist.jpg",
+    "uploaderName": "Rick Astley",
+    "uploaderUrl": "/channel/UCuAXFkgsw1L7xaCfnd5JJOw",
+    "videos": 2
+}"#;
+
+/// a stand-in Piped API, records the paths it is asked for
+fn serve() -> (PipedClient, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let paths = Arc::new(Mutex::new(Vec::new()));
+    let log = paths.clone();
+
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buffer = [0; 4096];
+            let len = stream.read(&mut buffer).unwrap_or_default();
+            let request = String::from_utf8_lossy(&buffer[..len]);
+            let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+            log.lock().unwrap().push(path.clone());
+
+            let (status, body) = respond(&path);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+
+    (PipedClient::new(url, Duration::from_secs(2)), paths)
+}
+
+fn respond(path: &str) -> (u16, String) {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    match path {
+        "/search" => (
+            200,
+            format!(
+                r#"{{"items":[{CHANNEL_ITEM},{STREAM},{PLAYLIST_ITEM},{{"type":"unknown"}}],"nextpage":null}}"#
+            ),
+        ),
+        "/suggestions" => (200, String::from(r#"["rick astley","rick roll"]"#)),
+        "/trending" => (200, format!("[{STREAM},{LIVE}]")),
+        "/streams/dQw4w9WgXcQ" => (
+            200,
+            format!(
+                r#"{{
+                    "title": "Never Gonna Give You Up",
+                    "description": "The official video<br>for &quot;Never Gonna Give You Up&quot; <a href=\"https://example.com\">link</a>",
+                    "uploadDate": "2009-10-25T06:57:33.000Z",
+                    "uploader": "Rick Astley",
+                    "uploaderUrl": "/channel/UCuAXFkgsw1L7xaCfnd5JJOw",
+                    "uploaderAvatar": "https://piped.example/avatar.jpg",
+                    "thumbnailUrl": "https://piped.example/vi/dQw4w9WgXcQ/maxresdefault.jpg",
+                    "category": "Music",
+                    "tags": ["rick astley"],
+                    "duration": 212,
+                    "views": 1500000000,
+                    "likes": 17000000,
+                    "dislikes": -1,
+                    "uploaderSubscriberCount": 4000000,
+                    "livestream": false,
+                    "subtitles": [{{"url": "https://piped.example/subs", "mimeType": "text/vtt", "name": "English", "code": "en", "autoGenerated": true}}],
+                    "relatedStreams": [{LIVE}]
+                }}"#
+            ),
+        ),
+        "/streams/removed" => (
+            500,
+            String::from(r#"{"error":"java.lang.Exception: ...","message":"Video unavailable"}"#),
+        ),
+        "/channel/UCuAXFkgsw1L7xaCfnd5JJOw" => (
+            200,
+            format!(
+                r#"{{
+                    "id": "UCuAXFkgsw1L7xaCfnd5JJOw",
+                    "name": "Rick Astley",
+                    "avatarUrl": "https://piped.example/avatar.jpg",
+                    "bannerUrl": "https://piped.example/banner.jpg",
+                    "description": "Official channel",
+                    "subscriberCount": 4000000,
+                    "verified": true,
+                    "relatedStreams": [{STREAM},{LIVE}],
+                    "nextpage": "channel-page-2",
+                    "tabs": [{{"name":"shorts","data":"shorts-data"}},{{"name":"playlists","data":"{{\"id\":\"playlists\"}}"}}]
+                }}"#
+            ),
+        ),
+        "/nextpage/channel/UCuAXFkgsw1L7xaCfnd5JJOw" => (
+            200,
+            format!(r#"{{"relatedStreams":[{LIVE}],"nextpage":null}}"#),
+        ),
+        // the first page of the tab has a next page
+        "/channels/tabs" if !query.contains("nextpage=") => (
+            200,
+            format!(r#"{{"content":[{PLAYLIST_ITEM}],"nextpage":"tabs-page-2"}}"#),
+        ),
+        "/channels/tabs" => (
+            200,
+            format!(r#"{{"content":[{PLAYLIST_ITEM}],"nextpage":null}}"#),
+        ),
+        "/nextpage/playlists/PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI" => (
+            200,
+            format!(r#"{{"relatedStreams":[{STREAM}],"nextpage":null}}"#),
+        ),
+        "/playlists/PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI" => (
+            200,
+            format!(
+                r#"{{
+                    "name": "Hits",
+                    "thumbnailUrl": "https://piped.example/playlist.jpg",
+                    "uploader": "Rick Astley",
+                    "uploaderUrl": "/channel/UCuAXFkgsw1L7xaCfnd5JJOw",
+                    "videos": 3,
+                    "relatedStreams": [{STREAM},{LIVE}],
+                    "nextpage": "playlist-page-2"
+                }}"#
+            ),
+        ),
+        _ => (404, String::from("<html>Not Found</html>")),
+    }
+}
+
+#[test]
+fn search() {
+    let (client, paths) = serve();
+    let items = client
+        .search(&Search {
+            query: String::from("rick astley"),
+            ..Default::default()
+        })
+        .unwrap();
+
+    // items of unknown types are left out
+    let [SearchItem::Channel(channel), SearchItem::Video(video), SearchItem::Playlist(playlist)] =
+        items.as_slice()
+    else {
+        panic!("unexpected search results");
+    };
+    assert_eq!(channel.id, CHANNEL);
+    assert_eq!(channel.subscribers, 4000000);
+    assert_eq!(video.id, VIDEO);
+    assert_eq!(video.author_id, CHANNEL);
+    assert_eq!(video.published, 1256428800);
+    assert_eq!(playlist.id, PLAYLIST);
+    assert_eq!(playlist.video_count, 2);
+
+    assert_eq!(
+        paths.lock().unwrap().as_slice(),
+        ["/search?q=rick%20astley&filter=all"]
+    );
+}
+
+#[test]
+fn search_filter_type() {
+    let (client, paths) = serve();
+    let mut search = Search {
+        query: String::from("rick"),
+        ..Default::default()
+    };
+    search.filters.r#type = SearchFilterType::Channel;
+    client.search(&search).unwrap();
+
+    assert_eq!(
+        paths.lock().unwrap().as_slice(),
+        ["/search?q=rick&filter=channels"]
+    );
+}
+
+#[test]
+fn suggestions() {
+    let (client, paths) = serve();
+    let suggestions = client.suggestions("rick a").unwrap();
+
+    assert_eq!(suggestions.query, "rick a");
+    assert_eq!(suggestions.suggestions, ["rick astley", "rick roll"]);
+    assert_eq!(
+        paths.lock().unwrap().as_slice(),
+        ["/suggestions?query=rick%20a"]
+    );
+}
+
+#[test]
+fn trending() {
+    let (client, paths) = serve();
+    let trending = client.trending(None).unwrap();
+    let videos = trending.videos;
+
+    assert_eq!(videos.len(), 2);
+    assert!(trending.continuation.is_none());
+    assert!(!videos[0].live);
+    assert_eq!(videos[0].length, 212);
+    assert!(videos[1].live);
+    assert_eq!(videos[1].author, "Unknown channel");
+    assert_eq!(paths.lock().unwrap().as_slice(), ["/trending?region=US"]);
+}
+
+#[test]
+fn video() {
+    let (client, _) = serve();
+    let video = client.video(VIDEO).unwrap();
+
+    assert_eq!(video.id, VIDEO);
+    assert_eq!(video.author_id, CHANNEL);
+    assert_eq!(
+        video.description,
+        "The official video\nfor \"Never Gonna Give You Up\" link"
+    );
+    assert_eq!(video.published, 1256428800);
+    assert_eq!(video.genre, "Music");
+    assert_eq!(video.dislikes, 0);
+    assert_eq!(video.captions[0].label, "English (auto-generated)");
+    assert_eq!(video.captions[0].language, "en");
+    assert_eq!(video.recommended_videos[0].id, "live0000000");
+}
+
+#[test]
+fn errors() {
+    let (client, _) = serve();
+
+    let err = client.video("removed").err().unwrap();
+    assert_eq!(err.to_string(), "Piped: Video unavailable");
+
+    let err = client.playlist("missing", None).err().unwrap();
+    assert_eq!(err.to_string(), "Piped: HTTP 404");
+}
+
+#[test]
+fn channel() {
+    let (client, _) = serve();
+
+    let channel = client.channel(CHANNEL).unwrap();
+    assert_eq!(channel.name, "Rick Astley");
+    assert_eq!(channel.banners.len(), 1);
+    assert_eq!(channel.latest_videos.len(), 2);
+
+    let videos = client.channel_videos(CHANNEL, None).unwrap();
+    assert_eq!(videos.videos[0].id, VIDEO);
+    assert_eq!(videos.continuation.as_deref(), Some("channel-page-2"));
+}
+
+#[test]
+fn channel_videos_continuation() {
+    let (client, paths) = serve();
+    let videos = client
+        .channel_videos(CHANNEL, Some("channel-page-2"))
+        .unwrap();
+
+    assert_eq!(videos.videos[0].id, "live0000000");
+    assert!(videos.continuation.is_none());
+    assert_eq!(
+        paths.lock().unwrap().as_slice(),
+        ["/nextpage/channel/UCuAXFkgsw1L7xaCfnd5JJOw?nextpage=channel-page-2"]
+    );
+}
+
+#[test]
+fn channel_playlists() {
+    let (client, paths) = serve();
+    let playlists = client.channel_playlists(CHANNEL, None).unwrap();
+
+    assert_eq!(playlists.playlists.len(), 1);
+    assert_eq!(playlists.playlists[0].id, PLAYLIST);
+    // the playlists tab is requested with the data from the channel
+    assert_eq!(
+        paths.lock().unwrap().last().unwrap(),
+        "/channels/tabs?data=%7B%22id%22%3A%22playlists%22%7D"
+    );
+
+    // the tab data is kept in the continuation for the next page
+    let next = client
+        .channel_playlists(CHANNEL, playlists.continuation.as_deref())
+        .unwrap();
+    assert_eq!(next.playlists.len(), 1);
+    assert!(next.continuation.is_none());
+    assert_eq!(
+        paths.lock().unwrap().last().unwrap(),
+        "/channels/tabs?data=%7B%22id%22%3A%22playlists%22%7D&nextpage=tabs-page-2"
+    );
+}
+
+#[test]
+fn playlist() {
+    let (client, _) = serve();
+    let playlist = client.playlist(PLAYLIST, None).unwrap();
+
+    assert_eq!(playlist.title, "Hits");
+    assert_eq!(playlist.author_id, CHANNEL);
+    assert_eq!(playlist.videos.len(), 2);
+    assert_eq!(playlist.videos[1].index, 1);
+    assert_eq!(playlist.videos[1].id, "live0000000");
+
+    let next = client
+        .playlist(PLAYLIST, playlist.continuation.as_deref())
+        .unwrap();
+    assert_eq!(next.videos.len(), 1);
+    assert!(next.continuation.is_none());
+}
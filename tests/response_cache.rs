@@ -0,0 +1,127 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use youtube_tui::{
+    config::CacheConfig,
+    global::structs::{CacheStats, Cached, ResponseCache},
+};
+
+fn dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!(
+        "youtube-tui-response-cache-{}-{name}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+/// videos are fresh for 5 minutes, then stale for 10
+fn config(max_size: u64) -> CacheConfig {
+    CacheConfig {
+        ttl: HashMap::from([(String::from("video"), 300)]),
+        stale: 600,
+        max_size,
+    }
+}
+
+/// makes every response in the cache `secs` seconds old
+fn age(dir: &Path, secs: u64) {
+    for entry in fs::read_dir(dir).unwrap() {
+        fs::File::options()
+            .write(true)
+            .open(entry.unwrap().path())
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(secs))
+            .unwrap();
+    }
+}
+
+#[test]
+fn fresh_and_stale() {
+    let dir = dir("fresh_and_stale");
+    let cache = ResponseCache::new(dir.clone(), config(100));
+    cache
+        .put(
+            "video",
+            "dQw4w9WgXcQ",
+            &String::from("Never Gonna Give You Up"),
+        )
+        .unwrap();
+
+    assert_eq!(
+        cache.get::<String>("video", "dQw4w9WgXcQ"),
+        Cached::Fresh(String::from("Never Gonna Give You Up"))
+    );
+    assert_eq!(cache.get::<String>("video", "yPYZpwSpKmA"), Cached::Missing);
+
+    age(&dir, 400);
+    assert_eq!(
+        cache.get::<String>("video", "dQw4w9WgXcQ"),
+        Cached::Stale(String::from("Never Gonna Give You Up"))
+    );
+
+    age(&dir, 1000);
+    assert_eq!(cache.get::<String>("video", "dQw4w9WgXcQ"), Cached::Missing);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn uncached_operations() {
+    let dir = dir("uncached_operations");
+    let cache = ResponseCache::new(dir.clone(), config(100));
+    cache.put("trending", "", &String::from("videos")).unwrap();
+
+    assert_eq!(cache.get::<String>("trending", ""), Cached::Missing);
+    assert_eq!(cache.stats(), CacheStats::default());
+}
+
+#[test]
+fn max_size() {
+    let dir = dir("max_size");
+    let cache = ResponseCache::new(dir.clone(), config(1));
+    let value = "a".repeat(600_000);
+
+    cache.put("video", "dQw4w9WgXcQ", &value).unwrap();
+    age(&dir, 10);
+    // the oldest response is removed to make space
+    cache.put("video", "yPYZpwSpKmA", &value).unwrap();
+
+    assert_eq!(cache.get::<String>("video", "dQw4w9WgXcQ"), Cached::Missing);
+    assert_eq!(
+        cache.get::<String>("video", "yPYZpwSpKmA"),
+        Cached::Fresh(value)
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn stable_file_names() {
+    let dir = dir("stable_file_names");
+    let cache = ResponseCache::new(dir.clone(), config(100));
+    cache.put("video", "dQw4w9WgXcQ", &1).unwrap();
+
+    // responses written by older versions are still found after updating
+    assert!(dir.join("video-a09eed42613321dd.json").exists());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn stats_and_clear() {
+    let dir = dir("stats_and_clear");
+    let cache = ResponseCache::new(dir.clone(), config(100));
+    cache.put("video", "dQw4w9WgXcQ", &1).unwrap();
+    age(&dir, 400);
+    cache.put("video", "yPYZpwSpKmA", &2).unwrap();
+
+    let stats = cache.stats();
+    assert_eq!((stats.responses, stats.stale), (2, 1));
+    assert!(stats.bytes > 0);
+
+    assert_eq!(cache.clear().unwrap(), 2);
+    assert_eq!(cache.stats(), CacheStats::default());
+    assert_eq!(cache.clear().unwrap(), 0);
+}
@@ -0,0 +1,412 @@
+//! renders pages with the fixture provider on a `TestBackend`, and compares the screen against
+//! `tests/snapshots/{name}.txt`
+//!
+//! run with `UPDATE_SNAPSHOTS=1` to write new snapshots after an intended layout change
+
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::{Mutex, MutexGuard, Once},
+    thread,
+    time::Duration,
+};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use tui_additions::framework::{Framework, State};
+use youtube_tui::{
+    global::structs::{BackgroundFetch, PageLoader, SearchSuggestions, Task, Tasks},
+    handle_event, init, poll_background_fetch, poll_page_load, run_tasks,
+};
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 36;
+
+/// the app uses global state, so only one harness can exist at a time
+static LOCK: Mutex<()> = Mutex::new(());
+static SETUP: Once = Once::new();
+
+fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+fn temp_dir() -> PathBuf {
+    env::temp_dir().join(format!("youtube-tui-snapshots-{}", std::process::id()))
+}
+
+/// points config and data directories to an empty temporary directory, with the fixture
+/// provider and images disabled
+fn setup() {
+    SETUP.call_once(|| {
+        let dir = temp_dir();
+        let _ = fs::remove_dir_all(&dir);
+        let config = dir.join("config");
+        fs::create_dir_all(config.join("youtube-tui")).unwrap();
+
+        env::set_var("HOME", &dir);
+        env::set_var("XDG_CONFIG_HOME", &config);
+        env::set_var("XDG_DATA_HOME", dir.join("data"));
+        env::set_var("XDG_CACHE_HOME", dir.join("cache"));
+
+        fs::write(
+            config.join("youtube-tui/main.yml"),
+            format!(
+                "images: None\nsearch_provider: Fixture\nfixture_dir: {}\n",
+                root().join("fixtures").display()
+            ),
+        )
+        .unwrap();
+    });
+}
+
+struct Harness {
+    framework: Framework,
+    terminal: Terminal<TestBackend>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Harness {
+    /// starts the app with a launch command, like `youtube-tui [command]`
+    fn new(command: &str) -> Self {
+        let lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        setup();
+
+        let mut harness = Self {
+            framework: Framework::new(State(Vec::new())),
+            terminal: Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap(),
+            _lock: lock,
+        };
+        init(&mut harness.framework, &mut harness.terminal, Some(command)).unwrap();
+        harness.run_tasks();
+        harness
+    }
+
+    /// runs tasks, then waits for the page being loaded and background fetches like the main loop
+    /// does
+    fn run_tasks(&mut self) {
+        run_tasks(&mut self.terminal, &mut self.framework).unwrap();
+        while self.pending() {
+            thread::sleep(Duration::from_millis(10));
+            if self
+                .framework
+                .data
+                .global
+                .get::<PageLoader>()
+                .unwrap()
+                .pending()
+            {
+                poll_page_load(&mut self.framework);
+            }
+            poll_background_fetch(&mut self.framework);
+            run_tasks(&mut self.terminal, &mut self.framework).unwrap();
+        }
+    }
+
+    fn pending(&self) -> bool {
+        let global = &self.framework.data.global;
+        global.get::<PageLoader>().unwrap().pending()
+            || global.get::<BackgroundFetch>().unwrap().pending()
+    }
+
+    fn key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> &mut Self {
+        self.press_no_wait(code, modifiers);
+        self.run_tasks();
+        self
+    }
+
+    /// leaves the page it loads pending
+    fn press_no_wait(&mut self, code: KeyCode, modifiers: KeyModifiers) -> &mut Self {
+        handle_event(
+            Event::Key(KeyEvent::new(code, modifiers)),
+            &mut self.terminal,
+            &mut self.framework,
+        );
+        run_tasks(&mut self.terminal, &mut self.framework).unwrap();
+        self
+    }
+
+    fn press(&mut self, code: KeyCode) -> &mut Self {
+        self.key(code, KeyModifiers::NONE)
+    }
+
+    fn type_str(&mut self, s: &str) -> &mut Self {
+        for c in s.chars() {
+            self.press(KeyCode::Char(c));
+        }
+        self
+    }
+
+    /// waits for the suggestions worker, like the main loop does
+    fn wait_for_suggestions(&mut self) -> &mut Self {
+        let global = &mut self.framework.data.global;
+        while global.get::<SearchSuggestions>().unwrap().pending() {
+            thread::sleep(Duration::from_millis(10));
+            global.get_mut::<SearchSuggestions>().unwrap().receive();
+        }
+
+        self.framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::RenderAll);
+        self.run_tasks();
+        self
+    }
+
+    /// the rendered screen as text, with trailing spaces removed
+    fn screen(&self) -> String {
+        let buffer = self.terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n"
+    }
+
+    fn assert_snapshot(&self, name: &str) {
+        let path = root().join("snapshots").join(format!("{name}.txt"));
+        let screen = self.screen();
+
+        if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &screen).unwrap();
+            return;
+        }
+
+        // a missing snapshot fails instead of being written, so a renamed or deleted file is noticed
+        let Ok(expected) = fs::read_to_string(&path) else {
+            panic!("snapshot `{name}` is missing, run with UPDATE_SNAPSHOTS=1 to write it\n\nfound:\n{screen}");
+        };
+        assert!(
+            expected == screen,
+            "snapshot `{name}` does not match, run with UPDATE_SNAPSHOTS=1 if the change is intended\n\nexpected:\n{expected}\nfound:\n{screen}"
+        );
+    }
+}
+
+#[test]
+fn trending() {
+    Harness::new("loadpage trending").assert_snapshot("trending");
+}
+
+#[test]
+fn popular() {
+    Harness::new("loadpage popular").assert_snapshot("popular");
+}
+
+#[test]
+fn search() {
+    Harness::new("loadpage search rick astley").assert_snapshot("search");
+}
+
+#[test]
+fn video() {
+    Harness::new("loadpage video dQw4w9WgXcQ").assert_snapshot("video");
+}
+
+#[test]
+fn playlist() {
+    Harness::new("loadpage playlist PLFgquLnL59alCl_2TQvOiD5Vgm1hCaGSI")
+        .assert_snapshot("playlist");
+}
+
+#[test]
+fn related() {
+    Harness::new("loadpage related dQw4w9WgXcQ").assert_snapshot("related");
+}
+
+#[test]
+fn mix() {
+    Harness::new(
+        "loadpage playlist https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=RDdQw4w9WgXcQ",
+    )
+    .assert_snapshot("mix");
+}
+
+#[test]
+fn channel() {
+    Harness::new("loadpage channel UCuAXFkgsw1L7xaCfnd5JJOw").assert_snapshot("channel");
+}
+
+#[test]
+fn channel_search() {
+    let mut harness = Harness::new("loadpage channel UCuAXFkgsw1L7xaCfnd5JJOw");
+    // deselect the channel page, hover the search field and search in it
+    harness
+        .press(KeyCode::Esc)
+        .press(KeyCode::Up)
+        .press(KeyCode::Right)
+        .press(KeyCode::Right)
+        .press(KeyCode::Right)
+        .press(KeyCode::Right)
+        .press(KeyCode::Right)
+        .press(KeyCode::Right)
+        .press(KeyCode::Enter)
+        .type_str("together")
+        .press(KeyCode::Enter);
+    harness.assert_snapshot("channel_search");
+
+    // the same page from the command
+    let screen = harness.screen();
+    drop(harness);
+    let harness = Harness::new("loadpage channelsearch UCuAXFkgsw1L7xaCfnd5JJOw together");
+    assert_eq!(harness.screen(), screen);
+}
+
+#[test]
+fn navigate_list() {
+    let mut harness = Harness::new("loadpage trending");
+    harness.press(KeyCode::Down).press(KeyCode::Down);
+    harness.assert_snapshot("trending_down");
+}
+
+#[test]
+fn command_bar() {
+    let mut harness = Harness::new("loadpage trending");
+    harness
+        .press(KeyCode::Char(':'))
+        .type_str("loadpage popular");
+    harness.assert_snapshot("command_bar");
+
+    harness.press(KeyCode::Enter);
+    harness.assert_snapshot("command_bar_popular");
+}
+
+#[test]
+fn cancel_load() {
+    let mut harness = Harness::new("loadpage trending");
+    harness
+        .press(KeyCode::Char(':'))
+        .type_str("loadpage popular")
+        .press_no_wait(KeyCode::Enter, KeyModifiers::NONE);
+    // the responses are not picked up until the page is polled, so the load is still pending
+    // after its worker is done
+    thread::sleep(Duration::from_millis(200));
+
+    // goes back to trending
+    harness.press(KeyCode::Esc);
+    assert!(!harness
+        .framework
+        .data
+        .global
+        .get::<PageLoader>()
+        .unwrap()
+        .pending());
+    harness.assert_snapshot("cancel_load");
+}
+
+#[test]
+fn search_filters() {
+    let mut harness = Harness::new("loadpage trending");
+    // deselect the list, hover the filters button and open the popup
+    harness
+        .press(KeyCode::Esc)
+        .press(KeyCode::Up)
+        .press(KeyCode::Up)
+        .press(KeyCode::Right)
+        .press(KeyCode::Enter);
+    harness.assert_snapshot("search_filters");
+}
+
+#[test]
+fn channel_videos_load_more() {
+    let mut harness = Harness::new("loadpage channel UCuAXFkgsw1L7xaCfnd5JJOw");
+    // deselect the channel page, hover the videos button and open it
+    harness
+        .press(KeyCode::Esc)
+        .press(KeyCode::Up)
+        .press(KeyCode::Right)
+        .press(KeyCode::Enter);
+    harness.assert_snapshot("channel_videos");
+
+    // reaching the last video loads the next page
+    harness.press(KeyCode::Down);
+    harness.assert_snapshot("channel_videos_more");
+}
+
+#[test]
+fn channel_streams() {
+    let mut harness = Harness::new("loadpage channel UCuAXFkgsw1L7xaCfnd5JJOw");
+    // deselect the channel page, hover the streams button and open it
+    harness
+        .press(KeyCode::Esc)
+        .press(KeyCode::Up)
+        .press(KeyCode::Right)
+        .press(KeyCode::Right)
+        .press(KeyCode::Right)
+        .press(KeyCode::Enter);
+    harness.assert_snapshot("channel_streams");
+}
+
+#[test]
+fn block() {
+    let mut harness = Harness::new("loadpage trending");
+    // hides the hovered video, with a rule saved to `filters.yml`
+    harness
+        .press(KeyCode::Char(':'))
+        .type_str("block title")
+        .press(KeyCode::Enter);
+
+    // other tests use the same config directory
+    let path = temp_dir().join("config/youtube-tui/filters.yml");
+    let rules = fs::read_to_string(&path).unwrap();
+    fs::remove_file(path).unwrap();
+
+    assert!(rules.contains(r"Never Gonna Give You Up \(Official Music Video\)$"));
+    harness.assert_snapshot("block");
+}
+
+#[test]
+fn localsearch() {
+    // opening the video adds it to the watch history
+    Harness::new("loadpage video dQw4w9WgXcQ")
+        .press(KeyCode::Char(':'))
+        .type_str("localsearch astley")
+        .press(KeyCode::Enter)
+        .assert_snapshot("localsearch");
+}
+
+#[test]
+fn search_suggestions() {
+    let mut harness = Harness::new("loadpage trending");
+    // deselect the list, hover the search bar and select it
+    harness
+        .press(KeyCode::Esc)
+        .press(KeyCode::Up)
+        .press(KeyCode::Up)
+        .press(KeyCode::Enter)
+        .type_str("rick")
+        .wait_for_suggestions();
+    harness.assert_snapshot("search_suggestions");
+
+    // searches for the highlighted suggestion
+    harness.press(KeyCode::Down).press(KeyCode::Enter);
+    harness.assert_snapshot("search_suggestions_search");
+
+    // the search is now in the history, which comes before the provider suggestions
+    harness
+        .press(KeyCode::Esc)
+        .press(KeyCode::Up)
+        .press(KeyCode::Up)
+        .press(KeyCode::Enter)
+        .key(KeyCode::Char('u'), KeyModifiers::CONTROL)
+        .type_str("rick a")
+        .wait_for_suggestions();
+    harness.assert_snapshot("search_suggestions_history");
+}
+
+#[test]
+fn blank_search_suggestions() {
+    // nothing is fetched for a blank query, so the main loop does not wait for it
+    let mut suggestions = SearchSuggestions::default();
+    suggestions.request("  ");
+    assert!(!suggestions.pending());
+}